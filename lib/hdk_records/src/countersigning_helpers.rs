@@ -0,0 +1,153 @@
+/**
+ * Helpers for writing entries which are countersigned by multiple agents.
+ *
+ * Countersigning proceeds in three steps:
+ *
+ * 1. The proposing agent builds a `PreflightRequest` for the entry and accepts it.
+ *    The serialized entry data is carried in the request's preflight bytes.
+ * 2. The request is relayed to all other signing agents, who inspect the entry
+ *    and accept the request, locking their source chains for the session.
+ * 3. Once every agent's `PreflightResponse` has been gathered, all agents commit
+ *    the entry along with the full set of responses before the session expires.
+ *
+ * Holochain does not permit any other writes in the zome call which commits a
+ * countersigned entry, so any links or dependant records must be written afterwards.
+ *
+ * @package HDK Graph Helpers
+ * @since   2026-10-18
+ */
+use hdk::prelude::*;
+use hdk::prelude::create as hdk_create;
+
+use crate::{
+    RecordAPIResult, DataIntegrityError,
+    entry_helpers::{try_entry_from_record, try_decode_entry},
+};
+
+//--------------------------------[ READ ]--------------------------------------
+
+/// Decode the entry data being countersigned in a session request.
+///
+/// Errors if the preflight bytes do not match the entry hash the request was made for.
+///
+pub fn read_countersigning_request_entry<R>(request: &PreflightRequest) -> RecordAPIResult<R>
+    where SerializedBytes: TryInto<R, Error = SerializedBytesError>,
+{
+    let entry = Entry::App(AppEntryBytes(SerializedBytes::from(UnsafeBytes::from(request.preflight_bytes().0.to_owned()))));
+    if hash_entry(entry.to_owned())? != *request.app_entry_hash() {
+        return Err(DataIntegrityError::CountersigningError("preflight request does not match the entry being countersigned".to_string()));
+    }
+    try_decode_entry(entry)
+}
+
+/// Retrieve the agents who countersigned the entry written by the given `ActionHash`.
+/// Entries which were not countersigned yield an empty list.
+///
+pub fn read_countersigning_agents(address: &ActionHash) -> RecordAPIResult<Vec<AgentPubKey>> {
+    let record = match get(address.to_owned(), GetOptions { strategy: GetStrategy::Latest }) {
+        Ok(Some(el)) => el,
+        _ => return Err(DataIntegrityError::EntryNotFound),
+    };
+
+    Ok(match try_entry_from_record(&record)? {
+        Entry::CounterSign(session, _) => session.preflight_request().signing_agents().iter()
+            .map(|(agent, _roles)| agent.to_owned())
+            .collect(),
+        _ => vec![],
+    })
+}
+
+//-------------------------------[ CREATE ]-------------------------------------
+
+/// Build a request for `signing_agents` to countersign `entry_struct` as a new entry,
+/// within a session lasting `session_millis` from now.
+///
+pub fn build_countersigning_request<T, I: Clone, E>(
+    entry_struct: I,
+    signing_agents: Vec<AgentPubKey>,
+    session_millis: u64,
+) -> RecordAPIResult<PreflightRequest>
+    where WasmError: From<E>,
+        Entry: TryFrom<I, Error = E>,
+        T: From<I>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a T, Error = E>,
+        EntryVisibility: for<'a> From<&'a T>,
+{
+    let (ScopedEntryDefIndex { zome_index, zome_type }, visibility, entry_bytes) = encode_app_entry::<T, _, _>(entry_struct)?;
+    let entry_type = EntryType::App(AppEntryDef::new(zome_type, zome_index, visibility));
+
+    PreflightRequest::try_new(
+        hash_entry(Entry::App(entry_bytes.to_owned()))?,
+        signing_agents.into_iter().map(|agent| (agent, vec![])).collect(),
+        None,
+        session_times_from_millis(session_millis)?,
+        ActionBase::Create(CreateBase::new(entry_type)),
+        PreflightBytes(entry_bytes.into_sb().bytes().to_owned()),
+    ).map_err(|e| DataIntegrityError::CountersigningError(e.to_string()))
+}
+
+/// Accept a countersigning session request on behalf of the current agent.
+/// This locks the agent's source chain until the session completes or expires.
+///
+pub fn accept_countersigning_request(request: PreflightRequest) -> RecordAPIResult<PreflightResponse> {
+    match accept_countersigning_preflight_request(request)? {
+        PreflightRequestAcceptance::Accepted(response) => Ok(response),
+        PreflightRequestAcceptance::UnacceptableFutureStart => Err(DataIntegrityError::CountersigningError("session starts too far in the future".to_string())),
+        PreflightRequestAcceptance::UnacceptableAgentNotFound => Err(DataIntegrityError::CountersigningError("current agent is not a party to the session".to_string())),
+        PreflightRequestAcceptance::Invalid(e) => Err(DataIntegrityError::CountersigningError(e)),
+    }
+}
+
+/// Commit the countersigned `entry_struct` to the current agent's source chain, once
+/// `responses` have been gathered from all signing agents. Returns a tuple of the
+/// written `Record` signature and the `EntryHash` it was stored under.
+///
+pub fn create_countersigned_entry<T, I: Clone, E>(
+    entry_struct: I,
+    responses: Vec<PreflightResponse>,
+) -> RecordAPIResult<(SignedActionHashed, EntryHash)>
+    where WasmError: From<E>,
+        Entry: TryFrom<I, Error = E>,
+        T: From<I>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a T, Error = E>,
+        EntryVisibility: for<'a> From<&'a T>,
+{
+    let session = CounterSigningSessionData::try_from_responses(responses)
+        .map_err(|e| DataIntegrityError::CountersigningError(e.to_string()))?;
+    let (ScopedEntryDefIndex { zome_index, zome_type }, visibility, entry_bytes) = encode_app_entry::<T, _, _>(entry_struct)?;
+
+    let action_hash = hdk_create(CreateInput::new(
+        EntryDefLocation::app(zome_index, zome_type),
+        visibility,
+        Entry::CounterSign(Box::new(session), entry_bytes),
+        ChainTopOrdering::Strict,
+    ))?;
+
+    let record = match get(action_hash, GetOptions { strategy: GetStrategy::Latest }) {
+        Ok(Some(el)) => el,
+        _ => return Err(DataIntegrityError::EntryNotFound),
+    };
+    let entry_hash = record.action().entry_hash().ok_or(DataIntegrityError::EntryNotFound)?.to_owned();
+
+    Ok((record.signed_action().to_owned(), entry_hash))
+}
+
+/// Use conversion traits to determine the HDK `EntryTypes` def for input entry data, and encode it
+fn encode_app_entry<T, I: Clone, E>(
+    entry_struct: I,
+) -> RecordAPIResult<(ScopedEntryDefIndex, EntryVisibility, AppEntryBytes)>
+    where WasmError: From<E>,
+        Entry: TryFrom<I, Error = E>,
+        T: From<I>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a T, Error = E>,
+        EntryVisibility: for<'a> From<&'a T>,
+{
+    let wrapped_entry_struct: T = entry_struct.to_owned().into();
+    let entry_def_index: ScopedEntryDefIndex = (&wrapped_entry_struct).try_into().map_err(|e: E| DataIntegrityError::Wasm(e.into()))?;
+    let visibility = EntryVisibility::from(&wrapped_entry_struct);
+
+    match entry_struct.try_into().map_err(|e: E| DataIntegrityError::Wasm(e.into()))? {
+        Entry::App(entry_bytes) => Ok((entry_def_index, visibility, entry_bytes)),
+        _ => Err(DataIntegrityError::EntryWrongType),
+    }
+}
@@ -36,7 +36,7 @@ pub (crate) fn try_decode_entry<T>(entry: Entry) -> RecordAPIResult<T>
     where SerializedBytes: TryInto<T, Error = SerializedBytesError>,
 {
     match entry {
-        Entry::App(content) => {
+        Entry::App(content) | Entry::CounterSign(_, content) => {
             let decoded: T = content.into_sb().try_into()?;
             Ok(decoded)
         },
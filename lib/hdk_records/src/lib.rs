@@ -26,6 +26,8 @@ mod record_helpers;
 mod anchored_record_helpers;
 mod rpc_helpers;
 mod metadata_helpers;
+mod validation_helpers;
+mod countersigning_helpers;
 
 // API interfaces
 
@@ -38,6 +40,8 @@ pub mod entries { pub use crate::entry_helpers::*; }
 pub mod records { pub use crate::record_helpers::*; }
 pub mod records_anchored { pub use crate::anchored_record_helpers::*; }
 pub mod rpc { pub use crate::rpc_helpers::*; }
+pub mod validation { pub use crate::validation_helpers::*; }
+pub mod countersigning { pub use crate::countersigning_helpers::*; }
 pub mod metadata { pub use crate::metadata_helpers::*; }
 
 // externally-facing structs
@@ -85,6 +89,42 @@ pub enum DataIntegrityError {
     LocalIndexNotConfigured(String, String),
     #[error("Mismatching units in arithmetic operation. Attempting to add or subtract {0:?} with {1:?}")]
     MismatchingUnits(Option<String>, Option<String>),
+    #[error("Invalid decimal quantity: {0}")]
+    InvalidDecimal(String),
+    #[error("Incompatible units in arithmetic operation. Cannot convert between dimensions {0:?} and {1:?}")]
+    IncompatibleUnitDimensions(String, String),
+    #[error("Invalid unit conversion: {0}")]
+    InvalidUnitConversion(String),
+    #[error("Unit {0} has no registered dimension and conversion factor, so quantities cannot be converted to or from it")]
+    UnregisteredUnitConversion(String),
+    #[error("No standard unit is defined with symbol {0}")]
+    UnknownBuiltinUnit(String),
+    #[error("No recipe found producing resources conforming to {0}")]
+    RecipeNotFound(String),
+    #[error("Proposal cannot be accepted: {0}")]
+    ProposalNotAcceptable(String),
+    #[error("Failed to accept proposal: {0}. Records which could not be rolled back: {1:?}")]
+    ProposalAcceptanceFailed(String, Vec<String>),
+    #[error("EconomicEvent history cannot be rewritten: {0}")]
+    EventHistoryImmutable(String),
+    #[error("'{0}' events which have altered the location or accountability of inventoried resources cannot be corrected")]
+    UncorrectableEvent(String),
+    #[error("EconomicEvent {0} may only be corrected by its original author or a record administrator")]
+    CorrectionNotPermitted(String),
+    #[error("EconomicEvent does not exactly reverse the event it corrects: {0}")]
+    InvalidReversal(String),
+    #[error("Invalid system time: {0}")]
+    InvalidTimestamp(String),
+    #[error("Transfer of EconomicResource {0} requires the consent of its primary accountable agent")]
+    TransferConsentRequired(String),
+    #[error("Countersigning session error: {0}")]
+    CountersigningError(String),
+    #[error("EconomicEvent would reduce the {0} quantity of an EconomicResource below zero, to {1}")]
+    NegativeInventoryBalance(String, String),
+    #[error("A unit of measure is required for the {0}")]
+    MissingInventoryUnit(String),
+    #[error("EconomicEvent conforms to ResourceSpecification {0} but the EconomicResource it affects conforms to {1:?}")]
+    MismatchingResourceSpecification(String, Option<String>),
 }
 
 pub type RecordAPIResult<T> = Result<T, DataIntegrityError>;
@@ -71,6 +71,20 @@ pub fn get_latest_action_hash(entry_hash: EntryHash) -> RecordAPIResult<ActionHa
     }
 }
 
+/// Retrieve the `Create` action which originally authored the entry at `entry_hash`.
+///
+/// Where identical entry content has been written more than once, the earliest creation is returned.
+///
+pub fn get_creating_action(entry_hash: &EntryHash) -> RecordAPIResult<SignedActionHashed> {
+    match get_details(entry_hash.to_owned(), GetOptions { strategy: GetStrategy::Latest })? {
+        Some(Details::Entry(details)) => details.actions.into_iter()
+            .filter(|action| matches!(action.action(), Action::Create(_)))
+            .min_by_key(|action| action.action().timestamp().as_micros())
+            .ok_or(DataIntegrityError::EntryNotFound),
+        _ => Err(DataIntegrityError::EntryNotFound),
+    }
+}
+
 /// Retrive the specific version of an entry specified by the given `ActionHash`
 ///
 pub fn read_record_entry_by_action<T, R, B>(
@@ -83,7 +97,26 @@ pub fn read_record_entry_by_action<T, R, B>(
         R: std::fmt::Debug + Identified<T, B>,
 {
     let (meta, storage_entry): (_, R) = get_entry_by_action(&action_hash)?;
-    Ok((meta, storage_entry.identity()?, storage_entry.entry()))
+    let identity = identity_of_revision(&meta, &storage_entry)?;
+    Ok((meta, identity, storage_entry.entry()))
+}
+
+/// Determine the identity of a record from any revision of its entry data.
+///
+/// Initial revisions are identified by the `EntryHash` of their `Create` action, which
+/// differs from the hash of the entry data alone where the entry was countersigned.
+///
+fn identity_of_revision<T, R, B>(
+    meta: &SignedActionHashed,
+    storage_entry: &R,
+) -> RecordAPIResult<B>
+    where B: DnaAddressable<EntryHash>,
+        R: Identified<T, B>,
+{
+    match meta.action() {
+        Action::Create(create) => Ok(B::new(dna_info()?.hash, create.entry_hash.to_owned())),
+        _ => storage_entry.identity(),
+    }
 }
 
 /// Read a record's entry data by its identity index
@@ -199,9 +232,9 @@ pub fn update_record<I, R: Clone, B, U, E>(
         SerializedBytes: TryInto<R, Error = SerializedBytesError>,
 {
     // get referenced entry for the given action
-    let (_meta, previous): (_, R) = get_entry_by_action(address)?;
+    let (prev_meta, previous): (_, R) = get_entry_by_action(address)?;
     let prev_entry = previous.entry();
-    let identity = previous.identity()?;
+    let identity = identity_of_revision(&prev_meta, &previous)?;
     let identity_hash: &EntryHash = identity.as_ref();
 
     // apply update payload
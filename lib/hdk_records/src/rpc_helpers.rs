@@ -10,13 +10,26 @@
 
 use hdk::prelude::*;
 use holo_hash::DnaHash;
-use hc_zome_dna_auth_resolver_lib::{DNAConnectionAuth, ensure_authed, AvailableCapability};
+use hc_zome_dna_auth_resolver_lib::{DNAConnectionAuth, ensure_authed};
+// re-exported so that wrappers of `call_zome_method` can declare the same constraints upon entry types
+pub use hc_zome_dna_auth_resolver_lib::AvailableCapability;
 
 use crate::{
     OtherCellResult,
     CrossCellError,
 };
 
+/**
+ * Allows cells identified only by their `DnaHash` to be targeted by `call_zome_method`.
+ */
+pub struct CellDna<'a>(pub &'a DnaHash);
+
+impl<'a> AsRef<DnaHash> for CellDna<'a> {
+    fn as_ref(&self) -> &DnaHash {
+        self.0
+    }
+}
+
 /**
  * Wrapper for `hdk::call` which handles decoding of the response and coercion of error types.
  */
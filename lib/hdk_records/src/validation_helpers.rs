@@ -0,0 +1,75 @@
+/**
+ * Helpers for validating permission to modify records.
+ *
+ * Records may only be updated or deleted by the agent who originally authored them,
+ * or by one of the agents listed as `record_administrators` in DNA properties:
+ *
+ * ```yaml
+ * properties:
+ *   record_permissions:
+ *     record_administrators:
+ *       - uhCAk...
+ * ```
+ *
+ * @package hREA
+ * @since   2026-10-18
+ */
+use hdk::prelude::*;
+use holo_hash::AgentPubKeyB64;
+
+/// DNA-wide configuration for record modification permissions
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug, Default)]
+pub struct RecordPermissionsConfig {
+    #[serde(default)]
+    pub record_administrators: Vec<AgentPubKeyB64>,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+struct DnaConfigSlice {
+    #[serde(default)]
+    pub record_permissions: RecordPermissionsConfig,
+}
+
+/// Read the agents permitted to modify records authored by others.
+/// DNAs which do not declare any are treated as having no administrators; a malformed
+/// `record_permissions` block is an error, rather than silently revoking all administrators.
+pub fn get_record_administrators() -> ExternResult<Vec<AgentPubKey>> {
+    let conf: DnaConfigSlice = dna_info()?.properties.try_into()
+        .map_err(|e: SerializedBytesError| wasm_error!(WasmErrorInner::Guest(format!("Invalid record_permissions in DNA properties: {}", e))))?;
+    Ok(conf.record_permissions.record_administrators.into_iter().map(AgentPubKey::from).collect())
+}
+
+/// Validate that `author` may modify a record originally written by `original_author`
+pub fn validate_record_author(author: &AgentPubKey, original_author: &AgentPubKey) -> ExternResult<ValidateCallbackResult> {
+    if author == original_author || get_record_administrators()?.contains(author) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    Ok(ValidateCallbackResult::Invalid("Records may only be modified by their original author or a record administrator".to_string()))
+}
+
+/// Determine the agent who created a record, following `action` back through any prior updates
+/// to the `Create` action which began the record's revision history
+pub fn get_creating_author(action: &EntryCreationAction) -> ExternResult<AgentPubKey> {
+    let mut original_action_address = match action {
+        EntryCreationAction::Create(create) => return Ok(create.author.to_owned()),
+        EntryCreationAction::Update(update) => update.original_action_address.to_owned(),
+    };
+    loop {
+        let original = must_get_action(original_action_address)?;
+        match original.action() {
+            Action::Create(create) => return Ok(create.author.to_owned()),
+            Action::Update(update) => original_action_address = update.original_action_address.to_owned(),
+            _ => return Err(wasm_error!(WasmErrorInner::Guest("Record revisions must originate from a Create action".to_string()))),
+        }
+    }
+}
+
+/// Validate a `RegisterUpdate` op against the author who created the record being updated
+pub fn validate_update_authorship(op: &RegisterUpdate) -> ExternResult<ValidateCallbackResult> {
+    validate_record_author(&op.update.hashed.content.author, &get_creating_author(&op.original_action)?)
+}
+
+/// Validate a `RegisterDelete` op against the author who created the record being deleted
+pub fn validate_delete_authorship(op: &RegisterDelete) -> ExternResult<ValidateCallbackResult> {
+    validate_record_author(&op.delete.hashed.content.author, &get_creating_author(&op.original_action)?)
+}
@@ -41,6 +41,7 @@ use hdk_semantic_indexes_zome_rpc::{
     ByAddress,
     RemoteEntryLinkRequest, RemoteEntryLinkResponse,
 };
+use hdk_relay_pagination::PageInfo;
 use hc_zome_dna_auth_resolver_lib::AvailableCapability;
 
 //-------------------------------[ MACRO LAYER ]-------------------------------------
@@ -435,6 +436,85 @@ pub fn read_local_index<'a, O, A, S, F, C>(
     )?)
 }
 
+/// Inputs for reading a page of results from the `query_*` or `read_all_*` endpoint of an
+/// index zome. `params` should be `None` when calling `read_all_*` endpoints.
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PagedQueryInputs<Q> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Q>,
+    pub first: Option<usize>,
+    pub after: Option<String>,
+    pub last: Option<usize>,
+    pub before: Option<String>,
+}
+
+/// A page of results returned from the `query_*` or `read_all_*` endpoint of an index zome.
+///
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PagedQueryResults<T> {
+    pub page_info: PageInfo,
+    #[serde(default)]
+    pub edges: Vec<PagedQueryEdge<T>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PagedQueryEdge<T> {
+    pub node: T,
+}
+
+/// Reads every page of results from an index zome query, from most recent to oldest.
+///
+/// `read_page` performs the zome call for each page, given the `PagedQueryInputs` for it.
+///
+pub fn read_all_pages<Q, T, F>(
+    params: Option<Q>,
+    page_size: usize,
+    read_page: F,
+) -> OtherCellResult<Vec<T>>
+    where Q: Clone,
+        F: FnMut(PagedQueryInputs<Q>) -> OtherCellResult<PagedQueryResults<T>>,
+{
+    read_pages(params, page_size, usize::MAX, read_page)
+}
+
+/// Reads pages of results from an index zome query, from most recent to oldest, until
+/// `max_results` have been read or there are no further results.
+///
+/// @see read_all_pages
+///
+pub fn read_pages<Q, T, F>(
+    params: Option<Q>,
+    page_size: usize,
+    max_results: usize,
+    mut read_page: F,
+) -> OtherCellResult<Vec<T>>
+    where Q: Clone,
+        F: FnMut(PagedQueryInputs<Q>) -> OtherCellResult<PagedQueryResults<T>>,
+{
+    let mut results = vec![];
+    let mut before = None;
+
+    while results.len() < max_results {
+        let PagedQueryResults { page_info, edges } = read_page(PagedQueryInputs {
+            params: params.to_owned(),
+            first: None,
+            after: None,
+            last: Some(std::cmp::min(page_size, max_results - results.len())),
+            before,
+        })?;
+        let page_empty = edges.is_empty();
+        results.extend(edges.into_iter().map(|e| e.node));
+        if page_empty || !page_info.has_next_page {
+            break;
+        }
+        before = Some(page_info.end_cursor);
+    }
+
+    Ok(results)
+}
+
 //-------------------------------[ UPDATE ]-------------------------------------
 
 /// Ask another bridged cell to build a 'destination query index' to match the
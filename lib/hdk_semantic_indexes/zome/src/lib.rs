@@ -5,9 +5,10 @@
  * @package hdk_semantic_indexes
  * @since   2021-09-30
  */
+use std::collections::HashSet;
 use chrono::{DateTime, Utc};
 use hdk::prelude::*;
-use holo_hash::{DnaHash, HOLO_HASH_FULL_LEN};
+use holo_hash::{DnaHash, EntryHashB64, HOLO_HASH_FULL_LEN};
 use hdk_records::{
     identities::calculate_identity_address,
     rpc::call_local_zome_method,
@@ -17,8 +18,10 @@ pub use hdk_semantic_indexes_error::*;
 pub use hdk_time_indexing::{
     TimeIndex,
     read_all_entry_hashes,
-    // get_latest_entry_hashes,
-    // get_older_entry_hashes,
+    get_latest_entry_hashes,
+    get_earliest_entry_hashes,
+    get_older_entry_hashes,
+    get_newer_entry_hashes,
     sort_entries_by_time_index,
 };
 pub use hdk_records::{
@@ -42,6 +45,76 @@ pub struct IndexingZomeConfig {
     pub record_storage_zome: String,
 }
 
+//--------------------------------[ PAGINATION ]--------------------------------
+
+/// Query input parameters mimicing Relay's pagination spec.
+/// @see https://relay.dev/graphql/connections.htm
+///
+/// Results are always ordered from most recent to oldest, and cursors are those returned
+/// alongside each result. Provide `before` (usually the previous page's `endCursor`) to read
+/// up to `last` results older than the cursor, or `after` (usually the previous page's
+/// `startCursor`) to read up to `first` results newer than it. Without cursors, `last` reads
+/// the most recent results and `first` reads the earliest.
+///
+/// If both forwards (`first` / `after`) and backwards (`last` / `before`) parameters are
+/// provided, the forwards parameters take precedence.
+///
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PagingParams {
+    pub first: Option<usize>,
+    pub after: Option<String>,
+    pub last: Option<usize>,
+    pub before: Option<String>,
+}
+
+impl PagingParams {
+    /// Whether these parameters request results forwards in time
+    pub fn is_forwards(&self) -> bool {
+        self.first.is_some() || self.after.is_some()
+    }
+
+    /// Number of results requested, or `default_page_size` if the caller did not specify
+    pub fn get_limit(&self, default_page_size: usize) -> usize {
+        let requested = if self.is_forwards() { self.first } else { self.last };
+        requested.unwrap_or(default_page_size)
+    }
+}
+
+/// A page of query results, each paired with its pagination cursor.
+///
+/// `has_previous_page` indicates that there are newer results preceding the page, and
+/// `has_next_page` that there are older results following it.
+///
+#[derive(Debug)]
+pub struct QueryPage<T> {
+    pub results: Vec<RecordAPIResult<(String, T)>>,
+    pub has_previous_page: bool,
+    pub has_next_page: bool,
+    pub page_limit: Option<usize>,
+}
+
+/// A relationship to filter index queries by, matching all records linked from
+/// `base_address` via `link_tag`.
+///
+#[derive(Debug, Clone)]
+pub struct IndexQueryFilter {
+    base_address: EntryHash,
+    link_tag: LinkTag,
+}
+
+impl IndexQueryFilter {
+    pub fn new<A, S>(base_address: &A, link_tag: &S) -> RecordAPIResult<Self>
+        where A: DnaAddressable<EntryHash>,
+            S: AsRef<[u8]> + ?Sized,
+    {
+        Ok(Self {
+            base_address: calculate_identity_address(base_address)?,
+            link_tag: LinkTag::new(link_tag.as_ref()),
+        })
+    }
+}
+
 //--------------------------------[ READ ]--------------------------------------
 
 /// Reads and returns all entry identities referenced by the given index from
@@ -114,11 +187,194 @@ pub fn query_index<'a, T, O, C, F, A, S, I, J>(
     Ok(entries)
 }
 
+/// Query foreign records matching all of the given `filters`, in order from most recent to
+/// oldest as determined by `order_by_time_index`.
+///
+/// Only the records linked from *every* filter's base address are matched. A page of at most
+/// `paging.get_limit(default_page_size)` of them is returned, starting from the position in the
+/// results determined by `paging`. @see `PagingParams`
+///
+/// Full entry data for the page is returned by querying from the associated record storage zome
+/// determined by `foreign_zome_name_from_config` and `foreign_read_method_name`.
+///
+pub fn query_filtered_index<'a, T, B, C, F, I, J>(
+    filters: &[IndexQueryFilter],
+    order_by_time_index: &I,
+    foreign_zome_name_from_config: &'a F,
+    foreign_read_method_name: &'a J,
+    paging: &PagingParams,
+    default_page_size: usize,
+) -> RecordAPIResult<QueryPage<T>>
+    where I: AsRef<str> + std::fmt::Debug,
+        J: AsRef<str>,
+        B: DnaAddressable<EntryHash>,
+        T: serde::de::DeserializeOwned + std::fmt::Debug,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        F: Fn(C) -> Option<String>,
+{
+    if filters.is_empty() {
+        return Err(SemanticIndexError::EmptyQuery.into());
+    }
+
+    let matching_addresses = get_intersecting_linked_addresses(filters)?;
+
+    read_address_page::<T, B, _,_,_,_>(
+        matching_addresses, order_by_time_index,
+        foreign_zome_name_from_config, foreign_read_method_name,
+        paging, default_page_size,
+    )
+}
+
+/// Query foreign records related to `base_address` by some relationship which is derived by the
+/// associated record storage zome rather than stored in an index, in order from most recent to
+/// oldest as determined by `order_by_time_index`.
+///
+/// The related identities are determined by calling `derive_method_name` in the record storage
+/// zome with `base_address`, which must return a list of `B`. Paging and record retrieval
+/// then proceed as for `query_filtered_index`.
+///
+pub fn query_derived_index<'a, T, A, B, C, F, I, J, K>(
+    base_address: &A,
+    derive_method_name: &K,
+    order_by_time_index: &I,
+    foreign_zome_name_from_config: &'a F,
+    foreign_read_method_name: &'a J,
+    paging: &PagingParams,
+    default_page_size: usize,
+) -> RecordAPIResult<QueryPage<T>>
+    where I: AsRef<str> + std::fmt::Debug,
+        J: AsRef<str>,
+        K: AsRef<str>,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash> + serde::de::DeserializeOwned,
+        T: serde::de::DeserializeOwned + std::fmt::Debug,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        F: Fn(C) -> Option<String>,
+{
+    let related: Vec<B> = call_local_zome_method(
+        foreign_zome_name_from_config.to_owned(), derive_method_name,
+        ByAddress { address: base_address.to_owned() },
+    )?;
+
+    let mut seen = HashSet::new();
+    let related_addresses = related.iter()
+        .map(|addr| { let hash: &EntryHash = addr.as_ref(); hash.to_owned() })
+        .filter(|hash| seen.insert(hash.to_owned()))
+        .collect();
+
+    read_address_page::<T, B, _,_,_,_>(
+        related_addresses, order_by_time_index,
+        foreign_zome_name_from_config, foreign_read_method_name,
+        paging, default_page_size,
+    )
+}
+
+/// Sorts `addresses` from most recent to oldest by `order_by_time_index`, then reads the
+/// foreign records for the page of them selected by `paging`.
+///
+fn read_address_page<'a, T, B, C, F, I, J>(
+    mut addresses: Vec<EntryHash>,
+    order_by_time_index: &I,
+    foreign_zome_name_from_config: &'a F,
+    foreign_read_method_name: &'a J,
+    paging: &PagingParams,
+    default_page_size: usize,
+) -> RecordAPIResult<QueryPage<T>>
+    where I: AsRef<str> + std::fmt::Debug,
+        J: AsRef<str>,
+        B: DnaAddressable<EntryHash>,
+        T: serde::de::DeserializeOwned + std::fmt::Debug,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        F: Fn(C) -> Option<String>,
+{
+    addresses.sort_by(sort_entries_by_time_index(order_by_time_index));
+
+    let page_limit = paging.get_limit(default_page_size);
+    let (page_addresses, has_previous_page, has_next_page) = paginate_addresses(&addresses, paging, page_limit)?;
+
+    let read_single_record = retrieve_foreign_record::<T, B, _,_,_>(foreign_zome_name_from_config, foreign_read_method_name);
+
+    Ok(QueryPage {
+        results: page_addresses.iter()
+            .map(read_single_record)
+            .collect(),
+        has_previous_page,
+        has_next_page,
+        page_limit: Some(page_limit),
+    })
+}
+
+/// Determine the identity addresses linked from the base addresses of all the given `filters`.
+///
+/// Addresses are returned in the order they are linked from the first filter, without duplicates.
+///
+fn get_intersecting_linked_addresses(filters: &[IndexQueryFilter]) -> RecordAPIResult<Vec<EntryHash>>
+{
+    let mut filter_results = filters.iter()
+        .map(|filter| get_linked_addresses(&filter.base_address, filter.link_tag.to_owned()));
+
+    let mut matches = match filter_results.next() {
+        None => return Ok(vec![]),
+        Some(result) => result?,
+    };
+    let mut seen = HashSet::new();
+    matches.retain(|addr| seen.insert(addr.to_owned()));
+
+    for result in filter_results {
+        // stop reading further indexes once there are no possible matches
+        if matches.is_empty() {
+            break;
+        }
+        let linked: HashSet<EntryHash> = result?.into_iter().collect();
+        matches.retain(|addr| linked.contains(addr));
+    }
+
+    Ok(matches)
+}
+
+/// Selects a page of up to `limit` `addresses` (which must already be ordered from most recent
+/// to oldest) according to `paging`.
+///
+/// Returns the addresses along with flags indicating whether there are newer results preceding
+/// the page, and older results following it.
+///
+fn paginate_addresses(
+    addresses: &[EntryHash],
+    paging: &PagingParams,
+    limit: usize,
+) -> RecordAPIResult<(Vec<EntryHash>, bool, bool)>
+{
+    let find_cursor = |cursor: &String| -> RecordAPIResult<usize> {
+        let cursor_hash = decode_cursor(cursor)?;
+        addresses.iter()
+            .position(|addr| *addr == cursor_hash)
+            .ok_or_else(|| SemanticIndexError::BadCursorError(cursor.to_owned(), "not present in query results".to_string()).into())
+    };
+
+    let (start, end) = if paging.is_forwards() {
+        let end = match &paging.after {
+            None => addresses.len(),
+            Some(cursor) => find_cursor(cursor)?,
+        };
+        (end.saturating_sub(limit), end)
+    } else {
+        let start = match &paging.before {
+            None => 0,
+            Some(cursor) => find_cursor(cursor)? + 1,
+        };
+        (start, std::cmp::min(start.saturating_add(limit), addresses.len()))
+    };
+
+    Ok((addresses[start..end].to_vec(), start > 0, end < addresses.len()))
+}
+
 /// Query foreign entries pointers from a time-ordered index, in order from most recent to oldest.
 ///
-/// If `start_from` is provided, the given `EntryHash` is used to determine the starting location
-/// for reading results. Otherwise the newest entries (as determined by their ordering in the time
-/// index) are returned.
+/// A page of at most `paging.get_limit(default_page_size)` results is returned, starting from
+/// the position in the index determined by `paging`. @see `PagingParams`
 ///
 /// Full entry data is returned by querying from the associated record storage zome determined by
 /// `zome_name_from_config` and `read_method_name`.
@@ -127,9 +383,9 @@ pub fn query_time_index<'a, T, B, C, F, I>(
     zome_name_from_config: &'a F,
     read_method_name: &I,
     index_name: &I,
-    _start_from: Option<EntryHash>,
-    _limit: usize,
-) -> RecordAPIResult<Vec<RecordAPIResult<T>>>
+    paging: &PagingParams,
+    default_page_size: usize,
+) -> RecordAPIResult<QueryPage<T>>
     where T: serde::de::DeserializeOwned + std::fmt::Debug,
         B: DnaAddressable<EntryHash> + TryFrom<SerializedBytes, Error = SerializedBytesError>,
         I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
@@ -137,25 +393,84 @@ pub fn query_time_index<'a, T, B, C, F, I>(
         SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
         F: Fn(C) -> Option<String>,
 {
-    // this algorithm is the 'make it work' current pass, pending the full implementation mentioned
-    // in the TODO below, regarding efficiency and completeness
-    let linked_records = read_all_entry_hashes(index_name)
-        .map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
-
-    // :TODO: efficient paginated retrieval
-    // let linked_records = match start_from {
-    //     None => get_latest_entry_hashes(index_name, limit),
-    //     Some(cursor) => get_older_entry_hashes(index_name, cursor, limit),
-    // }.map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
+    let page_limit = paging.get_limit(default_page_size);
+    let (linked_records, has_previous_page, has_next_page) = read_time_index_page(index_name, paging, page_limit)?;
 
     let read_single_record = retrieve_foreign_record::<T, B, _,_,_>(zome_name_from_config, read_method_name);
 
-    Ok(linked_records.iter()
-        .map(|addr| {
-            // query full record from the associated CRUD zome
-            read_single_record(addr)
-        })
-        .collect())
+    Ok(QueryPage {
+        results: linked_records.iter()
+            .map(|addr| {
+                // query full record from the associated CRUD zome
+                read_single_record(addr)
+            })
+            .collect(),
+        has_previous_page,
+        has_next_page,
+        page_limit: Some(page_limit),
+    })
+}
+
+/// Reads a page of up to `limit` `EntryHash`es from the time index `index_name`, in order
+/// from most recent to oldest.
+///
+/// Returns the hashes along with flags indicating whether there are newer results preceding
+/// the page, and older results following it.
+///
+fn read_time_index_page<I>(
+    index_name: &I,
+    paging: &PagingParams,
+    limit: usize,
+) -> RecordAPIResult<(Vec<EntryHash>, bool, bool)>
+    where I: AsRef<str>,
+{
+    // one more result than necessary is requested, to determine whether there are further pages
+    let read_limit = limit.saturating_add(1);
+
+    if paging.is_forwards() {
+        let after = paging.after.as_ref().map(decode_cursor).transpose()?;
+        let has_older = after.is_some();
+
+        let mut hashes = match after {
+            None => get_earliest_entry_hashes(index_name, read_limit),
+            Some(cursor) => get_newer_entry_hashes(index_name, cursor, read_limit),
+        }.map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
+
+        let has_newer = hashes.len() > limit;
+        hashes.truncate(limit);
+        hashes.reverse();
+
+        Ok((hashes, has_newer, has_older))
+    } else {
+        let before = paging.before.as_ref().map(decode_cursor).transpose()?;
+        let has_newer = before.is_some();
+
+        let mut hashes = match before {
+            None => get_latest_entry_hashes(index_name, read_limit),
+            Some(cursor) => get_older_entry_hashes(index_name, cursor, read_limit),
+        }.map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
+
+        let has_older = hashes.len() > limit;
+        hashes.truncate(limit);
+
+        Ok((hashes, has_newer, has_older))
+    }
+}
+
+/// Decode a pagination cursor (as returned alongside results from `query_time_index`) into
+/// the `EntryHash` of the indexed entry it refers to.
+///
+/// Cursors are the string-encoded `DnaAddressable` identity of the indexed entry, with the
+/// encoded `EntryHash` as the leading segment.
+///
+pub fn decode_cursor<S>(cursor: &S) -> RecordAPIResult<EntryHash>
+    where S: AsRef<str>,
+{
+    let encoded_hash = cursor.as_ref().split(':').next().unwrap_or_default();
+
+    Ok(EntryHashB64::from_b64_str(encoded_hash)
+        .map_err(|e| { SemanticIndexError::BadCursorError(cursor.as_ref().to_string(), e.to_string()) })?
+        .into())
 }
 
 /// Fetches all referenced record entries found corresponding to the input
@@ -179,13 +494,17 @@ fn retrieve_foreign_records<'a, T, B, C, F, S>(
 
     addresses.iter()
         .map(read_single_record)
+        .map(|result| result.map(|(_cursor, record)| record))
         .collect()
 }
 
+/// Returns a callback to load a referenced record by its identity address, along with
+/// the pagination cursor referencing that record within the index.
+///
 fn retrieve_foreign_record<'a, T, B, C, F, S>(
     zome_name_from_config: &'a F,
     method_name: &'a S,
-) -> impl Fn(&EntryHash) -> RecordAPIResult<T> + 'a
+) -> impl Fn(&EntryHash) -> RecordAPIResult<(String, T)> + 'a
     where S: AsRef<str>,
         T: serde::de::DeserializeOwned + std::fmt::Debug,
         B: DnaAddressable<EntryHash>,
@@ -195,8 +514,9 @@ fn retrieve_foreign_record<'a, T, B, C, F, S>(
 {
     move |addr| {
         let address: B = read_remote_entry_identity(addr)?;
+        let cursor = address.to_string();
         let entry_res: T = call_local_zome_method(zome_name_from_config.to_owned(), method_name, ByAddress { address })?;
-        Ok(entry_res)
+        Ok((cursor, entry_res))
     }
 }
 
@@ -193,39 +193,21 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
             match index_datatype {
                 Some(string_ident) => match string_ident.to_string().as_ref() {
                     "String" => quote! {
-                        match &params.#query_field_ident {
-                            Some(#query_field_ident) => {
-                                // adapt the externally passed String identifier to an EntryHash for indexing engine
-                                let index_anchor_path = Path::from(#query_field_ident);
-                                let index_anchor_id: #related_index_field_type = DnaAddressable::new(dna_info()?.hash, index_anchor_path.path_entry_hash()?);
-
-                                entries_result = query_index::<ResponseData, #record_index_field_type, _,_,_,_,_,_>(
-                                    &index_anchor_id,
-                                    &stringify!(#reciprocal_index_name),
-                                    &LOCAL_TIME_INDEX_ID,
-                                    &read_index_target_zome,
-                                    &QUERY_FN_NAME,
-                                );
-                            },
-                            _ => (),
-                        };
+                        if let Some(#query_field_ident) = &params.#query_field_ident {
+                            // adapt the externally passed String identifier to an EntryHash for indexing engine
+                            let index_anchor_path = Path::from(#query_field_ident);
+                            let index_anchor_id: #related_index_field_type = DnaAddressable::new(dna_info()?.hash, index_anchor_path.path_entry_hash()?);
+
+                            filters.push(IndexQueryFilter::new(&index_anchor_id, &stringify!(#reciprocal_index_name))?);
+                        }
                     },
                     _ => panic!("String is currently the only valid index datatype"),
                 },
                 // standard logic for *Addressable-based indexes
                 None => quote! {
-                    match &params.#query_field_ident {
-                        Some(#query_field_ident) => {
-                            entries_result = query_index::<ResponseData, #record_index_field_type, _,_,_,_,_,_>(
-                                #query_field_ident,
-                                &stringify!(#reciprocal_index_name),
-                                &LOCAL_TIME_INDEX_ID,
-                                &read_index_target_zome,
-                                &QUERY_FN_NAME,
-                            );
-                        },
-                        _ => (),
-                    };
+                    if let Some(#query_field_ident) = &params.#query_field_ident {
+                        filters.push(IndexQueryFilter::new(#query_field_ident, &stringify!(#reciprocal_index_name))?);
+                    }
                 },
             }
         });
@@ -248,10 +230,15 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
             Some(conf.#record_type_index_attribute.record_storage_zome)
         }
 
-        // define struct to wrap query parameter inputs, so that other meta-args (eg. pagination) can be added later
+        // define struct to wrap query parameter inputs alongside pagination arguments
+        // @see hdk_semantic_indexes_zome_lib::PagingParams
         #[derive(Debug, Serialize, Deserialize)]
         struct SearchInputs {
             pub params: QueryParams,
+            pub first: Option<usize>,
+            pub after: Option<String>,
+            pub last: Option<usize>,
+            pub before: Option<String>,
         }
 
         // define zome API function name to read indexed records
@@ -271,19 +258,6 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
             #index_mutators
         )*
 
-        // query input parameters mimicing Relay's pagination spec
-        // @see https://relay.dev/graphql/connections.htm
-        // :TODO: extend to allow for filtering with `QueryParams`
-        #[derive(Debug, Serialize, Deserialize)]
-        #[serde(rename_all = "camelCase")]
-        struct PagingParams {
-            // :TODO: forwards pagination
-            // first: Option<usize>,
-            // after: Option<EntryHash>,
-            last: Option<usize>,
-            before: Option<EntryHash>,
-        }
-
         // query results structure mimicing Relay's pagination format
         #[derive(Debug, Serialize, Deserialize)]
         #[serde(rename_all = "camelCase")]
@@ -304,19 +278,18 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
         }
 
         // declare public list API
+        // @see hdk_semantic_indexes_zome_lib::PagingParams
         #[hdk_extern]
-        fn #exposed_read_api_method_name(PagingParams { /*first, after,*/ last, before }: PagingParams) -> ExternResult<QueryResults> {
-            let mut entries_result: RecordAPIResult<Vec<RecordAPIResult<ResponseData>>> = Err(SemanticIndexError::EmptyQuery.into());
-
-            entries_result = query_time_index::<ResponseData, #record_index_field_type,_,_,_>(
+        fn #exposed_read_api_method_name(paging: PagingParams) -> ExternResult<QueryResults> {
+            let page = query_time_index::<ResponseData, #record_index_field_type,_,_,_>(
                 &read_index_target_zome,
                 &QUERY_FN_NAME,
                 &LOCAL_TIME_INDEX_ID,
-                before,
-                last.unwrap_or(PAGE_SIZE),
-            );
+                &paging,
+                PAGE_SIZE,
+            )?;
 
-            Ok(handle_list_output(entries_result?.as_slice())?)
+            Ok(handle_list_output(page)?)
         }
 
         // declare API for global list API management
@@ -327,54 +300,58 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
 
         // declare public query method with injected handler logic
         #[hdk_extern]
-        fn #exposed_query_api_method_name(SearchInputs { params }: SearchInputs) -> ExternResult<QueryResults>
+        fn #exposed_query_api_method_name(SearchInputs { params, first, after, last, before }: SearchInputs) -> ExternResult<QueryResults>
         {
-            let mut entries_result: RecordAPIResult<Vec<RecordAPIResult<ResponseData>>> = Err(SemanticIndexError::EmptyQuery.into());
-
-            // :TODO: proper search combinator logic, this just does exclusive boolean ops
+            // results must match all provided query params
+            #[allow(unused_mut)]
+            let mut filters: Vec<IndexQueryFilter> = vec![];
             #(
                 #query_handlers
             )*
 
-            Ok(handle_list_output(entries_result?.as_slice())?)
+            let page = query_filtered_index::<ResponseData, #record_index_field_type, _,_,_,_>(
+                filters.as_slice(),
+                &LOCAL_TIME_INDEX_ID,
+                &read_index_target_zome,
+                &QUERY_FN_NAME,
+                &PagingParams { first, after, last, before },
+                PAGE_SIZE,
+            )?;
+
+            Ok(handle_list_output(page)?)
         }
 
-        fn handle_list_output(entries: &[RecordAPIResult<ResponseData>]) -> RecordAPIResult<QueryResults>
+        fn handle_list_output(page: QueryPage<ResponseData>) -> RecordAPIResult<QueryResults>
         {
-            let valid_edges = entries.iter()
-                .cloned()
-                .filter_map(Result::ok);
-
-            let edge_cursors = valid_edges
-                .clone()
-                .map(|node| {
-                    node.#record_type_str_ident.id.to_string()
-                });
-
-            let formatted_edges = valid_edges.zip(edge_cursors)
-                .map(|(node, cursor)| {
+            let QueryPage { results, has_previous_page, has_next_page, page_limit } = page;
+
+            let (valid_edges, errors): (Vec<_>, Vec<_>) = results.into_iter()
+                .partition(Result::is_ok);
+
+            let formatted_edges: Vec<Edge> = valid_edges.into_iter()
+                .filter_map(Result::ok)
+                .map(|(cursor, node)| {
                     Edge {
                         node: node.#record_type_str_ident,
                         cursor,
                     }
-                });
+                })
+                .collect();
 
-            let mut edge_cursors = formatted_edges.clone().map(|e| { e.cursor });
-            let first_cursor = edge_cursors.next().unwrap_or("0".to_string());
+            let first_cursor = formatted_edges.first().map(|e| e.cursor.to_owned()).unwrap_or("0".to_string());
+            let last_cursor = formatted_edges.last().map(|e| e.cursor.to_owned()).unwrap_or(first_cursor.clone());
 
             Ok(QueryResults {
-                edges: formatted_edges.collect(),
+                edges: formatted_edges,
                 page_info: PageInfo {
-                    end_cursor: edge_cursors.last().unwrap_or(first_cursor.clone()),
+                    end_cursor: last_cursor,
                     start_cursor: first_cursor,
-                    // :TODO:
-                    has_next_page: true,
-                    has_previous_page: true,
-                    page_limit: None,
+                    has_next_page,
+                    has_previous_page,
+                    page_limit,
                     total_count: None,
                 },
-                errors: entries.iter()
-                    .cloned()
+                errors: errors.into_iter()
                     .filter_map(Result::err)
                     .map(|err| { WasmError::from(err) })
                     .collect(),
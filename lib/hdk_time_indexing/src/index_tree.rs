@@ -159,17 +159,15 @@ where S: AsRef<str> + std::fmt::Display,
 
     if is_chunk_segment {
         // handle chunks differently by splitting off the chunk portion first
-        // and adding offset milliseconds after parsing
-        let (data_str, chunk_offset_str) = adjusted_segment_data.split_once('|').unwrap();
-        match NaiveDateTime::parse_from_str(data_str, try_format_str.as_ref()) {
-            Ok(raw_datetime) => Ok(DateTime::<Utc>::from_utc(
-                raw_datetime
-                .checked_add_signed(Duration::milliseconds(chunk_offset_str.parse::<i64>().unwrap()))
-                .unwrap(),
-                Utc
-            )),
-            Err(_e) => Err(TimeIndexingError::Malformed(adjusted_segment_data.as_bytes().to_vec())),
-        }
+        // and adding offset milliseconds after parsing the base segment
+        let (data_str, chunk_offset_str) = segment_data.as_ref().split_once('|')
+            .ok_or(TimeIndexingError::Malformed(segment_data.as_ref().as_bytes().to_vec()))?;
+        let chunk_offset = chunk_offset_str.parse::<i64>()
+            .map_err(|_e| TimeIndexingError::Malformed(segment_data.as_ref().as_bytes().to_vec()))?;
+        let base_time = timestamp_for_segment_str(&data_str, granularity, false)?;
+
+        base_time.checked_add_signed(Duration::milliseconds(chunk_offset))
+            .ok_or(TimeIndexingError::Malformed(segment_data.as_ref().as_bytes().to_vec()))
     } else {
         // for standard segments we can just parse using the appropriate (already determined) format string
         match NaiveDateTime::parse_from_str(adjusted_segment_data.as_ref(), try_format_str.as_ref()) {
@@ -183,7 +181,7 @@ where S: AsRef<str> + std::fmt::Display,
 ///
 /// Returns a `TimeIndexingError::Malformed` if an invalid link tag is passed.
 ///
-fn decode_link_tag_timestamp(tag: LinkTag) -> TimeIndexResult<DateTime<Utc>>
+pub (crate) fn decode_link_tag_timestamp(tag: LinkTag) -> TimeIndexResult<DateTime<Utc>>
 {
     // take the raw bytes of the LinkTag and split on the first null byte separator. All bytes following are the truncated timestamp as an encoded string.
     let bits: Vec<&[u8]> = tag.as_ref().splitn(2, |byte| { *byte == 0x0 as u8 }).collect();
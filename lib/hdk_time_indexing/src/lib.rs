@@ -46,7 +46,9 @@ pub use writing::index_entry;
 pub use reading::{
     read_all_entry_hashes,
     get_latest_entry_hashes,
+    get_earliest_entry_hashes,
     get_older_entry_hashes,
+    get_newer_entry_hashes,
 };
 pub use sorting::sort_entries_by_time_index;
 
@@ -1,100 +1,66 @@
-use chrono::{DateTime, Utc, Duration};
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+};
+use chrono::{DateTime, Utc};
 use hdk::prelude::*;
 use crate::{
     INDEX_DEPTH, HAS_CHUNK_LEAVES,
-    index_tree::IndexSegment,
+    index_tree::decode_link_tag_timestamp,
     TimeIndexResult, TimeIndexingError,
 };
 use hdk_semantic_indexes_core::LinkTypes;
 
+/// Direction in which to walk the time index tree when reading entries.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReadOrder {
+    NewestFirst,
+    OldestFirst,
+}
+
+/// Position of an indexed entry within a time index, used as an exclusive offset
+/// when reading pages of results. Entries indexed at the same time are ordered by hash.
+///
+type IndexOffset = (DateTime<Utc>, EntryHash);
+
 /**
- * Retrieve the complete set of linked `EntryHash`es referenced in the `index_name` index.
+ * Retrieve the complete set of linked `EntryHash`es referenced in the `index_name` index,
+ * in order from most recent to oldest.
+ *
  * This method is highly inefficient and strongly discouraged for large datasets. Use
  * only with indexes which are known to be of a small size.
  */
 pub fn read_all_entry_hashes<I>(index_name: &I) -> TimeIndexResult<Vec<EntryHash>>
     where I: AsRef<str>,
 {
-    let root_hash = get_root_hash(index_name)?;
-
-    match root_hash {
-        None => Ok(vec![]),
-        Some(hash) => {
-            Ok(collect_leaf_index_hashes(index_name, hash, (*INDEX_DEPTH).len() as isize)?)
-        }
-    }
+    read_ordered_entry_hashes(index_name, ReadOrder::NewestFirst, None, usize::MAX)
 }
 
-/// Recursively performs a depth-first traversal of the specified time index tree, returning the `EntryHash`es
-/// of all the leafmost nodes (i.e. indexed entries) present in the index.
-///
-fn collect_leaf_index_hashes<I>(index_name: &I, context_hash: EntryHash, context_depth: isize) -> TimeIndexResult<Vec<EntryHash>>
+/**
+ * Retrieve the most recent entry hashes stored in the `index_name` time-ordered index,
+ * up to a maximum of `limit`. Results are ordered from most recent to oldest.
+ */
+pub fn get_latest_entry_hashes<I>(index_name: &I, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
     where I: AsRef<str>,
 {
-    let children = get_ordered_child_links_of_node(
-        index_name,
-        context_hash.clone(),
-    )?;
-
-    // last hop outside the index tree links to the targeted nodes, so return them
-    if (*HAS_CHUNK_LEAVES && context_depth == -1) || (!(*HAS_CHUNK_LEAVES) && context_depth == 0) {
-        return Ok(children.iter()
-            .map(|link| EntryHash::from(link.target.to_owned()))
-            .collect());
-    }
-
-    // still recursing downwards- load descendent nodes for every child found
-    let (descendents, errors): (Vec<TimeIndexResult<Vec<EntryHash>>>, Vec<TimeIndexResult<Vec<EntryHash>>>) = children.iter()
-        .map(|link| {
-            collect_leaf_index_hashes(index_name, EntryHash::from(link.target.to_owned()), context_depth - 1)
-        })
-        .partition(Result::is_ok);
-
-    // return any errors fetching children if encountered
-    if errors.len() > 0 {
-        return errors.first().unwrap().to_owned();
-    }
-
-    // flatten the descendent nodes into a single vector
-    Ok(descendents.iter().cloned()
-        .flat_map(Result::unwrap)
-        .collect())
+    read_ordered_entry_hashes(index_name, ReadOrder::NewestFirst, None, limit)
 }
 
 /**
- * Retrieve the most recent entry hashes stored in the `index_name` time-ordered index,
- * up to a maximum of `limit`.
+ * Retrieve the earliest entry hashes stored in the `index_name` time-ordered index,
+ * up to a maximum of `limit`. Results are ordered from oldest to most recent.
  */
-pub fn get_latest_entry_hashes<I>(index_name: &I, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
+pub fn get_earliest_entry_hashes<I>(index_name: &I, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
     where I: AsRef<str>,
 {
-    // find the most recently indexed EntryHash as a starting point
-    let most_recent = get_latest_indexed_entry_hash(index_name)?;
-
-    match most_recent {
-        None => Ok(vec![]),
-        Some(latest) => {
-            // load a page of links to entries immediately before the latest
-            let mut earlier_page = get_older_entry_hashes(index_name, latest.to_owned(), limit)?;
-
-            // remove the earliest linked entry
-            let num_earlier = earlier_page.len();
-            if num_earlier > 0 {
-                earlier_page.truncate(num_earlier - 1);
-            }
-
-            // prepend the most recent one
-            Ok([
-                vec![latest.to_owned()],
-                earlier_page,
-            ].concat())
-        },
-    }
+    read_ordered_entry_hashes(index_name, ReadOrder::OldestFirst, None, limit)
 }
 
 /**
  * Retrieve entry hashes indexed in the `index_name` time-ordered index immediately
- * before `before_entry` (not inclusive), up to a maximum of `limit`.
+ * before `before_entry` (not inclusive), up to a maximum of `limit`. Results are
+ * ordered from most recent to oldest.
  *
  * This method is best used with cursor-based pagination, where the previously oldest
  * returned `EntryHash` is used as a cursor to return the next most recent page of entries.
@@ -102,194 +68,193 @@ pub fn get_latest_entry_hashes<I>(index_name: &I, limit: usize) -> TimeIndexResu
 pub fn get_older_entry_hashes<I>(index_name: &I, before_entry: EntryHash, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
     where I: AsRef<str>,
 {
-    Ok(get_ordered_links_before(index_name, before_entry, limit)?
-        .iter()
-        .map(|link| { EntryHash::from(link.target.to_owned()) })
-        .collect())
+    let offset = (get_indexed_time(index_name, &before_entry)?, before_entry);
+
+    read_ordered_entry_hashes(index_name, ReadOrder::NewestFirst, Some(offset), limit)
 }
 
-/// Return a maximum of `limit` `Link`s, in order from most recent to oldest in `index_name`
-/// starting at the given (already indexed) `entry_hash`.
-///
-/// :TODO: account for the possibility that an entry might be validly linked multiple times in the same index
-/// :TODO: de-duplicate links to account for network partitions erroneously causing multiple writes of the same index
-///
-fn get_ordered_links_before<I>(index_name: &I, entry_hash: EntryHash, limit: usize) -> TimeIndexResult<Vec<Link>>
+/**
+ * Retrieve entry hashes indexed in the `index_name` time-ordered index immediately
+ * after `after_entry` (not inclusive), up to a maximum of `limit`. Results are
+ * ordered from oldest to most recent.
+ *
+ * This is the inverse of `get_older_entry_hashes`, for paginating forwards in time from
+ * the most recent `EntryHash` previously returned.
+ */
+pub fn get_newer_entry_hashes<I>(index_name: &I, after_entry: EntryHash, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
     where I: AsRef<str>,
 {
-    // inspect link from entry to index in order to determine indexed time & parent node in index tree
-    let parents = get_links(
-        entry_hash.to_owned(),
-        LinkTypes::TimeIndex,
-        Some(link_prefix_for_index(index_name)),
-    )?;
-    let leaf_link = parents.first().ok_or(
-        TimeIndexingError::NotIndexed(index_name.as_ref().to_string(), entry_hash)
-    )?;
-    let this_index: IndexSegment = leaf_link.tag.to_owned().try_into()?;
-    let this_timestamp = this_index.into();
-
-    let leaf_hash = EntryHash::from(leaf_link.target.to_owned());
-
-    // find all our older siblings
-    let mut older_siblings = get_ordered_child_links_of_node_older_than(index_name, leaf_hash, this_timestamp)?;
-
-    // truncate to requested limit
-    older_siblings.truncate(limit);
-
-    // continue fetching links from previous leaf nodes in the index until we have reached the limit
-    let mut context_timestamp = this_timestamp;
-    while older_siblings.len() < limit {
-        let previous_leaf_link = get_previous_leaf(
-            index_name,
-            &context_timestamp,
-            // :NOTE: if there are chunk nodes in the tree, immediate parent will be at
-            //        first INDEX_DEPTH, otherwise it will be second tier.
-            if *HAS_CHUNK_LEAVES { 0 } else { 1 },
-            if *HAS_CHUNK_LEAVES { 0 } else { 1 },
-        )?;
-
-        match previous_leaf_link {
-            // stop looking if there are no more leaves to query
-            None => { break; },
-            Some(prev) => {
-                // append any found link targets from the previous leaf
-                let more_older_siblings = get_ordered_child_links_of_node_older_than(index_name, prev.hash()?, this_timestamp)?;
-                older_siblings = [older_siblings, more_older_siblings].concat();
-                older_siblings.truncate(limit);
+    let offset = (get_indexed_time(index_name, &after_entry)?, after_entry);
 
-                // look further backward from previous leaf if we still need to find more
-                let prev_time: DateTime<Utc> = prev.into();
-                context_timestamp = prev_time + Duration::milliseconds(-1);
-            },
-        }
-    }
-
-    Ok(older_siblings)
+    read_ordered_entry_hashes(index_name, ReadOrder::OldestFirst, Some(offset), limit)
 }
 
-/// Locate all the child links of the node with hash `leaf_hash`, ordered from newest to oldest.
+/// Walk the time index tree for `index_name` in the given `order`, returning a maximum of `limit`
+/// indexed `EntryHash`es found past the (exclusive) `offset`, if provided.
 ///
-fn get_ordered_child_links_of_node<I>(index_name: &I, leaf_hash: EntryHash) -> TimeIndexResult<Vec<Link>>
+fn read_ordered_entry_hashes<I>(index_name: &I, order: ReadOrder, offset: Option<IndexOffset>, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
     where I: AsRef<str>,
 {
-    // query children of parent node
-    let mut siblings = get_links(
-        leaf_hash,
-        LinkTypes::TimeIndex,
-        Some(link_prefix_for_index(index_name)),
-    )?;
+    let mut results = vec![];
 
-    // order them from newest to oldest
-    siblings.sort_unstable_by(|a, b| b.tag.cmp(&a.tag));
+    match get_root_hash(index_name)? {
+        // bail early if the index hasn't been touched
+        None => {},
+        Some(hash) => {
+            collect_leaf_index_hashes(
+                index_name, hash, (*INDEX_DEPTH).len() as isize,
+                order, offset.as_ref(), limit,
+                &mut results,
+            )?;
+        },
+    };
 
-    Ok(siblings)
+    Ok(results)
 }
 
-/// Locate all the child links of the node with hash `leaf_hash` which are older than `this_timestamp`,
-/// ordered from newest to oldest.
+/// Recursively performs a depth-first traversal of the specified time index tree in the given `order`,
+/// appending the `EntryHash`es of leafmost nodes (i.e. indexed entries) to `results` until `limit` is reached.
 ///
-fn get_ordered_child_links_of_node_older_than<I>(index_name: &I, leaf_hash: EntryHash, this_timestamp: DateTime<Utc>) -> TimeIndexResult<Vec<Link>>
-    where I: AsRef<str>,
-{
-    // query children of parent node
-    let siblings = get_ordered_child_links_of_node(
-        index_name,
-        leaf_hash,
-    )?;
-
-    // filter out those newer
-    Ok(siblings.iter().cloned().filter(|sib| {
-        let this_index: Result<IndexSegment, _> = sib.tag.to_owned().try_into();
-        match this_index {
-            Ok(time) => {
-                let dt: DateTime<Utc> = time.into();
-                dt < this_timestamp
-            },
-            Err(_e) => false,
-        }
-    }).collect())
-}
-
-/// Find the previous leafmost node in the time index tree
-/// :TODO: make this work
+/// Branches of the tree which cannot contain entries past `offset` are skipped without being read.
 ///
-fn get_previous_leaf<I>(index_name: &I, from_timestamp: &DateTime<Utc>, try_depth: usize, starting_depth: usize) -> TimeIndexResult<Option<IndexSegment>>
+/// Entries which were indexed at more than one time are only collected at their earliest indexed time,
+/// which is also the position used to resolve cursors in `get_indexed_time`. This keeps each entry at a
+/// single position regardless of read direction or which page it is read in.
+///
+fn collect_leaf_index_hashes<I>(
+    index_name: &I,
+    context_hash: EntryHash,
+    context_depth: isize,
+    order: ReadOrder,
+    offset: Option<&IndexOffset>,
+    limit: usize,
+    results: &mut Vec<EntryHash>,
+) -> TimeIndexResult<()>
     where I: AsRef<str>,
 {
-    let mut this_depth = try_depth;
-    while this_depth >= starting_depth {
-        let try_parent_hash = if this_depth >= INDEX_DEPTH.len() {
-            // can't go upwards anymore, so go to the root
-            Path::from(index_name.as_ref()).typed(LinkTypes::TimeIndex)?.path_entry_hash()?
-        } else {
-            // determine next parent upwards
-            IndexSegment::new(from_timestamp, INDEX_DEPTH.get(this_depth).unwrap()).hash()?
+    let children = get_ordered_child_links_of_node(index_name, context_hash)?;
+
+    // last hop outside the index tree links to the targeted nodes, so return them
+    if (*HAS_CHUNK_LEAVES && context_depth == -1) || (!(*HAS_CHUNK_LEAVES) && context_depth == 0) {
+        let targets = children.into_iter()
+            .map(|(time, link)| (time, EntryHash::from(link.target)));
+        let ordered_targets: Vec<IndexOffset> = match order {
+            ReadOrder::NewestFirst => targets.rev().collect(),
+            ReadOrder::OldestFirst => targets.collect(),
         };
 
-        // find all nodes in the tree at this depth that are older than the starting offset
-        let older_siblings = get_ordered_child_links_of_node_older_than(index_name, try_parent_hash, from_timestamp.to_owned())?;
+        for target in ordered_targets {
+            if results.len() >= limit {
+                break;
+            }
+            if is_past_offset(&target, offset, order) && get_indexed_time(index_name, &target.1)? == target.0 {
+                results.push(target.1);
+            }
+        }
 
-        match older_siblings.first() {
-            // there are no older siblings in this list
-            None => {
-                if this_depth >= INDEX_DEPTH.len() {
-                    // if we are at the root, there are no more index links to try
-                    return Ok(None);
-                } else {
-                    // if we are not yet at the root, go up a level and try reading more links
-                    this_depth += 1;
-                    continue;
-                }
-            },
-            // if there was an older sibling, come back down to the earliest leaf node
-            Some(link) => {
-                let _found_at_this_depth: IndexSegment = link.tag.to_owned().try_into()?;
-                while this_depth >= starting_depth {
-                    // :TODO: finish this, or scrap it and start again
+        return Ok(());
+    }
 
-                    this_depth -= 1;
-                }
-                // this isn't what we want...
-                return Ok(Some(IndexSegment::new(from_timestamp, INDEX_DEPTH.get(starting_depth).unwrap())))
-            },
+    // Still recursing downwards- determine the span of time covered by each child node.
+    // Each node covers the period from its own timestamp until that of its next sibling.
+    let num_children = children.len();
+    let mut spans: Vec<(DateTime<Utc>, Option<DateTime<Utc>>, EntryHash)> = children.iter()
+        .enumerate()
+        .map(|(idx, (time, link))| {
+            let next_time = if idx + 1 < num_children { Some(children[idx + 1].0) } else { None };
+            (time.to_owned(), next_time, EntryHash::from(link.target.to_owned()))
+        })
+        .collect();
+    if order == ReadOrder::NewestFirst {
+        spans.reverse();
+    }
+
+    // load descendent nodes for every child that may contain entries past the offset
+    for (span_start, span_end, child_hash) in spans {
+        if results.len() >= limit {
+            break;
+        }
+        let may_contain_results = match (offset, order) {
+            (None, _) => true,
+            (Some((offset_time, _)), ReadOrder::NewestFirst) => span_start <= *offset_time,
+            (Some((offset_time, _)), ReadOrder::OldestFirst) => span_end.map_or(true, |end| end > *offset_time),
+        };
+        if may_contain_results {
+            collect_leaf_index_hashes(index_name, child_hash, context_depth - 1, order, offset, limit, results)?;
         }
     }
 
-    Ok(None)    // also probably wrong
+    Ok(())
 }
 
-/// Determine the hash of the most recent `IndexSegment` written for the given `index_name`.
-/// If the index has not been created yet, `None` is returned in the result.
+/// Determine whether an indexed `target` is beyond the given exclusive `offset` when reading in `order`.
 ///
-fn get_latest_indexed_entry_hash<I>(index_name: &I) -> TimeIndexResult<Option<EntryHash>>
-    where I: AsRef<str>,
-{
-    match get_root_hash(index_name)? {
-        None => Ok(None),
-        // recurse into the tree, returning leafmost node
-        Some(hash) => Ok(Some(get_newest_leafmost_hash(hash, index_name)?)),
+fn is_past_offset(target: &IndexOffset, offset: Option<&IndexOffset>, order: ReadOrder) -> bool {
+    match offset {
+        None => true,
+        Some(offset) => match order {
+            ReadOrder::NewestFirst => compare_index_positions(target, offset) == Ordering::Less,
+            ReadOrder::OldestFirst => compare_index_positions(target, offset) == Ordering::Greater,
+        },
     }
 }
 
-/// Find the most recent indexed hash in all children of `current_segment_hash`
-/// by recursively performing a depth-first search of its children.
+/// Order entries by indexed time, falling back to comparing their hashes where times are equal.
+///
+fn compare_index_positions(a: &IndexOffset, b: &IndexOffset) -> Ordering {
+    a.0.cmp(&b.0)
+        .then_with(|| a.1.get_raw_39().cmp(b.1.get_raw_39()))
+}
+
+/// Locate all the child links of the node with hash `node_hash` along with their decoded timestamps,
+/// ordered from oldest to newest.
+///
+/// Links with malformed tags are ignored, as are duplicate links to the same target (which
+/// may occur when network partitions cause the same index to be written multiple times).
+/// Where a target is linked more than once, only its earliest link is retained.
 ///
-fn get_newest_leafmost_hash<I>(current_segment_hash: EntryHash, index_name: &I) -> TimeIndexResult<EntryHash>
+fn get_ordered_child_links_of_node<I>(index_name: &I, node_hash: EntryHash) -> TimeIndexResult<Vec<(DateTime<Utc>, Link)>>
     where I: AsRef<str>,
 {
-    let mut children = get_links(
-        current_segment_hash.to_owned(),
+    let mut children: Vec<(DateTime<Utc>, Link)> = get_links(
+        node_hash,
         LinkTypes::TimeIndex,
         Some(link_prefix_for_index(index_name)),
-    )?;
+    )?
+        .into_iter()
+        .filter_map(|link| {
+            decode_link_tag_timestamp(link.tag.to_owned()).ok()
+                .map(|time| (time, link))
+        })
+        .collect();
 
-    children.sort_unstable_by(|a, b| a.tag.cmp(&b.tag));
+    children.sort_by(|(a_time, a), (b_time, b)| {
+        a_time.cmp(b_time)
+            .then_with(|| a.target.get_raw_39().cmp(b.target.get_raw_39()))
+    });
+    let mut seen_targets = HashSet::new();
+    children.retain(|(_, link)| seen_targets.insert(link.target.to_owned()));
 
-    match children.last() {
-        None => Ok(current_segment_hash),
-        Some(l) => get_newest_leafmost_hash(EntryHash::from(l.target.to_owned()), index_name),
-    }
+    Ok(children)
+}
+
+/// Determine the time at which `entry_hash` was indexed into `index_name`, by inspecting
+/// the reciprocal links from the entry back to its parent nodes in the index tree.
+///
+/// If the entry has been indexed more than once, the earliest time is used.
+///
+fn get_indexed_time<I>(index_name: &I, entry_hash: &EntryHash) -> TimeIndexResult<DateTime<Utc>>
+    where I: AsRef<str>,
+{
+    get_links(
+        entry_hash.to_owned(),
+        LinkTypes::TimeIndex,
+        Some(link_prefix_for_index(index_name)),
+    )?
+        .into_iter()
+        .filter_map(|link| decode_link_tag_timestamp(link.tag).ok())
+        .min()
+        .ok_or(TimeIndexingError::NotIndexed(index_name.as_ref().to_string(), entry_hash.to_owned()))
 }
 
 /// Determine the hash of the root node for the given index.
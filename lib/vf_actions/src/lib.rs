@@ -7,7 +7,7 @@
 use serde::{Deserializer, Serializer, de::Error};
 
 use hdk::prelude::*;
-use vf_attributes_hdk::{ ActionId, ProcessAddress, EconomicResourceAddress };
+use vf_attributes_hdk::{ ActionId, ProcessAddress, RecipeProcessAddress, EconomicResourceAddress };
 
 pub mod builtins;
 pub use builtins::{ get_builtin_action, get_all_builtin_actions };
@@ -110,20 +110,39 @@ pub struct Action {
     pub pairs_with: String, // any of the action labels, or "notApplicable"
 }
 
+/**
+ * Determine whether `action_id` refers to one of the builtin `actions`.
+ * Action IDs are accepted in either hyphenated or underscored form, as with `get_builtin_action`.
+ */
+pub fn is_builtin_action(action_id: &str, actions: &[&str]) -> bool {
+    match get_builtin_action(action_id) {
+        Some(action) => actions.iter().any(|a| get_builtin_action(a).map_or(false, |b| b.id == action.id)),
+        None => false,
+    }
+}
+
 /**
  * Validation for EconomicEvent, Commitment and Process to ensure correct use of actions & Processes
  */
 pub fn validate_flow_action(action_id: ActionId, input_process: Option<ProcessAddress>, output_process: Option<ProcessAddress>) -> Result<(), String> {
+    validate_action_process_links("EconomicEvent", action_id, input_process.is_some(), output_process.is_some())
+}
+
+pub fn validate_recipe_flow_action(action_id: ActionId, input_process: Option<RecipeProcessAddress>, output_process: Option<RecipeProcessAddress>) -> Result<(), String> {
+    validate_action_process_links("RecipeFlow", action_id, input_process.is_some(), output_process.is_some())
+}
+
+fn validate_action_process_links(record_type: &str, action_id: ActionId, has_input_process: bool, has_output_process: bool) -> Result<(), String> {
     if let Some(action) = get_builtin_action(action_id.as_ref()) {
         match action.input_output {
-            ProcessType::NotApplicable => if input_process.is_some() || output_process.is_some() {
-                Err(format!("EconomicEvent of '{:}' action cannot link to processes", action.id).into())
+            ProcessType::NotApplicable => if has_input_process || has_output_process {
+                Err(format!("{:} of '{:}' action cannot link to processes", record_type, action.id).into())
             } else { Ok(()) },
-            ProcessType::Input => if input_process.is_none() {
-                Err(format!("EconomicEvent input process required for '{:}' action", action.id).into())
+            ProcessType::Input => if !has_input_process {
+                Err(format!("{:} input process required for '{:}' action", record_type, action.id).into())
             } else { Ok(()) },
-            ProcessType::Output => if output_process.is_none() {
-                Err(format!("EconomicEvent output process required for '{:}' action", action.id).into())
+            ProcessType::Output => if !has_output_process {
+                Err(format!("{:} output process required for '{:}' action", record_type, action.id).into())
             } else { Ok(()) },
         }
     } else {
@@ -132,14 +151,75 @@ pub fn validate_flow_action(action_id: ActionId, input_process: Option<ProcessAd
 }
 
 pub fn validate_move_inventories(resouce_inventoried_as: Option<EconomicResourceAddress>, to_resource_inventoried_as: Option<EconomicResourceAddress>) -> Result<(), String> {
+    validate_paired_inventories("move", resouce_inventoried_as, to_resource_inventoried_as)
+}
+
+pub fn validate_transfer_inventories(resouce_inventoried_as: Option<EconomicResourceAddress>, to_resource_inventoried_as: Option<EconomicResourceAddress>) -> Result<(), String> {
+    validate_paired_inventories("transfer", resouce_inventoried_as, to_resource_inventoried_as)
+}
+
+fn validate_paired_inventories(action_id: &str, resouce_inventoried_as: Option<EconomicResourceAddress>, to_resource_inventoried_as: Option<EconomicResourceAddress>) -> Result<(), String> {
     match resouce_inventoried_as {
         Some(_) => match to_resource_inventoried_as {
             Some(_) => Ok(()),
-            None => Err("inventoried move EconomicEvent requires both source and destination inventory fields".into()),
+            None => Err(format!("inventoried {:} EconomicEvent requires both source and destination inventory fields", action_id)),
         },
         None => match to_resource_inventoried_as {
             None => Ok(()),
-            Some(_) => Err("non-inventoried move EconomicEvent must omit inventory fields".into()),
+            Some(_) => Err(format!("non-inventoried {:} EconomicEvent must omit inventory fields", action_id)),
+        },
+    }
+}
+
+/**
+ * Validation for EconomicEvents affecting inventoried resources, which must state the quantity
+ * of resource to increment or decrement by if their action has any effect upon inventory.
+ */
+pub fn validate_inventory_quantity(action_id: ActionId, is_inventoried: bool, has_resource_quantity: bool) -> Result<(), String> {
+    if !is_inventoried || has_resource_quantity {
+        return Ok(());
+    }
+    match get_builtin_action(action_id.as_ref()) {
+        Some(action) => if action.accounting_effect == ActionEffect::NoEffect && action.onhand_effect == ActionEffect::NoEffect {
+            Ok(())
+        } else {
+            Err(format!("EconomicEvent of '{:}' action requires a resource quantity when affecting an inventoried resource", action.id))
         },
+        None => Err("Unknown action".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource() -> Option<EconomicResourceAddress> {
+        Some(EconomicResourceAddress(DnaHash::from_raw_36(vec![0; 36]), EntryHash::from_raw_36(vec![0; 36])))
+    }
+
+    #[test]
+    fn test_action_matching_is_normalized() {
+        assert!(is_builtin_action("transfer-all-rights", &["transfer_all_rights"]));
+        assert!(is_builtin_action("transfer_all_rights", &["move", "transfer-all-rights"]));
+        assert!(is_builtin_action("move", &["move"]));
+        assert!(!is_builtin_action("transfer-custody", &["transfer", "transfer-all-rights"]));
+        assert!(!is_builtin_action("unknown", &["unknown"]));
+    }
+
+    #[test]
+    fn test_transfer_inventories_must_be_paired() {
+        assert!(validate_transfer_inventories(resource(), resource()).is_ok());
+        assert!(validate_transfer_inventories(None, None).is_ok());
+        assert!(validate_transfer_inventories(resource(), None).is_err());
+        assert!(validate_transfer_inventories(None, resource()).is_err());
+    }
+
+    #[test]
+    fn test_inventory_quantity_required_for_inventory_effects() {
+        assert!(validate_inventory_quantity(ActionId("raise".to_string()), true, true).is_ok());
+        assert!(validate_inventory_quantity(ActionId("raise".to_string()), true, false).is_err());
+        assert!(validate_inventory_quantity(ActionId("raise".to_string()), false, false).is_ok());
+        assert!(validate_inventory_quantity(ActionId("work".to_string()), true, false).is_ok());
+        assert!(validate_inventory_quantity(ActionId("unknown".to_string()), true, false).is_err());
     }
 }
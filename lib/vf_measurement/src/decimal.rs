@@ -0,0 +1,410 @@
+/**
+ * Fixed-point decimal numbers for exact quantity arithmetic
+ *
+ * Values are held as an integer count of `10^-scale` units, so that sums and differences
+ * of quantities (eg. currency amounts) never accumulate binary floating-point error.
+ *
+ * Serialized as a decimal string to remain lossless across the wire; plain numeric
+ * input is also accepted for compatibility with records written prior to this format.
+ *
+ * @package     hREA
+ * @since       2026-10-18
+ */
+use std::{
+    cmp::Ordering,
+    fmt,
+    str::FromStr,
+};
+use serde::{
+    Serialize, Serializer, Deserialize, Deserializer,
+    de::{self, Visitor},
+};
+use hdk_records::{RecordAPIResult, DataIntegrityError};
+
+/// Maximum number of fractional digits retained by any `Decimal`
+pub const MAX_DECIMAL_SCALE: u32 = 18;
+/// Number of fractional digits retained by the result of a division, unless the operands have more
+pub const DEFAULT_DIVISION_SCALE: u32 = 9;
+/// Maximum number of significant digits in a parsed `Decimal`, all of which fit within an `i128`
+const MAX_DECIMAL_DIGITS: usize = 38;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Decimal {
+    value: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    /// Create a decimal equal to `value * 10^-scale`
+    pub fn new(value: i128, scale: u32) -> RecordAPIResult<Decimal> {
+        if scale > MAX_DECIMAL_SCALE {
+            return Err(DataIntegrityError::InvalidDecimal(format!("scale {} exceeds maximum of {}", scale, MAX_DECIMAL_SCALE)));
+        }
+        Ok(Decimal { value, scale })
+    }
+
+    pub fn zero() -> Decimal {
+        Decimal { value: 0, scale: 0 }
+    }
+
+    pub fn one() -> Decimal {
+        Decimal { value: 1, scale: 0 }
+    }
+
+    /// Convert from a binary floating-point number, using the shortest decimal
+    /// representation which round-trips to the same `f64`.
+    pub fn from_f64(value: f64) -> RecordAPIResult<Decimal> {
+        if !value.is_finite() {
+            return Err(DataIntegrityError::InvalidDecimal(format!("{} is not a finite number", value)));
+        }
+        Decimal::from_str(&value.to_string())
+    }
+
+    /// Approximate this value as a binary floating-point number, for display or heuristics only
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    pub fn get_scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.value < 0
+    }
+
+    pub fn abs(&self) -> Decimal {
+        Decimal { value: self.value.abs(), scale: self.scale }
+    }
+
+    pub fn negate(&self) -> Decimal {
+        Decimal { value: -self.value, scale: self.scale }
+    }
+
+    /// Express this value with exactly `scale` fractional digits, rounding half away from zero if precision is lost
+    pub fn rescale(&self, scale: u32) -> RecordAPIResult<Decimal> {
+        if scale > MAX_DECIMAL_SCALE {
+            return Err(DataIntegrityError::InvalidDecimal(format!("scale {} exceeds maximum of {}", scale, MAX_DECIMAL_SCALE)));
+        }
+        match scale.cmp(&self.scale) {
+            Ordering::Equal => Ok(*self),
+            Ordering::Greater => Ok(Decimal {
+                value: checked(self.value.checked_mul(pow10(scale - self.scale)?))?,
+                scale,
+            }),
+            Ordering::Less => Ok(Decimal {
+                value: div_round(self.value, pow10(self.scale - scale)?),
+                scale,
+            }),
+        }
+    }
+
+    /// Remove any trailing fractional zeroes
+    pub fn normalize(&self) -> Decimal {
+        let mut result = *self;
+        while result.scale > 0 && result.value % 10 == 0 {
+            result.value /= 10;
+            result.scale -= 1;
+        }
+        result
+    }
+
+    /// Separate the integer part of this value from its fractional part, expressed at `MAX_DECIMAL_SCALE`.
+    /// Both parts take the sign of the value.
+    fn split(&self) -> (i128, i128) {
+        // scales never exceed `MAX_DECIMAL_SCALE`, so neither power nor product can overflow
+        let unit = 10i128.pow(self.scale);
+        (self.value / unit, (self.value % unit) * 10i128.pow(MAX_DECIMAL_SCALE - self.scale))
+    }
+
+    pub fn checked_add(&self, other: &Decimal) -> RecordAPIResult<Decimal> {
+        let (a, b, scale) = align(self, other)?;
+        Ok(Decimal { value: checked(a.checked_add(b))?, scale })
+    }
+
+    pub fn checked_sub(&self, other: &Decimal) -> RecordAPIResult<Decimal> {
+        let (a, b, scale) = align(self, other)?;
+        Ok(Decimal { value: checked(a.checked_sub(b))?, scale })
+    }
+
+    pub fn checked_mul(&self, other: &Decimal) -> RecordAPIResult<Decimal> {
+        let (a, b) = (self.normalize(), other.normalize());
+        let product = match a.value.checked_mul(b.value) {
+            Some(value) => Decimal { value, scale: a.scale + b.scale },
+            // digits beyond the maximum scale are discarded from the product in any case,
+            // so drop them from the operands instead to make room for the integer part
+            None => {
+                let (a, b) = reduce_combined_scale(&a, &b, MAX_DECIMAL_SCALE)?;
+                Decimal { value: checked(a.value.checked_mul(b.value))?, scale: a.scale + b.scale }
+            },
+        };
+        if product.scale > MAX_DECIMAL_SCALE {
+            return Ok(product.rescale(MAX_DECIMAL_SCALE)?.normalize());
+        }
+        Ok(product.normalize())
+    }
+
+    pub fn checked_div(&self, other: &Decimal) -> RecordAPIResult<Decimal> {
+        if other.is_zero() {
+            return Err(DataIntegrityError::InvalidDecimal("division by zero".to_string()));
+        }
+        let scale = self.scale.max(other.scale).max(DEFAULT_DIVISION_SCALE);
+        // (a / 10^sa) / (b / 10^sb) = (a * 10^(scale + sb - sa) / b) / 10^scale
+        let numerator = checked(self.value.checked_mul(pow10(scale + other.scale - self.scale)?))?;
+        Ok(Decimal { value: div_round(numerator, other.value), scale }.normalize())
+    }
+}
+
+fn pow10(exp: u32) -> RecordAPIResult<i128> {
+    checked(10i128.checked_pow(exp))
+}
+
+fn checked(result: Option<i128>) -> RecordAPIResult<i128> {
+    result.ok_or_else(|| DataIntegrityError::InvalidDecimal("arithmetic overflow".to_string()))
+}
+
+/// Integer division, rounding half away from zero
+fn div_round(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if remainder.abs() * 2 >= denominator.abs() {
+        if (numerator < 0) == (denominator < 0) { quotient + 1 } else { quotient - 1 }
+    } else {
+        quotient
+    }
+}
+
+/// Round the operands of a multiplication so that their combined scale does not exceed `max_scale`,
+/// removing fractional digits from whichever operand has the most
+fn reduce_combined_scale(a: &Decimal, b: &Decimal, max_scale: u32) -> RecordAPIResult<(Decimal, Decimal)> {
+    let (mut scale_a, mut scale_b) = (a.scale, b.scale);
+    while scale_a + scale_b > max_scale {
+        if scale_a >= scale_b { scale_a -= 1 } else { scale_b -= 1 }
+    }
+    Ok((a.rescale(scale_a)?, b.rescale(scale_b)?))
+}
+
+/// Express both values at the larger of their scales
+fn align(a: &Decimal, b: &Decimal) -> RecordAPIResult<(i128, i128, u32)> {
+    let scale = a.scale.max(b.scale);
+    Ok((a.rescale(scale)?.value, b.rescale(scale)?.value, scale))
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Decimal) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    /// Compares integer and fractional parts separately, so that values of any magnitude are
+    /// ordered exactly without needing to share a scale.
+    fn cmp(&self, other: &Decimal) -> Ordering {
+        let (a_integer, a_fraction) = self.split();
+        let (b_integer, b_fraction) = other.split();
+        a_integer.cmp(&b_integer).then(a_fraction.cmp(&b_fraction))
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Decimal {
+        Decimal { value: value as i128, scale: 0 }
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = DataIntegrityError;
+
+    fn from_str(s: &str) -> RecordAPIResult<Decimal> {
+        let invalid = || DataIntegrityError::InvalidDecimal(format!("'{}' is not a decimal number", s));
+
+        let trimmed = s.trim();
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, fraction),
+            None => (digits, ""),
+        };
+        if (integer.is_empty() && fraction.is_empty())
+            || !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        // discard fractional digits beyond those which can be held alongside the integer part,
+        // rounding half away from zero
+        let integer = integer.trim_start_matches('0');
+        if integer.len() > MAX_DECIMAL_DIGITS {
+            return Err(DataIntegrityError::InvalidDecimal(format!("'{}' exceeds {} significant digits", s, MAX_DECIMAL_DIGITS)));
+        }
+        let scale = (fraction.len() as u32)
+            .min(MAX_DECIMAL_SCALE)
+            .min((MAX_DECIMAL_DIGITS - integer.len()) as u32);
+        let (retained, discarded) = fraction.split_at(scale as usize);
+
+        let mut value: i128 = 0;
+        for c in integer.chars().chain(retained.chars()) {
+            value = checked(value.checked_mul(10).and_then(|v| v.checked_add(c.to_digit(10).unwrap() as i128)))?;
+        }
+        if discarded.starts_with(|c: char| c >= '5') {
+            value = checked(value.checked_add(1))?;
+        }
+
+        Ok(Decimal { value: if negative { -value } else { value }, scale })
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.value < 0 { "-" } else { "" };
+        let digits = self.value.unsigned_abs().to_string();
+        if self.scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+        let scale = self.scale as usize;
+        let padded = format!("{:0>width$}", digits, width = scale + 1);
+        let (integer, fraction) = padded.split_at(padded.len() - scale);
+        write!(f, "{}{}.{}", sign, integer, fraction)
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+struct DecimalVisitor;
+
+impl<'de> Visitor<'de> for DecimalVisitor {
+    type Value = Decimal;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a decimal string or number")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Decimal, E> {
+        Decimal::from_str(v).map_err(E::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Decimal, E> {
+        Ok(Decimal::from(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Decimal, E> {
+        Decimal::new(v as i128, 0).map_err(E::custom)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Decimal, E> {
+        Decimal::from_f64(v).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+        deserializer.deserialize_any(DecimalVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(dec("12.50").to_string(), "12.50");
+        assert_eq!(dec("-0.05").to_string(), "-0.05");
+        assert_eq!(dec("7").to_string(), "7");
+        assert_eq!(dec(".5").to_string(), "0.5");
+        assert!(Decimal::from_str("1.2.3").is_err());
+        assert!(Decimal::from_str("abc").is_err());
+        assert!(Decimal::from_str("").is_err());
+    }
+
+    #[test]
+    fn test_sums_are_exact() {
+        let mut total = Decimal::zero();
+        for _ in 0..10000 {
+            total = total.checked_add(&dec("0.01")).unwrap();
+        }
+        assert_eq!(total, dec("100"));
+        assert_eq!(dec("0.3").checked_sub(&dec("0.1")).unwrap(), dec("0.2"));
+    }
+
+    #[test]
+    fn test_equality_ignores_scale() {
+        assert_eq!(dec("1.50"), dec("1.5"));
+        assert!(dec("1.49") < dec("1.5"));
+        assert!(dec("-2") < dec("-1.999"));
+    }
+
+    #[test]
+    fn test_multiply_divide() {
+        assert_eq!(dec("2.5").checked_mul(&dec("0.4")).unwrap(), dec("1"));
+        assert_eq!(dec("1").checked_div(&dec("3")).unwrap().to_string(), "0.333333333");
+        assert_eq!(dec("2").checked_div(&dec("3")).unwrap().to_string(), "0.666666667");
+        assert_eq!(dec("10").checked_div(&dec("4")).unwrap().to_string(), "2.5");
+        assert!(dec("1").checked_div(&Decimal::zero()).is_err());
+    }
+
+    #[test]
+    fn test_multiply_high_scale_operands() {
+        let large = dec("10000000000.000000000000000001");
+        assert_eq!(large.checked_mul(&large).unwrap(), dec("100000000000000000000"));
+        assert_eq!(dec("1.000000000000000005").checked_mul(&dec("2.000000000000000000")).unwrap(), dec("2.00000000000000001"));
+    }
+
+    #[test]
+    fn test_rescale_rounds_half_away_from_zero() {
+        assert_eq!(dec("1.005").rescale(2).unwrap().to_string(), "1.01");
+        assert_eq!(dec("-1.005").rescale(2).unwrap().to_string(), "-1.01");
+        assert_eq!(dec("1.004").rescale(2).unwrap().to_string(), "1.00");
+        assert_eq!(dec("1.5").rescale(3).unwrap().to_string(), "1.500");
+    }
+
+    #[test]
+    fn test_parse_rounds_excess_fractional_digits() {
+        assert_eq!(dec("0.1234567890123456789").to_string(), "0.123456789012345679");
+        assert_eq!(dec("-0.0000000000000000005").to_string(), "-0.000000000000000001");
+        assert_eq!(dec("12345678901234567890123456789012345678.9").to_string(), "12345678901234567890123456789012345679");
+        assert_eq!(dec("0001.50").to_string(), "1.50");
+        assert!(Decimal::from_str("123456789012345678901234567890123456789").is_err());
+    }
+
+    #[test]
+    fn test_ordering_is_exact_at_any_magnitude() {
+        let large = dec("12345678901234567890123456789012345678");
+        let nearly_large = dec("12345678901234567890.123456789012345678");
+        assert!(large > nearly_large);
+        assert!(large.negate() < nearly_large.negate());
+        assert!(dec("10000000000000000000.000000000000000001") > dec("10000000000000000000"));
+        assert!(dec("-0.5") < dec("0.25"));
+        assert!(dec("-1.5") < dec("-1.25"));
+        assert_eq!(dec("100000000000000000000.0"), dec("100000000000000000000"));
+    }
+
+    #[test]
+    fn test_legacy_float_input() {
+        assert_eq!(Decimal::from_f64(0.1).unwrap(), dec("0.1"));
+        assert_eq!(Decimal::from_f64(8.0).unwrap(), dec("8"));
+        assert!(Decimal::from_f64(f64::NAN).is_err());
+    }
+}
@@ -8,6 +8,10 @@ use holochain_serialized_bytes::prelude::*;
 use vf_attributes_hdk::UnitId;
 use hdk_records::{RecordAPIResult, DataIntegrityError};
 use hdk::prelude::*;
+use std::cmp::Ordering;
+
+mod decimal;
+pub use decimal::*;
 
 #[derive(Debug, Clone)]
 pub struct Unit {
@@ -16,52 +20,218 @@ pub struct Unit {
     pub symbol: Option<String>,
 }
 
+/// A quantity of some unit. Numerical values are held as fixed-point decimals, so that
+/// arithmetic upon them is exact.
+///
+/// The value is serialized both as a plain number (`hasNumericalValue`) for clients expecting one,
+/// and as the exact decimal string `hasDecimalValue`, which takes precedence when deserializing.
+///
 #[derive(Clone, PartialEq, Serialize, Deserialize, SerializedBytes, Debug)]
-#[serde(rename_all = "camelCase")]
+#[serde(try_from = "QuantityValueRepr", into = "QuantityValueRepr")]
 pub struct QuantityValue {
-    // :TODO: https://users.rust-lang.org/t/currency-in-rust/890/9 ?
-    has_numerical_value: f64,     // :NOTE: uses https://en.wikipedia.org/wiki/IEEE_754 for math
+    has_numerical_value: Decimal,
+    has_unit: Option<UnitId>,
+}
+
+/// Wire format of `QuantityValue`
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuantityValueRepr {
+    #[serde(default)]
+    #[serde(serialize_with = "approximate::serialize")]
+    has_numerical_value: Option<Decimal>,
+    #[serde(default)]
+    has_decimal_value: Option<Decimal>,
     #[serde(default)]
     has_unit: Option<UnitId>,
 }
 
+/// Serializes the floating-point approximation of a decimal as a plain number
+mod approximate {
+    use super::Decimal;
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer>(value: &Option<Decimal>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_f64(value.to_f64()),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl TryFrom<QuantityValueRepr> for QuantityValue {
+    type Error = String;
+
+    fn try_from(r: QuantityValueRepr) -> Result<QuantityValue, String> {
+        match r.has_decimal_value.or(r.has_numerical_value) {
+            Some(has_numerical_value) => Ok(QuantityValue { has_numerical_value, has_unit: r.has_unit }),
+            None => Err("QuantityValue must include a numerical value".to_string()),
+        }
+    }
+}
+
+impl From<QuantityValue> for QuantityValueRepr {
+    fn from(q: QuantityValue) -> QuantityValueRepr {
+        QuantityValueRepr {
+            has_numerical_value: Some(q.has_numerical_value),
+            has_decimal_value: Some(q.has_numerical_value),
+            has_unit: q.has_unit,
+        }
+    }
+}
+
 impl<'a> QuantityValue {
-    pub fn new(has_numerical_value: f64, has_unit: Option<UnitId>) -> QuantityValue {
+    pub fn new(has_numerical_value: Decimal, has_unit: Option<UnitId>) -> QuantityValue {
         QuantityValue {
             has_numerical_value,
             has_unit,
         }
     }
 
-    pub fn get_numerical_value(&'a self) -> f64 {
+    /// Create a quantity from a legacy floating-point value
+    pub fn from_f64(has_numerical_value: f64, has_unit: Option<UnitId>) -> RecordAPIResult<QuantityValue> {
+        Ok(QuantityValue::new(Decimal::from_f64(has_numerical_value)?, has_unit))
+    }
+
+    /// Create an empty quantity of the same unit as this one
+    pub fn zeroed(&'a self) -> QuantityValue {
+        QuantityValue::new(Decimal::zero(), self.get_unit())
+    }
+
+    pub fn get_numerical_value(&'a self) -> Decimal {
         self.has_numerical_value.to_owned()
     }
 
     pub fn get_unit(&'a self) -> Option<UnitId> {
         self.has_unit.to_owned()
     }
+
+    /// Express the numerical value with a fixed number of fractional digits
+    pub fn rescale(&'a self, scale: u32) -> RecordAPIResult<QuantityValue> {
+        Ok(QuantityValue::new(self.has_numerical_value.rescale(scale)?, self.get_unit()))
+    }
 }
 
-pub fn add(q1: QuantityValue, q2: QuantityValue) -> RecordAPIResult<QuantityValue> {
+/// Dimension measured by a unit, and its scale relative to the base unit of that dimension.
+/// For example, with kilograms as the base unit of mass, grams have a `conversion_factor` of `0.001`.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitConversion {
+    pub dimension: String,
+    pub conversion_factor: Decimal,
+}
+
+/// Express a quantity in another unit measuring the same dimension
+pub fn convert(q: QuantityValue, from: &UnitConversion, to_unit: Option<UnitId>, to: &UnitConversion) -> RecordAPIResult<QuantityValue> {
+    if from.dimension != to.dimension {
+        return Err(DataIntegrityError::IncompatibleUnitDimensions(from.dimension.to_owned(), to.dimension.to_owned()));
+    }
+    Ok(
+        QuantityValue {
+            has_numerical_value: q.has_numerical_value
+                .checked_mul(&from.conversion_factor)?
+                .checked_div(&to.conversion_factor)?
+                .normalize(),
+            has_unit: to_unit,
+        }
+    )
+}
+
+fn check_units(q1: &QuantityValue, q2: &QuantityValue) -> RecordAPIResult<()> {
     if q1.has_unit != q2.has_unit {
         return Err(DataIntegrityError::MismatchingUnits(q1.get_unit().map(|unit| unit.1), q2.get_unit().map(|unit| unit.1)));
     }
+    Ok(())
+}
+
+pub fn add(q1: QuantityValue, q2: QuantityValue) -> RecordAPIResult<QuantityValue> {
+    check_units(&q1, &q2)?;
     Ok(
         QuantityValue {
-            has_numerical_value: q1.has_numerical_value + q2.has_numerical_value,
+            has_numerical_value: q1.has_numerical_value.checked_add(&q2.has_numerical_value)?,
             has_unit: q1.has_unit,
         }
     )
 }
 
 pub fn subtract(q1: QuantityValue, q2: QuantityValue) -> RecordAPIResult<QuantityValue> {
-    if q1.has_unit != q2.has_unit {
-        return Err(DataIntegrityError::MismatchingUnits(q1.get_unit().map(|unit| unit.1), q2.get_unit().map(|unit| unit.1)));
-    }
+    check_units(&q1, &q2)?;
     Ok(
         QuantityValue {
-            has_numerical_value: q1.has_numerical_value - q2.has_numerical_value,
+            has_numerical_value: q1.has_numerical_value.checked_sub(&q2.has_numerical_value)?,
             has_unit: q1.has_unit,
         }
     )
 }
+
+/// Scale a quantity by some dimensionless factor
+pub fn multiply(q: QuantityValue, factor: Decimal) -> RecordAPIResult<QuantityValue> {
+    Ok(
+        QuantityValue {
+            has_numerical_value: q.has_numerical_value.checked_mul(&factor)?,
+            has_unit: q.has_unit,
+        }
+    )
+}
+
+/// Divide a quantity by some dimensionless divisor
+pub fn divide(q: QuantityValue, divisor: Decimal) -> RecordAPIResult<QuantityValue> {
+    Ok(
+        QuantityValue {
+            has_numerical_value: q.has_numerical_value.checked_div(&divisor)?,
+            has_unit: q.has_unit,
+        }
+    )
+}
+
+/// Determine the dimensionless ratio of two quantities of the same unit
+pub fn ratio(q1: &QuantityValue, q2: &QuantityValue) -> RecordAPIResult<Decimal> {
+    check_units(q1, q2)?;
+    q1.has_numerical_value.checked_div(&q2.has_numerical_value)
+}
+
+/// Compare two quantities of the same unit
+pub fn compare(q1: &QuantityValue, q2: &QuantityValue) -> RecordAPIResult<Ordering> {
+    check_units(q1, q2)?;
+    Ok(q1.has_numerical_value.cmp(&q2.has_numerical_value))
+}
+
+pub fn negate(q: QuantityValue) -> QuantityValue {
+    QuantityValue {
+        has_numerical_value: q.has_numerical_value.negate(),
+        has_unit: q.has_unit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(symbol: &str) -> Option<UnitId> {
+        Some(UnitId(DnaHash::from_raw_36(vec![0; 36]), symbol.to_string()))
+    }
+
+    fn conversion(dimension: &str, factor: &str) -> UnitConversion {
+        UnitConversion { dimension: dimension.to_string(), conversion_factor: factor.parse().unwrap() }
+    }
+
+    #[test]
+    fn test_convert_compatible_units() {
+        let grams = QuantityValue::new("1500".parse().unwrap(), unit("g"));
+        let kilos = convert(grams, &conversion("mass", "0.001"), unit("kg"), &conversion("mass", "1")).unwrap();
+        assert_eq!(kilos.get_unit(), unit("kg"));
+        assert_eq!(kilos.get_numerical_value().to_string(), "1.5");
+
+        let total = add(QuantityValue::new("2".parse().unwrap(), unit("kg")), kilos).unwrap();
+        assert_eq!(total.get_numerical_value().to_string(), "3.5");
+    }
+
+    #[test]
+    fn test_convert_rejects_incompatible_dimensions() {
+        let hours = QuantityValue::new("1".parse().unwrap(), unit("h"));
+        assert!(matches!(
+            convert(hours, &conversion("time", "3600"), unit("kg"), &conversion("mass", "1")),
+            Err(DataIntegrityError::IncompatibleUnitDimensions(_, _))
+        ));
+    }
+}
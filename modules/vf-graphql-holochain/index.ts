@@ -86,6 +86,9 @@ const bindSchema = async (options: BindSchemaOptions) => {
   // or more holochain specific schema extensions.
   // add more here if more are added.
   const overriddenExtensionSchemas = [...extensionSchemas, hreaExtensionSchemas.associateMyAgentExtension]
+  if (enabledVFModules.includes(VfModule.Observation)) {
+    overriddenExtensionSchemas.push(hreaExtensionSchemas.countersignedEventExtension)
+  }
 
   return makeExecutableSchema({
     typeDefs: printSchema(buildSchema(enabledVFModules, overriddenExtensionSchemas)),
@@ -18,6 +18,12 @@ import {
   OrganizationResponse,
   AccountingScope,
   Person,
+  AgentRelationshipCreateParams,
+  AgentRelationshipUpdateParams,
+  AgentRelationshipResponse,
+  AgentRelationshipRoleCreateParams,
+  AgentRelationshipRoleUpdateParams,
+  AgentRelationshipRoleResponse,
 } from '@valueflows/vf-graphql'
 
 // export type AgentResponse = OrganizationResponse
@@ -51,6 +57,26 @@ export interface AgentUpdateArgs {
     agent: OrganizationUpdateParams,
 }
 
+export interface AgentRelationshipCreateArgs {
+  relationship: AgentRelationshipCreateParams,
+}
+export type createAgentRelationshipHandler = (root: any, args: AgentRelationshipCreateArgs) => Promise<AgentRelationshipResponse>
+
+export interface AgentRelationshipUpdateArgs {
+  relationship: AgentRelationshipUpdateParams,
+}
+export type updateAgentRelationshipHandler = (root: any, args: AgentRelationshipUpdateArgs) => Promise<AgentRelationshipResponse>
+
+export interface AgentRelationshipRoleCreateArgs {
+  agentRelationshipRole: AgentRelationshipRoleCreateParams,
+}
+export type createAgentRelationshipRoleHandler = (root: any, args: AgentRelationshipRoleCreateArgs) => Promise<AgentRelationshipRoleResponse>
+
+export interface AgentRelationshipRoleUpdateArgs {
+  agentRelationshipRole: AgentRelationshipRoleUpdateParams,
+}
+export type updateAgentRelationshipRoleHandler = (root: any, args: AgentRelationshipRoleUpdateArgs) => Promise<AgentRelationshipRoleResponse>
+
 export interface AssociateAgentParams {
   agentAddress: AgentAddress,
 }
@@ -60,6 +86,12 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runAssociateMyAgent = mapZomeFn<AssociateAgentParams, boolean>(dnaConfig, conductorUri, 'agent', 'agent', 'associate_my_agent')
   const runUpdateAgent = mapZomeFn<AgentUpdateArgs, AgentResponse>(dnaConfig, conductorUri, 'agent', 'agent', 'update_agent')
   const runDeleteAgent = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'agent', 'agent', 'delete_agent')
+  const runCreateRelationship = mapZomeFn<{ agentRelationship: AgentRelationshipCreateParams }, AgentRelationshipResponse>(dnaConfig, conductorUri, 'agent', 'agent_relationship', 'create_agent_relationship')
+  const runUpdateRelationship = mapZomeFn<{ agentRelationship: AgentRelationshipUpdateParams }, AgentRelationshipResponse>(dnaConfig, conductorUri, 'agent', 'agent_relationship', 'update_agent_relationship')
+  const runDeleteRelationship = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'agent', 'agent_relationship', 'delete_agent_relationship')
+  const runCreateRelationshipRole = mapZomeFn<AgentRelationshipRoleCreateArgs, AgentRelationshipRoleResponse>(dnaConfig, conductorUri, 'agent', 'agent_relationship_role', 'create_agent_relationship_role')
+  const runUpdateRelationshipRole = mapZomeFn<AgentRelationshipRoleUpdateArgs, AgentRelationshipRoleResponse>(dnaConfig, conductorUri, 'agent', 'agent_relationship_role', 'update_agent_relationship_role')
+  const runDeleteRelationshipRole = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'agent', 'agent_relationship_role', 'delete_agent_relationship_role')
 
   const createPerson: createPersonHandler = async (root, args) => {
     const createAgentArgs = {
@@ -111,23 +143,28 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
     return runDeleteAgent(args)
   }
 
-  const createAgentRelationship = () => {
-    throw new Error('mutation unimplemented')
+  const createAgentRelationship: createAgentRelationshipHandler = async (root, args) => {
+    return runCreateRelationship({ agentRelationship: args.relationship })
   }
-  const updateAgentRelationship = () => {
-    throw new Error('mutation unimplemented')
+
+  const updateAgentRelationship: updateAgentRelationshipHandler = async (root, args) => {
+    return runUpdateRelationship({ agentRelationship: args.relationship })
   }
-  const deleteAgentRelationship = () => {
-    throw new Error('mutation unimplemented')
+
+  const deleteAgentRelationship: deleteHandler = async (root, args) => {
+    return runDeleteRelationship(args)
   }
-  const createAgentRelationshipRole = () => {
-    throw new Error('mutation unimplemented')
+
+  const createAgentRelationshipRole: createAgentRelationshipRoleHandler = async (root, args) => {
+    return runCreateRelationshipRole(args)
   }
-  const updateAgentRelationshipRole = () => {
-    throw new Error('mutation unimplemented')
+
+  const updateAgentRelationshipRole: updateAgentRelationshipRoleHandler = async (root, args) => {
+    return runUpdateRelationshipRole(args)
   }
-  const deleteAgentRelationshipRole = () => {
-    throw new Error('mutation unimplemented')
+
+  const deleteAgentRelationshipRole: deleteHandler = async (root, args) => {
+    return runDeleteRelationshipRole(args)
   }
 
   return {
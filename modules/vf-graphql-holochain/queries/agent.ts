@@ -18,7 +18,13 @@ import {
   Organization,
   OrganizationConnection,
   Person,
-  PersonConnection
+  PersonConnection,
+  AgentRelationship,
+  AgentRelationshipConnection,
+  AgentRelationshipResponse,
+  AgentRelationshipRole,
+  AgentRelationshipRoleConnection,
+  AgentRelationshipRoleResponse,
 } from '@valueflows/vf-graphql'
 import { AgentPubKey } from '@holochain/client'
 import { AgentResponse } from '../mutations/agent'
@@ -53,17 +59,22 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const readAll = mapZomeFn<PagingParams, AgentConnectionWithType>(dnaConfig, conductorUri, 'agent', 'agent_index', 'read_all_agents')
   const readAllAgentType = mapZomeFn<AgentSearchInput, AgentConnection>(dnaConfig, conductorUri, 'agent', 'agent_index', 'query_agents')
 
-  const agentRelationship = () => {
-    throw new Error('query unimplemented')
+  const readRelationship = mapZomeFn<ReadParams, AgentRelationshipResponse>(dnaConfig, conductorUri, 'agent', 'agent_relationship', 'get_agent_relationship')
+  const readAllRelationships = mapZomeFn<PagingParams, AgentRelationshipConnection>(dnaConfig, conductorUri, 'agent', 'agent_relationship_index', 'read_all_agent_relationships')
+  const readRelationshipRole = mapZomeFn<ReadParams, AgentRelationshipRoleResponse>(dnaConfig, conductorUri, 'agent', 'agent_relationship_role', 'get_agent_relationship_role')
+  const readAllRelationshipRoles = mapZomeFn<PagingParams, AgentRelationshipRoleConnection>(dnaConfig, conductorUri, 'agent', 'agent_relationship_role_index', 'read_all_agent_relationship_roles')
+
+  const agentRelationship = async (root, args): Promise<AgentRelationship> => {
+    return (await readRelationship({ address: args.id })).agentRelationship
   }
-  const agentRelationships = () => {
-    throw new Error('query unimplemented')
+  const agentRelationships = async (root, args: PagingParams): Promise<AgentRelationshipConnection> => {
+    return await readAllRelationships(args)
   }
-  const agentRelationshipRole = () => {
-    throw new Error('query unimplemented')
+  const agentRelationshipRole = async (root, args): Promise<AgentRelationshipRole> => {
+    return (await readRelationshipRole({ address: args.id })).agentRelationshipRole
   }
-  const agentRelationshipRoles = () => {
-    throw new Error('query unimplemented')
+  const agentRelationshipRoles = async (root, args: PagingParams): Promise<AgentRelationshipRoleConnection> => {
+    return await readAllRelationshipRoles(args)
   }
 
   return {
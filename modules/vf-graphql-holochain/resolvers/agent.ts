@@ -24,7 +24,7 @@ import {
 } from '@valueflows/vf-graphql'
 import { extractEdges, mapZomeFn } from '../connection.js'
 import { DNAIdMappings, DEFAULT_VF_MODULES, VfModule, ByRevision, AddressableIdentifier } from '../types.js'
-import { CommitmentSearchInput, EconomicEventSearchInput, EconomicResourceSearchInput, IntentSearchInput, PlanSearchInput, ProcessSearchInput, ProposalSearchInput } from './zomeSearchInputTypes.js'
+import { AgentRelationshipSearchInput, CommitmentSearchInput, EconomicEventSearchInput, EconomicResourceSearchInput, IntentSearchInput, PlanSearchInput, ProcessSearchInput, ProposalSearchInput } from './zomeSearchInputTypes.js'
 
 
 export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DNAIdMappings, conductorUri: string) => {
@@ -44,6 +44,7 @@ export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DN
   const queryEconomicResources = mapZomeFn<EconomicResourceSearchInput, EconomicResourceConnection>(dnaConfig, conductorUri, 'observation', 'economic_resource_index', 'query_economic_resources')
   const queryPlans = mapZomeFn<PlanSearchInput, PlanConnection>(dnaConfig, conductorUri, 'plan', 'plan_index', 'query_plans')
   const queryProposals = mapZomeFn<ProposalSearchInput, ProposalConnection>(dnaConfig, conductorUri, 'plan', 'plan_index', 'query_plans')
+  const queryRelationships = mapZomeFn<AgentRelationshipSearchInput, AgentRelationshipConnection>(dnaConfig, conductorUri, 'agent', 'agent_relationship_index', 'query_agent_relationships')
 
   return Object.assign(
     { __resolveType: (obj, ctx, info) => obj.__typename },
@@ -52,10 +53,10 @@ export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DN
         throw new Error('resolver unimplemented')
       },
       relationshipsAsSubject: async (record: Agent): Promise<AgentRelationshipConnection> => {
-        throw new Error('resolver unimplemented')
+        return await queryRelationships({ params: { subject: record.id } })
       },
       relationshipsAsObject: async (record: Agent): Promise<AgentRelationshipConnection> => {
-        throw new Error('resolver unimplemented')
+        return await queryRelationships({ params: { object: record.id } })
       },
       roles: async (record: Agent): Promise<AgentRelationshipRole[]> => {
         throw new Error('resolver unimplemented')
@@ -0,0 +1,35 @@
+/**
+ * Resolvers for AgentRelationship fields
+ *
+ * @package: hREA
+ * @since:   2026-10-18
+ */
+
+import { DNAIdMappings, DEFAULT_VF_MODULES, VfModule, AgentAddress, AgentRelationshipRoleAddress } from '../types.js'
+
+import {
+  Agent,
+  AgentRelationshipRole,
+} from '@valueflows/vf-graphql'
+import agentQueries from '../queries/agent.js'
+
+export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DNAIdMappings, conductorUri: string) => {
+  const queries = agentQueries(dnaConfig, conductorUri)
+  const readAgent = queries['agent']
+  const readRole = queries['agentRelationshipRole']
+
+  return {
+    subject: async (record: { subject: AgentAddress }): Promise<Agent> => {
+      return readAgent(record, { id: record.subject })
+    },
+    object: async (record: { object: AgentAddress }): Promise<Agent> => {
+      return readAgent(record, { id: record.object })
+    },
+    relationship: async (record: { relationship: AgentRelationshipRoleAddress }): Promise<AgentRelationshipRole> => {
+      return readRole(record, { id: record.relationship })
+    },
+    inScopeOf: async (record: { inScopeOf: AgentAddress[] }): Promise<Agent[]> => {
+      return (await Promise.all((record.inScopeOf || []).map((id) => readAgent(record, { id }))))
+    },
+  }
+}
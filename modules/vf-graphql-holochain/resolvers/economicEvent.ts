@@ -30,6 +30,7 @@ import agentQueries from '../queries/agent.js'
 import agreementQueries from '../queries/agreement.js'
 import resourceQueries from '../queries/economicResource.js'
 import { FulfillmentSearchInput, ProcessSearchInput, SatisfactionSearchInput } from './zomeSearchInputTypes.js'
+import trackTraceResolvers from './trackTrace.js'
 
 export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DNAIdMappings, conductorUri: string) => {
   const hasHistory = -1 !== enabledVFModules.indexOf(VfModule.History)
@@ -50,6 +51,7 @@ export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DN
   const readAgent = agentQueries(dnaConfig, conductorUri)['agent']
   const readAgreement = agreementQueries(dnaConfig, conductorUri)['agreement']
   const readResource = resourceQueries(dnaConfig, conductorUri)['economicResource']
+  const trackTrace = trackTraceResolvers(dnaConfig, conductorUri)
 
   return Object.assign(
     {
@@ -73,12 +75,7 @@ export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DN
       next: () => {
         throw new Error('resolver unimplemented')
       },
-      track: () => {
-        throw new Error('resolver unimplemented')
-      },
-      trace: () => {
-        throw new Error('resolver unimplemented')
-      },
+      ...trackTrace('EconomicEvent'),
     },
     (hasProcess ? {
       inputOf: async (record: EconomicEvent): Promise<Process> => {
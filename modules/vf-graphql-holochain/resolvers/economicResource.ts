@@ -25,6 +25,7 @@ import {
 import { EconomicResourceSearchInput } from './zomeSearchInputTypes.js'
 import { AgentResponse } from '../mutations/agent'
 import agentQueries from '../queries/agent.js'
+import trackTraceResolvers from './trackTrace.js'
 
 export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DNAIdMappings, conductorUri: string) => {
   const hasHistory = -1 !== enabledVFModules.indexOf(VfModule.History)
@@ -43,6 +44,7 @@ export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DN
   const readAction = mapZomeFn<ById, Action>(dnaConfig, conductorUri, 'specification', 'action', 'get_action')
   const readResourceSpecification = mapZomeFn<ReadParams, ResourceSpecificationResponse>(dnaConfig, conductorUri, 'specification', 'resource_specification', 'get_resource_specification')
   const readAgent = agentQueries(dnaConfig, conductorUri)['agent']
+  const trackTrace = trackTraceResolvers(dnaConfig, conductorUri)
 
   return Object.assign(
     {
@@ -73,12 +75,7 @@ export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DN
       next: () => {
         throw new Error('resolver unimplemented')
       },
-      track: () => {
-        throw new Error('resolver unimplemented')
-      },
-      trace: () => {
-        throw new Error('resolver unimplemented')
-      },
+      ...trackTrace('EconomicResource'),
     },
     (hasCommitment ? {
       commitments: () => {
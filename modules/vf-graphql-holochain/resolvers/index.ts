@@ -20,6 +20,7 @@ import Unit from './unit.js'
 import ResourceSpecification from './resourceSpecification.js'
 
 import Agent from './agent.js'
+import AgentRelationship from './agentRelationship.js'
 
 import Process from './process.js'
 import ProcessSpecification from './processSpecification.js'
@@ -104,6 +105,7 @@ const generateResolvers = async (options: ResolverOptions) => {
       Agent: Agent(enabledVFModules, dnaConfig, conductorUri),
       Person: Agent(enabledVFModules, dnaConfig, conductorUri),
       Organization: Agent(enabledVFModules, dnaConfig, conductorUri),
+      AgentRelationship: AgentRelationship(enabledVFModules, dnaConfig, conductorUri),
     } : {}),
     (hasMeasurement ? {
       Measure: Measure(enabledVFModules, dnaConfig, conductorUri),
@@ -20,11 +20,12 @@ import {
   CommitmentConnection,
   IntentConnection,
   ProcessSpecificationResponse,
+  ProcessConnection,
   Agent,
   AccountingScope
 } from '@valueflows/vf-graphql'
 import planQueries from '../queries/plan.js'
-import { CommitmentSearchInput, EconomicEventSearchInput, IntentSearchInput } from './zomeSearchInputTypes.js'
+import { CommitmentSearchInput, EconomicEventSearchInput, IntentSearchInput, ProcessFlowSearchInput } from './zomeSearchInputTypes.js'
 import { AgentResponse } from '../mutations/agent.js'
 
 export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DNAIdMappings, conductorUri: string) => {
@@ -37,6 +38,8 @@ export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DN
   const hasAgent = -1 !== enabledVFModules.indexOf(VfModule.Agent)
 
   const readRevision = mapZomeFn<ByRevision, ProcessResponse>(dnaConfig, conductorUri, 'observation', 'process', 'get_revision')
+  const readNextProcesses = mapZomeFn<ProcessFlowSearchInput, ProcessConnection>(dnaConfig, conductorUri, 'observation', 'process_index', 'query_next_processes')
+  const readPreviousProcesses = mapZomeFn<ProcessFlowSearchInput, ProcessConnection>(dnaConfig, conductorUri, 'observation', 'process_index', 'query_previous_processes')
   const readEvents = mapZomeFn<EconomicEventSearchInput, EconomicEventConnection>(dnaConfig, conductorUri, 'observation', 'economic_event_index', 'query_economic_events')
   const readCommitments = mapZomeFn<CommitmentSearchInput, CommitmentConnection>(dnaConfig, conductorUri, 'planning', 'commitment_index', 'query_commitments')
   const readIntents = mapZomeFn<IntentSearchInput, IntentConnection>(dnaConfig, conductorUri, 'planning', 'intent_index', 'query_intents')
@@ -46,11 +49,11 @@ export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DN
 
   return Object.assign(
     {
-      nextProcesses: () => {
-        throw new Error('resolver unimplemented')
+      nextProcesses: async (record: Process): Promise<Process[]> => {
+        return extractEdges(await readNextProcesses({ process: record.id }))
       },
-      previousProcesses: () => {
-        throw new Error('resolver unimplemented')
+      previousProcesses: async (record: Process): Promise<Process[]> => {
+        return extractEdges(await readPreviousProcesses({ process: record.id }))
       },
     },
     (hasObservation ? {
@@ -0,0 +1,68 @@
+/**
+ * Shared resolvers for `track` & `trace` fields on flow records
+ *
+ * @package: hREA
+ * @since:   2026-10-18
+ */
+
+import { DNAIdMappings, EconomicEventAddress, EconomicResourceAddress, ProcessAddress, addTypename } from '../types.js'
+import { mapZomeFn } from '../connection.js'
+
+import {
+  EconomicEvent,
+  EconomicResource,
+  Process,
+} from '@valueflows/vf-graphql'
+
+import eventQueries from '../queries/economicEvent.js'
+import resourceQueries from '../queries/economicResource.js'
+import processQueries from '../queries/process.js'
+
+export type TrackTraceItemId =
+  { EconomicEvent: EconomicEventAddress } |
+  { EconomicResource: EconomicResourceAddress } |
+  { Process: ProcessAddress }
+
+export interface TrackTraceParams {
+  start: TrackTraceItemId,
+  maxDepth?: number,
+}
+
+export interface FlowGraph {
+  nodes: { item: TrackTraceItemId, depth: number }[],
+  edges: { from: TrackTraceItemId, to: TrackTraceItemId }[],
+}
+
+type TrackTraceItem = EconomicEvent | EconomicResource | Process
+
+export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
+  const runTrace = mapZomeFn<TrackTraceParams, FlowGraph>(dnaConfig, conductorUri, 'observation', 'economic_event', 'trace')
+  const runTrack = mapZomeFn<TrackTraceParams, FlowGraph>(dnaConfig, conductorUri, 'observation', 'economic_event', 'track')
+  const readEvent = eventQueries(dnaConfig, conductorUri)['economicEvent']
+  const readResource = resourceQueries(dnaConfig, conductorUri)['economicResource']
+  const readProcess = processQueries(dnaConfig, conductorUri)['process']
+
+  const readItem = async (item: TrackTraceItemId): Promise<TrackTraceItem> => {
+    if ('EconomicEvent' in item) {
+      return readEvent(null, { id: item.EconomicEvent })
+    }
+    if ('EconomicResource' in item) {
+      return addTypename<EconomicResource>('EconomicResource')(await readResource(null, { id: item.EconomicResource }))
+    }
+    return addTypename<Process>('Process')(await readProcess(null, { id: item.Process }))
+  }
+
+  // load all records reached by the traversal, excluding the starting record
+  const readFlows = async (graph: FlowGraph): Promise<TrackTraceItem[]> => {
+    return Promise.all(graph.nodes.filter(({ depth }) => depth > 0).map(({ item }) => readItem(item)))
+  }
+
+  return (startType: 'EconomicEvent' | 'EconomicResource' | 'Process') => ({
+    track: async (record: { id: string }): Promise<TrackTraceItem[]> => {
+      return readFlows(await runTrack({ start: { [startType]: record.id } as TrackTraceItemId }))
+    },
+    trace: async (record: { id: string }): Promise<TrackTraceItem[]> => {
+      return readFlows(await runTrace({ start: { [startType]: record.id } as TrackTraceItemId }))
+    },
+  })
+}
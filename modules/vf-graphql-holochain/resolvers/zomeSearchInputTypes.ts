@@ -1,17 +1,16 @@
-import { EntryHash } from "@holochain/client"
-import { AgentAddress, AgreementAddress, CommitmentAddress, EconomicEventAddress, EconomicResourceAddress, FulfillmentAddress, IntentAddress, PlanAddress, ProcessAddress, ProposedIntentAddress, ResourceSpecificationAddress, SatisfactionAddress } from "../types";
+import { AgentAddress, AgentRelationshipAddress, AgentRelationshipRoleAddress, AgreementAddress, CommitmentAddress, EconomicEventAddress, EconomicResourceAddress, FulfillmentAddress, IntentAddress, PlanAddress, ProcessAddress, ProposedIntentAddress, ResourceSpecificationAddress, SatisfactionAddress } from "../types";
 
 // this type name
 // matches the Rust side type name
 export interface PagingParams {
-    // TODO: forwards pagination
-    // first: number
-    // after: string
+    first?: number,
+    after?: string,
     last?: number,
-    before?: EntryHash
+    before?: string,
 }
 
-interface SearchInput<QueryParamType> {
+// all filters in `params` must match, and results are paginated as for `PagingParams`
+interface SearchInput<QueryParamType> extends PagingParams {
   params: QueryParamType,
 }
 
@@ -26,6 +25,13 @@ export type IntentSearchInput = SearchInput<IntentQueryParams>
 export type PlanSearchInput = SearchInput<PlanQueryParams>
 export type ProposalSearchInput = SearchInput<ProposalQueryParams>
 export type AgentSearchInput = SearchInput<AgentQueryParams>
+export type AgentRelationshipSearchInput = SearchInput<AgentRelationshipQueryParams>
+export type AgentRelationshipRoleSearchInput = SearchInput<AgentRelationshipRoleQueryParams>
+
+// processes connected to `process` by resource flows, paginated as for `PagingParams`
+export interface ProcessFlowSearchInput extends PagingParams {
+  process: ProcessAddress,
+}
 
 interface CommitmentQueryParam {
     inputOf?: ProcessAddress,
@@ -38,6 +44,7 @@ interface CommitmentQueryParam {
     inScopeOf?: AgentAddress,
     provider?: AgentAddress,
     receiver?: AgentAddress,
+    resourceConformsTo?: ResourceSpecificationAddress,
 }
 
 interface EconomicEventQueryParams {
@@ -76,6 +83,8 @@ interface ProcessQueryParams {
 
 interface ResourceSpecificationQueryParams {
     conformingResources?: EconomicResourceAddress,
+    commitments?: CommitmentAddress,
+    intents?: IntentAddress,
 }
 interface EconomicResourceQueryParams {
     contains?: EconomicResourceAddress,
@@ -92,6 +101,7 @@ interface IntentQueryParams {
     inScopeOf?: AgentAddress,
     provider?: AgentAddress,
     receiver?: AgentAddress,
+    resourceConformsTo?: ResourceSpecificationAddress,
 }
 
 interface PlanQueryParams {
@@ -104,4 +114,16 @@ interface ProposalQueryParams {
 
 interface AgentQueryParams {
     agentType?: string,
+    relationshipsAsSubject?: AgentRelationshipAddress,
+    relationshipsAsObject?: AgentRelationshipAddress,
+}
+
+interface AgentRelationshipQueryParams {
+    subject?: AgentAddress,
+    object?: AgentAddress,
+    relationship?: AgentRelationshipRoleAddress,
+}
+
+interface AgentRelationshipRoleQueryParams {
+    agentRelationships?: AgentRelationshipAddress,
 }
@@ -5,4 +5,11 @@ type Mutation  {
 }
 `;
 
-export { associateMyAgentExtension };
+const countersignedEventExtension = `
+type EconomicEvent {
+    "Whether the event was countersigned by both its provider and receiver."
+    countersigned: Boolean
+}
+`;
+
+export { associateMyAgentExtension, countersignedEventExtension };
@@ -92,6 +92,8 @@ export type PlanAddress = AddressableIdentifier
 export type ProposalAddress = AddressableIdentifier
 export type IntentAddress = AddressableIdentifier
 export type AgentAddress = AddressableIdentifier
+export type AgentRelationshipAddress = AddressableIdentifier
+export type AgentRelationshipRoleAddress = AddressableIdentifier
 export type EconomicResourceAddress = AddressableIdentifier
 export type EconomicEventAddress = AddressableIdentifier
 export type ResourceSpecificationAddress = AddressableIdentifier
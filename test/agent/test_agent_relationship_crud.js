@@ -0,0 +1,114 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import { buildPlayer } from '../init.js'
+
+const exampleRole = {
+  roleLabel: 'member of',
+  inverseRoleLabel: 'has member',
+  note: 'test role note',
+}
+
+test('AgentRelationship record API', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['agent'])
+  try {
+    const { cells: [agent] } = alice
+
+    // SCENARIO: two agents and a role relating them
+    const personResp = await agent.call('agent', 'create_agent', { agent: { name: 'test person', agentType: 'Person' } })
+    const orgResp = await agent.call('agent', 'create_agent', { agent: { name: 'test organization', agentType: 'Organization' } })
+    const personId = personResp.agent.id
+    const orgId = orgResp.agent.id
+
+    const roleResp = await agent.call('agent_relationship_role', 'create_agent_relationship_role', { agentRelationshipRole: exampleRole })
+    await pause(100)
+    const roleId = roleResp.agentRelationshipRole.id
+    t.ok(roleId, 'role created')
+
+    let readResp = await agent.call('agent_relationship_role', 'get_agent_relationship_role', { address: roleId })
+    t.equal(readResp.agentRelationshipRole.roleLabel, exampleRole.roleLabel, 'role read OK')
+    t.equal(readResp.agentRelationshipRole.inverseRoleLabel, exampleRole.inverseRoleLabel, 'role inverse label read OK')
+
+    // ACT: relate the agents
+    const relResp = await agent.call('agent_relationship', 'create_agent_relationship', {
+      agentRelationship: { subject: personId, object: orgId, relationship: roleId, note: 'test relationship' },
+    })
+    await pause(100)
+    const relId = relResp.agentRelationship.id
+    t.ok(relId, 'relationship created')
+
+    readResp = await agent.call('agent_relationship', 'get_agent_relationship', { address: relId })
+    t.deepLooseEqual(readResp.agentRelationship.subject, personId, 'subject read OK')
+    t.deepLooseEqual(readResp.agentRelationship.object, orgId, 'object read OK')
+    t.deepLooseEqual(readResp.agentRelationship.relationship, roleId, 'relationship role read OK')
+
+    // ASSERT: relationship indexes
+    readResp = await agent.call('agent_relationship_index', 'query_agent_relationships', { params: { subject: personId } })
+    t.equal(readResp.edges.length, 1, 'relationship queryable by subject')
+    t.deepLooseEqual(readResp.edges[0].node.id, relId, 'subject query OK')
+
+    readResp = await agent.call('agent_relationship_index', 'query_agent_relationships', { params: { object: orgId } })
+    t.equal(readResp.edges.length, 1, 'relationship queryable by object')
+    t.deepLooseEqual(readResp.edges[0].node.id, relId, 'object query OK')
+
+    readResp = await agent.call('agent_relationship_index', 'query_agent_relationships', { params: { relationship: roleId } })
+    t.equal(readResp.edges.length, 1, 'relationship queryable by role')
+    t.deepLooseEqual(readResp.edges[0].node.id, relId, 'role query OK')
+
+    readResp = await agent.call('agent', 'get_agent', { address: personId })
+    t.deepLooseEqual(readResp.agent.relationshipsAsSubject, [relId], 'reciprocal subject index OK')
+    readResp = await agent.call('agent', 'get_agent', { address: orgId })
+    t.deepLooseEqual(readResp.agent.relationshipsAsObject, [relId], 'reciprocal object index OK')
+
+    readResp = await agent.call('agent_relationship_role_index', 'query_agent_relationship_roles', { params: { agentRelationships: relId } })
+    t.equal(readResp.edges.length, 1, 'role queryable by relationship')
+    t.deepLooseEqual(readResp.edges[0].node.id, roleId, 'reciprocal role index OK')
+
+    // ACT: swap the subject and object of the relationship
+    const updateResp = await agent.call('agent_relationship', 'update_agent_relationship', {
+      agentRelationship: { revisionId: relResp.agentRelationship.revisionId, subject: orgId, object: personId },
+    })
+    await pause(100)
+    t.deepLooseEqual(updateResp.agentRelationship.subject, orgId, 'relationship updated')
+
+    readResp = await agent.call('agent_relationship_index', 'query_agent_relationships', { params: { subject: personId } })
+    t.equal(readResp.edges.length, 0, 'stale subject index removed')
+    readResp = await agent.call('agent_relationship_index', 'query_agent_relationships', { params: { subject: orgId } })
+    t.equal(readResp.edges.length, 1, 'updated subject index created')
+
+    // ACT: update the role
+    const roleUpdateResp = await agent.call('agent_relationship_role', 'update_agent_relationship_role', {
+      agentRelationshipRole: { revisionId: roleResp.agentRelationshipRole.revisionId, roleLabel: 'employee of' },
+    })
+    await pause(100)
+    t.equal(roleUpdateResp.agentRelationshipRole.roleLabel, 'employee of', 'role updated')
+    t.equal(roleUpdateResp.agentRelationshipRole.inverseRoleLabel, exampleRole.inverseRoleLabel, 'unchanged role fields retained')
+
+    // ACT: delete the relationship
+    const deleteResp = await agent.call('agent_relationship', 'delete_agent_relationship', { revisionId: updateResp.agentRelationship.revisionId })
+    await pause(100)
+    t.equal(deleteResp, true, 'relationship deleted')
+
+    readResp = await agent.call('agent_relationship_index', 'query_agent_relationships', { params: { relationship: roleId } })
+    t.equal(readResp.edges.length, 0, 'role index removed on delete')
+    readResp = await agent.call('agent', 'get_agent', { address: orgId })
+    t.equal((readResp.agent.relationshipsAsSubject || []).length, 0, 'reciprocal subject index removed on delete')
+
+    let err
+    try {
+      await agent.call('agent_relationship', 'get_agent_relationship', { address: relId })
+    } catch (e) {
+      err = e
+    }
+    t.ok(err, 'querying deleted record is an error')
+
+    const roleDeleteResp = await agent.call('agent_relationship_role', 'delete_agent_relationship_role', { revisionId: roleUpdateResp.agentRelationshipRole.revisionId })
+    t.equal(roleDeleteResp, true, 'role deleted')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
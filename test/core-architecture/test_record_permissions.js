@@ -0,0 +1,92 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayers,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+  resourceClassifiedAs: ['http://www.productontology.org/doc/Apple.ttl'],
+}
+
+test('Record modification permissions', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const { players: [alice, bob], scenario } = await buildPlayers(2, ['observation'])
+  try {
+    const { cells: [aliceObservation] } = alice
+    const { cells: [bobObservation] } = bob
+    const resourceUnitId = mockIdentifier(false)
+    const qty = (hasNumericalValue) => ({ hasNumericalValue, hasDecimalValue: String(hasNumericalValue), hasUnit: resourceUnitId })
+    const rejection = async (call) => {
+      try {
+        await call()
+      } catch (e) {
+        return e.data.data
+      }
+      return ''
+    }
+
+    // SCENARIO: Alice creates a process and revises it twice
+    let resp = await aliceObservation.call('process', 'create_process', { process: { name: 'first' } })
+    await pause(100)
+    resp = await aliceObservation.call('process', 'update_process', { process: { revisionId: resp.process.revisionId, name: 'second' } })
+    await pause(100)
+    resp = await aliceObservation.call('process', 'update_process', { process: { revisionId: resp.process.revisionId, name: 'third' } })
+    const processRevision = resp.process.revisionId
+    await pause(1000)
+
+    // ASSERT: authorship is determined by the record's creator, not its latest revision
+    let err = await rejection(() => bobObservation.call('process', 'update_process', { process: { revisionId: processRevision, name: 'hijacked' } }))
+    t.notEqual(err.indexOf('original author or a record administrator'), -1, 'other agents cannot update revised records')
+    err = await rejection(() => bobObservation.call('process', 'delete_process', { revisionId: processRevision }))
+    t.notEqual(err.indexOf('original author or a record administrator'), -1, 'other agents cannot delete revised records')
+
+    resp = await aliceObservation.call('process', 'update_process', { process: { revisionId: processRevision, name: 'fourth' } })
+    t.equal(resp.process.name, 'fourth', 'creator can update later revisions')
+
+    // SCENARIO: Alice holds resources which Bob observes events against
+    resp = await aliceObservation.call('economic_event', 'create_economic_event', {
+      event: { ...testEventProps, action: 'raise', resourceQuantity: qty(10) },
+      newInventoriedResource: { name: 'apples' },
+    })
+    const resourceId = resp.economicResource.id
+    resp = await aliceObservation.call('economic_event', 'create_economic_event', {
+      event: { ...testEventProps, action: 'raise', resourceQuantity: qty(0) },
+      newInventoriedResource: { name: 'stored apples' },
+    })
+    const destResourceId = resp.economicResource.id
+    await pause(1000)
+
+    // ASSERT: other agents may alter quantities of resources they did not create
+    resp = await bobObservation.call('economic_event', 'create_economic_event', {
+      event: { ...testEventProps, action: 'lower', resourceInventoriedAs: resourceId, resourceQuantity: qty(2) },
+    })
+    t.ok(resp.economicEvent, 'quantity-altering event observed by another agent')
+    await pause(1000)
+
+    // ASSERT: but not reassign their location or accountability
+    err = await rejection(() => bobObservation.call('economic_event', 'create_economic_event', {
+      event: { ...testEventProps, action: 'move', resourceInventoriedAs: resourceId, toResourceInventoriedAs: destResourceId, atLocation: mockAddress(false), resourceQuantity: qty(1) },
+    }))
+    t.notEqual(err.indexOf('other than quantities and classifications'), -1, 'other agents cannot move resources they did not create')
+    err = await rejection(() => bobObservation.call('economic_event', 'create_economic_event', {
+      event: { ...testEventProps, action: 'transfer-all-rights', receiver: mockAddress(false), resourceInventoriedAs: resourceId, toResourceInventoriedAs: resourceId, resourceQuantity: qty(1) },
+    }))
+    t.notEqual(err.indexOf('other than quantities and classifications'), -1, 'other agents cannot reassign accountability of resources they did not create')
+
+    resp = await aliceObservation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(resp.economicResource.accountingQuantity, qty(8), 'only permitted updates applied')
+    t.equal(resp.economicResource.currentLocation, undefined, 'resource location unchanged')
+    t.deepLooseEqual(resp.economicResource.primaryAccountable, testEventProps.receiver, 'resource accountability unchanged')
+  } catch (e) {
+    await scenario.cleanUp()
+    throw e
+  }
+  await scenario.cleanUp()
+})
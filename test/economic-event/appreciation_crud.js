@@ -0,0 +1,94 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('Appreciation record API', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    // SCENARIO: a contribution of work appreciated by a gift
+    const createEvent = async (action, note) => {
+      const resp = await observation.call('economic_event', 'create_economic_event', { event: { action, note, ...testEventProps } })
+      return resp.economicEvent.id
+    }
+    const workId = await createEvent('work', 'volunteer work')
+    const giftId = await createEvent('transfer', 'thank you gift')
+    const otherGiftId = await createEvent('transfer', 'another gift')
+    await pause(100)
+
+    // ACT: create the appreciation
+    const createResp = await observation.call('appreciation', 'create_appreciation', {
+      appreciation: { appreciationOf: workId, appreciationWith: giftId, note: 'appreciating the work' },
+    })
+    await pause(100)
+    const appreciationId = createResp.appreciation.id
+    t.ok(appreciationId, 'record created')
+
+    let readResp = await observation.call('appreciation', 'get_appreciation', { address: appreciationId })
+    t.deepLooseEqual(readResp.appreciation.appreciationOf, workId, 'appreciated event read OK')
+    t.deepLooseEqual(readResp.appreciation.appreciationWith, giftId, 'appreciating event read OK')
+    t.equal(readResp.appreciation.note, 'appreciating the work', 'note read OK')
+
+    // ASSERT: event indexes
+    readResp = await observation.call('economic_event', 'get_economic_event', { address: workId })
+    t.deepLooseEqual(readResp.economicEvent.appreciatedBy, [appreciationId], 'reciprocal appreciated event index OK')
+    readResp = await observation.call('economic_event', 'get_economic_event', { address: giftId })
+    t.deepLooseEqual(readResp.economicEvent.appreciation, [appreciationId], 'reciprocal appreciating event index OK')
+
+    readResp = await observation.call('appreciation_index', 'query_appreciations', { params: { appreciationOf: workId } })
+    t.equal(readResp.edges.length, 1, 'appreciation queryable by appreciated event')
+    t.deepLooseEqual(readResp.edges[0].node.id, appreciationId, 'appreciated event query OK')
+
+    readResp = await observation.call('economic_event_index', 'query_economic_events', { params: { appreciatedBy: appreciationId } })
+    t.equal(readResp.edges.length, 1, 'event queryable by appreciation')
+    t.deepLooseEqual(readResp.edges[0].node.id, workId, 'appreciation event query OK')
+
+    // ACT: appreciate with a different event
+    const updateResp = await observation.call('appreciation', 'update_appreciation', {
+      appreciation: { revisionId: createResp.appreciation.revisionId, appreciationWith: otherGiftId },
+    })
+    await pause(100)
+    t.deepLooseEqual(updateResp.appreciation.appreciationWith, otherGiftId, 'record updated')
+
+    readResp = await observation.call('economic_event', 'get_economic_event', { address: giftId })
+    t.deepLooseEqual(readResp.economicEvent.appreciation || [], [], 'stale event index removed')
+    readResp = await observation.call('economic_event', 'get_economic_event', { address: otherGiftId })
+    t.deepLooseEqual(readResp.economicEvent.appreciation, [appreciationId], 'updated event index created')
+
+    // ACT: delete the appreciation
+    const deleteResp = await observation.call('appreciation', 'delete_appreciation', { revisionId: updateResp.appreciation.revisionId })
+    await pause(100)
+    t.equal(deleteResp, true, 'record deleted')
+
+    readResp = await observation.call('economic_event', 'get_economic_event', { address: workId })
+    t.deepLooseEqual(readResp.economicEvent.appreciatedBy || [], [], 'event index removed on delete')
+
+    let err
+    try {
+      await observation.call('appreciation', 'get_appreciation', { address: appreciationId })
+    } catch (e) {
+      err = e
+    }
+    t.ok(err, 'querying deleted record is an error')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
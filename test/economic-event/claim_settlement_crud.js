@@ -0,0 +1,138 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+} from '../init.js'
+
+test('Claim and Settlement record API', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'agent'])
+  try {
+    const { cells: [observation, agent] } = alice
+    const unitId = mockIdentifier(false)
+    const qty = (hasNumericalValue) => ({ hasNumericalValue, hasDecimalValue: String(hasNumericalValue), hasUnit: unitId })
+
+    // SCENARIO: Bob delivers services to Alice, which triggers a claim for payment settled by a later transfer
+    const aResp = await agent.call('agent', 'create_agent', { agent: { name: 'Alice', agentType: 'Person' } })
+    const bResp = await agent.call('agent', 'create_agent', { agent: { name: 'Bob', agentType: 'Person' } })
+    const aliceId = aResp.agent.id
+    const bobId = bResp.agent.id
+
+    const createEvent = async (action, provider, receiver, quantity) => {
+      const resp = await observation.call('economic_event', 'create_economic_event', {
+        event: {
+          action,
+          provider,
+          receiver,
+          resourceClassifiedAs: ['some-resource-type'],
+          resourceQuantity: qty(quantity),
+          hasPointInTime: new Date().toISOString(),
+        },
+      })
+      return resp.economicEvent.id
+    }
+    const serviceEventId = await createEvent('deliver-service', bobId, aliceId, 1)
+    const paymentEventId = await createEvent('transfer', aliceId, bobId, 100)
+    await pause(100)
+
+    // ACT: create the claim
+    const claimResp = await observation.call('claim', 'create_claim', {
+      claim: {
+        action: 'transfer',
+        provider: aliceId,
+        receiver: bobId,
+        triggeredBy: serviceEventId,
+        resourceQuantity: qty(100),
+        note: 'payment for services',
+      },
+    })
+    await pause(100)
+    const claimId = claimResp.claim.id
+    t.ok(claimId, 'claim created')
+    t.equal(claimResp.claim.finished, false, 'claim is not finished by default')
+
+    let readResp = await observation.call('claim', 'get_claim', { address: claimId })
+    t.deepLooseEqual(readResp.claim.triggeredBy, serviceEventId, 'claim trigger read OK')
+    t.deepLooseEqual(readResp.claim.resourceQuantity, qty(100), 'claim quantity read OK')
+    t.deepLooseEqual(readResp.claim.settledBy || [], [], 'new claim is unsettled')
+
+    // ASSERT: claim indexes
+    readResp = await observation.call('economic_event', 'get_economic_event', { address: serviceEventId })
+    t.deepLooseEqual(readResp.economicEvent.triggers, [claimId], 'reciprocal event trigger index OK')
+
+    readResp = await observation.call('claim_index', 'query_claims', { params: { triggeredBy: serviceEventId } })
+    t.equal(readResp.edges.length, 1, 'claim queryable by triggering event')
+    t.deepLooseEqual(readResp.edges[0].node.id, claimId, 'claim trigger query OK')
+
+    readResp = await observation.call('claim_index', 'query_claims', { params: { provider: aliceId } })
+    t.equal(readResp.edges.length, 1, 'claim queryable by provider')
+
+    readResp = await agent.call('agent', 'get_agent', { address: aliceId })
+    t.deepLooseEqual(readResp.agent.claimsAsProvider, [claimId], 'reciprocal agent provider index OK')
+    readResp = await agent.call('agent', 'get_agent', { address: bobId })
+    t.deepLooseEqual(readResp.agent.claimsAsReceiver, [claimId], 'reciprocal agent receiver index OK')
+
+    // ACT: settle the claim
+    const settlementResp = await observation.call('settlement', 'create_settlement', {
+      settlement: { settledBy: paymentEventId, settles: claimId, resourceQuantity: qty(100) },
+    })
+    await pause(100)
+    const settlementId = settlementResp.settlement.id
+    t.ok(settlementId, 'settlement created')
+
+    readResp = await observation.call('settlement', 'get_settlement', { address: settlementId })
+    t.deepLooseEqual(readResp.settlement.settles, claimId, 'settled claim read OK')
+    t.deepLooseEqual(readResp.settlement.settledBy, paymentEventId, 'settling event read OK')
+
+    // ASSERT: settlement indexes
+    readResp = await observation.call('claim', 'get_claim', { address: claimId })
+    t.deepLooseEqual(readResp.claim.settledBy, [settlementId], 'reciprocal claim settlement index OK')
+
+    readResp = await observation.call('economic_event', 'get_economic_event', { address: paymentEventId })
+    t.deepLooseEqual(readResp.economicEvent.settles, [settlementId], 'reciprocal event settlement index OK')
+
+    readResp = await observation.call('settlement_index', 'query_settlements', { params: { settles: claimId } })
+    t.equal(readResp.edges.length, 1, 'settlement queryable by claim')
+    t.deepLooseEqual(readResp.edges[0].node.id, settlementId, 'settlement claim query OK')
+
+    // ACT: mark the claim finished
+    const updateResp = await observation.call('claim', 'update_claim', {
+      claim: { revisionId: claimResp.claim.revisionId, finished: true },
+    })
+    await pause(100)
+    t.equal(updateResp.claim.finished, true, 'claim updated')
+    t.deepLooseEqual(updateResp.claim.settledBy, [settlementId], 'settlement index retained on update')
+
+    // ACT: delete the settlement, then the claim
+    let deleteResp = await observation.call('settlement', 'delete_settlement', { revisionId: settlementResp.settlement.revisionId })
+    await pause(100)
+    t.equal(deleteResp, true, 'settlement deleted')
+
+    readResp = await observation.call('claim', 'get_claim', { address: claimId })
+    t.deepLooseEqual(readResp.claim.settledBy || [], [], 'claim settlement index removed on delete')
+    readResp = await observation.call('economic_event', 'get_economic_event', { address: paymentEventId })
+    t.deepLooseEqual(readResp.economicEvent.settles || [], [], 'event settlement index removed on delete')
+
+    deleteResp = await observation.call('claim', 'delete_claim', { revisionId: updateResp.claim.revisionId })
+    await pause(100)
+    t.equal(deleteResp, true, 'claim deleted')
+
+    readResp = await observation.call('claim_index', 'query_claims', { params: { triggeredBy: serviceEventId } })
+    t.equal(readResp.edges.length, 0, 'claim trigger index removed on delete')
+
+    let err
+    try {
+      await observation.call('claim', 'get_claim', { address: claimId })
+    } catch (e) {
+      err = e
+    }
+    t.ok(err, 'querying deleted record is an error')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,165 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  buildPlayers,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('EconomicEvent corrections', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+    const qty = (hasNumericalValue) => ({ hasNumericalValue, hasDecimalValue: String(hasNumericalValue), hasUnit: resourceUnitId })
+
+    // SCENARIO: raise a resource and consume some of it
+    const rResp = await observation.call('economic_event', 'create_economic_event', {
+      event: { action: 'raise', resourceQuantity: qty(10), resourceClassifiedAs: ['http://www.productontology.org/doc/Apple.ttl'], ...testEventProps },
+      newInventoriedResource: { name: 'apples' },
+    })
+    await pause(100)
+    const resourceId = rResp.economicResource.id
+
+    const cResp = await observation.call('economic_event', 'create_economic_event', {
+      event: { action: 'consume', resourceInventoriedAs: resourceId, resourceQuantity: qty(3), ...testEventProps },
+    })
+    await pause(100)
+    const consumeEvent = cResp.economicEvent
+    t.ok(consumeEvent && consumeEvent.id, 'event to be corrected created')
+
+    let readResp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(readResp.economicResource.accountingQuantity, qty(7), 'original event applied to resource')
+
+    // ASSERT: events affecting inventories cannot be deleted
+    let err
+    try {
+      await observation.call('economic_event', 'delete_economic_event', { revisionId: consumeEvent.revisionId })
+    } catch (e) {
+      err = e
+    }
+    t.notEqual(err.data.data.indexOf('must be corrected rather than deleted'), -1, 'inventory-affecting event cannot be deleted')
+
+    // ACT: correct the consumed quantity
+    const correctionResp = await observation.call('economic_event', 'correct_economic_event', {
+      event: consumeEvent.id,
+      replacement: { action: 'consume', resourceInventoriedAs: resourceId, resourceQuantity: qty(2), ...testEventProps },
+    })
+    await pause(100)
+    const { reversal, replacement } = correctionResp
+    t.deepLooseEqual(reversal.economicEvent.corrects, consumeEvent.id, 'reversal corrects the original event')
+    t.deepLooseEqual(reversal.economicEvent.resourceQuantity, qty(-3), 'reversal negates the original quantity')
+    t.notEqual(reversal.economicEvent.hasPointInTime, consumeEvent.hasPointInTime, 'reversal is observed at the time of correction')
+    t.ok(new Date(reversal.economicEvent.hasPointInTime) > new Date(consumeEvent.hasPointInTime), 'reversal is observed after the original event')
+    t.deepLooseEqual(replacement.economicEvent.corrects, consumeEvent.id, 'replacement corrects the original event')
+    t.deepLooseEqual(replacement.economicEvent.resourceQuantity, qty(2), 'replacement records the corrected quantity')
+
+    // ASSERT: resource reflects the corrected observation only
+    readResp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(readResp.economicResource.accountingQuantity, qty(8), 'accounting quantity reflects corrected event')
+    t.deepLooseEqual(readResp.economicResource.onhandQuantity, qty(8), 'onhand quantity reflects corrected event')
+
+    readResp = await observation.call('economic_event', 'get_economic_event', { address: consumeEvent.id })
+    t.equal(readResp.economicEvent.resourceQuantity.hasNumericalValue, 3, 'original event is left unaltered')
+
+    // ASSERT: events can only be corrected once
+    err = undefined
+    try {
+      await observation.call('economic_event', 'correct_economic_event', {
+        event: consumeEvent.id,
+        replacement: { action: 'consume', resourceInventoriedAs: resourceId, resourceQuantity: qty(1), ...testEventProps },
+      })
+    } catch (e) {
+      err = e
+    }
+    t.notEqual(err.data.data.indexOf('already been corrected'), -1, 'second correction of the same event is rejected')
+
+    readResp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(readResp.economicResource.accountingQuantity, qty(8), 'rejected correction has no effect on resource')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('EconomicEvent correction permissions', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const { players: [alice, bob], scenario } = await buildPlayers(2, ['observation'])
+  try {
+    const { cells: [aliceObservation] } = alice
+    const { cells: [bobObservation] } = bob
+    const resourceUnitId = mockIdentifier(false)
+    const qty = (hasNumericalValue) => ({ hasNumericalValue, hasDecimalValue: String(hasNumericalValue), hasUnit: resourceUnitId })
+    const resourceClassifiedAs = ['http://www.productontology.org/doc/Apple.ttl']
+
+    // SCENARIO: Alice raises two resources and moves some of one into the other
+    let resp = await aliceObservation.call('economic_event', 'create_economic_event', {
+      event: { action: 'raise', resourceQuantity: qty(10), resourceClassifiedAs, ...testEventProps },
+      newInventoriedResource: { name: 'apples' },
+    })
+    const resourceId = resp.economicResource.id
+    const raiseEvent = resp.economicEvent
+    resp = await aliceObservation.call('economic_event', 'create_economic_event', {
+      event: { action: 'raise', resourceQuantity: qty(0), resourceClassifiedAs, ...testEventProps },
+      newInventoriedResource: { name: 'stored apples' },
+    })
+    const destResourceId = resp.economicResource.id
+    resp = await aliceObservation.call('economic_event', 'create_economic_event', {
+      event: { action: 'move', resourceInventoriedAs: resourceId, toResourceInventoriedAs: destResourceId, resourceQuantity: qty(2), ...testEventProps },
+    })
+    const moveEvent = resp.economicEvent
+    await pause(1000)
+
+    // ASSERT: other agents cannot correct the event
+    let err
+    try {
+      await bobObservation.call('economic_event', 'correct_economic_event', {
+        event: raiseEvent.id,
+        replacement: { action: 'raise', resourceInventoriedAs: resourceId, resourceQuantity: qty(1), ...testEventProps },
+      })
+    } catch (e) {
+      err = e
+    }
+    t.notEqual(err.data.data.indexOf('may only be corrected by its original author'), -1, 'correction by another agent is rejected')
+
+    // ASSERT: corrections by other agents are rejected by validation
+    err = undefined
+    try {
+      await bobObservation.call('economic_event', 'create_economic_event', {
+        event: { action: 'raise', corrects: raiseEvent.id, resourceClassifiedAs, resourceQuantity: qty(1), ...testEventProps },
+      })
+    } catch (e) {
+      err = e
+    }
+    t.notEqual(err.data.data.indexOf('may only be corrected by their original author'), -1, 'correcting event authored by another agent is invalid')
+
+    // ASSERT: move events cannot be corrected
+    err = undefined
+    try {
+      await aliceObservation.call('economic_event', 'correct_economic_event', { event: moveEvent.id })
+    } catch (e) {
+      err = e
+    }
+    t.notEqual(err.data.data.indexOf("'move' events which have altered the location or accountability of inventoried resources cannot be corrected"), -1, 'move event correction is rejected')
+
+    resp = await aliceObservation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(resp.economicResource.accountingQuantity, qty(8), 'rejected corrections have no effect on resource')
+  } catch (e) {
+    await scenario.cleanUp()
+    throw e
+  }
+  await scenario.cleanUp()
+})
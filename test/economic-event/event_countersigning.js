@@ -0,0 +1,118 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayers,
+  mockIdentifier,
+} from '../init.js'
+
+test('Countersigned EconomicEvents', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const { players: [alice, bob], scenario } = await buildPlayers(2, ['observation', 'agent'])
+  try {
+    const { cells: [aliceObservation, aliceAgent] } = alice
+    const { cells: [bobObservation, bobAgent] } = bob
+    const resourceUnitId = mockIdentifier(false)
+
+    // SCENARIO: Alice and Bob are each associated with an Agent, and Alice holds a resource
+    const aResp = await aliceAgent.call('agent', 'create_agent', { agent: { name: 'Alice', agentType: 'Person' } })
+    const bResp = await bobAgent.call('agent', 'create_agent', { agent: { name: 'Bob', agentType: 'Person' } })
+    const aliceId = aResp.agent.id
+    const bobId = bResp.agent.id
+    await aliceAgent.call('agent', 'associate_my_agent', { agentAddress: aliceId })
+    await bobAgent.call('agent', 'associate_my_agent', { agentAddress: bobId })
+
+    const rResp = await aliceObservation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'raise',
+        provider: aliceId,
+        receiver: aliceId,
+        resourceClassifiedAs: ['some-resource-type'],
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        hasPointInTime: new Date(),
+      },
+      newInventoriedResource: { name: 'shared resource' },
+    })
+    const resourceId = rResp.economicResource.id
+    await pause(1000)
+
+    // ACT: Alice proposes transferring the resource to Bob, and Bob accepts
+    const proposal = await aliceObservation.call('economic_event', 'propose_countersigned_economic_event', {
+      event: {
+        action: 'transfer',
+        provider: aliceId,
+        receiver: bobId,
+        resourceInventoriedAs: resourceId,
+        toResourceInventoriedAs: resourceId,
+        resourceClassifiedAs: ['some-resource-type'],
+        resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+        hasPointInTime: new Date(),
+      },
+      counterparty: bob.player.agentPubKey,
+    })
+    t.ok(proposal.request && proposal.response, 'countersigning session proposed')
+
+    const bobResponse = await bobObservation.call('economic_event', 'accept_countersigned_economic_event', { request: proposal.request })
+    t.ok(bobResponse, 'countersigning session accepted by counterparty')
+
+    // ACT: both parties commit the event within the session
+    const responses = [proposal.response, bobResponse]
+    const [aliceCommit, bobCommit] = await Promise.all([
+      aliceObservation.call('economic_event', 'commit_countersigned_economic_event', { responses }),
+      bobObservation.call('economic_event', 'commit_countersigned_economic_event', { responses }),
+    ])
+    await pause(1000)
+    t.deepLooseEqual(aliceCommit.economicEvent.id, bobCommit.economicEvent.id, 'both parties committed the same event')
+    t.equal(aliceCommit.economicEvent.countersigned, true, 'committed event is flagged as countersigned')
+
+    // ASSERT: committed events have no effect on inventories until applied
+    let resourceResp = await aliceObservation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(resourceResp.economicResource.primaryAccountable, aliceId, 'unapplied event does not transfer accountability')
+
+    // ASSERT: only the proposer can apply the event
+    let err
+    try {
+      await bobObservation.call('economic_event', 'apply_countersigned_economic_event', { revisionId: bobCommit.economicEvent.revisionId })
+    } catch (e) {
+      err = e
+    }
+    t.notEqual(err.data.data.indexOf('can only be applied by their proposing agent'), -1, 'counterparty cannot apply the event')
+
+    // ACT: the proposer applies the event
+    const applyResp = await aliceObservation.call('economic_event', 'apply_countersigned_economic_event', { revisionId: aliceCommit.economicEvent.revisionId })
+    await pause(100)
+    t.equal(applyResp.economicEvent.countersigned, true, 'applied event is flagged as countersigned')
+
+    resourceResp = await aliceObservation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(resourceResp.economicResource.primaryAccountable, bobId, 'applied event transfers accountability')
+
+    const eventResp = await aliceObservation.call('economic_event', 'get_economic_event', { address: aliceCommit.economicEvent.id })
+    t.equal(eventResp.economicEvent.countersigned, true, 'event read from the DHT is flagged as countersigned')
+
+    // ASSERT: events cannot be applied more than once
+    err = undefined
+    try {
+      await aliceObservation.call('economic_event', 'apply_countersigned_economic_event', { revisionId: aliceCommit.economicEvent.revisionId })
+    } catch (e) {
+      err = e
+    }
+    t.notEqual(err.data.data.indexOf('already been applied'), -1, 'reapplying the event is rejected')
+
+    // ASSERT: revisions of countersigned events retain the flag
+    const updateResp = await aliceObservation.call('economic_event', 'update_economic_event', {
+      event: { revisionId: eventResp.economicEvent.revisionId, note: 'transferred to Bob' },
+    })
+    await pause(100)
+    t.equal(updateResp.economicEvent.countersigned, true, 'updated event remains flagged as countersigned')
+
+    // ASSERT: events which were not countersigned are flagged as such
+    t.equal(rResp.economicEvent.countersigned, false, 'regular events are not flagged as countersigned')
+    const regularResp = await aliceObservation.call('economic_event', 'get_economic_event', { address: rResp.economicEvent.id })
+    t.equal(regularResp.economicEvent.countersigned, false, 'regular events read from the DHT are not flagged as countersigned')
+  } catch (e) {
+    await scenario.cleanUp()
+    throw e
+  }
+  await scenario.cleanUp()
+})
@@ -0,0 +1,163 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  serializeId,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  provider: mockAddress(),
+  receiver: mockAddress(),
+  resourceClassifiedAs: ['some-type-of-resource'],
+  resourceQuantity: { hasNumericalValue: 1.0, hasUnit: mockIdentifier() },
+}
+
+const pageQuery = `query($first: Int, $after: String, $last: Int, $before: String) {
+  economicEvents(first: $first, after: $after, last: $last, before: $before) {
+    pageInfo {
+      startCursor
+      endCursor
+      hasPreviousPage
+      hasNextPage
+    }
+    edges {
+      cursor
+      node {
+        id
+      }
+    }
+  }
+}`
+
+test('EconomicEvent list pagination', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    // events are created one at a time so that their indexed times are distinct
+    const eventIds = []
+    for (let i = 0; i < 5; i++) {
+      const resp = await alice.graphQL(`
+        mutation($e: EconomicEventCreateParams!) {
+          createEconomicEvent(event: $e) {
+            economicEvent {
+              id
+            }
+          }
+        }
+      `, {
+        e: {
+          note: `event ${i}`,
+          hasPointInTime: new Date(),
+          ...testEventProps,
+        },
+      })
+      t.ok(resp.data.createEconomicEvent.economicEvent.id, `event ${i} created`)
+      eventIds.push(resp.data.createEconomicEvent.economicEvent.id)
+      await pause(100)
+    }
+    // list APIs return results from most recent to oldest
+    const [e5, e4, e3, e2, e1] = eventIds.slice().reverse()
+    const ids = (resp) => resp.data.economicEvents.edges.map(e => e.node.id)
+
+    // backwards pagination from the most recent event
+    let resp = await alice.graphQL(pageQuery, { last: 2 })
+    t.deepEqual(ids(resp), [e5, e4], 'first backwards page contains most recent events')
+    t.equal(resp.data.economicEvents.pageInfo.hasPreviousPage, false, 'first backwards page has no newer results')
+    t.equal(resp.data.economicEvents.pageInfo.hasNextPage, true, 'first backwards page has older results')
+
+    resp = await alice.graphQL(pageQuery, { last: 2, before: resp.data.economicEvents.pageInfo.endCursor })
+    t.deepEqual(ids(resp), [e3, e2], 'second backwards page continues across page boundary')
+    t.equal(resp.data.economicEvents.pageInfo.hasPreviousPage, true, 'second backwards page has newer results')
+    t.equal(resp.data.economicEvents.pageInfo.hasNextPage, true, 'second backwards page has older results')
+
+    resp = await alice.graphQL(pageQuery, { last: 2, before: resp.data.economicEvents.pageInfo.endCursor })
+    t.deepEqual(ids(resp), [e1], 'final backwards page contains remaining oldest event')
+    t.equal(resp.data.economicEvents.pageInfo.hasPreviousPage, true, 'final backwards page has newer results')
+    t.equal(resp.data.economicEvents.pageInfo.hasNextPage, false, 'final backwards page has no older results')
+
+    // forwards pagination from the oldest event
+    resp = await alice.graphQL(pageQuery, { first: 2 })
+    t.deepEqual(ids(resp), [e2, e1], 'first forwards page contains oldest events')
+    t.equal(resp.data.economicEvents.pageInfo.hasPreviousPage, true, 'first forwards page has newer results')
+    t.equal(resp.data.economicEvents.pageInfo.hasNextPage, false, 'first forwards page has no older results')
+
+    resp = await alice.graphQL(pageQuery, { first: 2, after: resp.data.economicEvents.pageInfo.startCursor })
+    t.deepEqual(ids(resp), [e4, e3], 'second forwards page continues across page boundary')
+    t.equal(resp.data.economicEvents.pageInfo.hasPreviousPage, true, 'second forwards page has newer results')
+    t.equal(resp.data.economicEvents.pageInfo.hasNextPage, true, 'second forwards page has older results')
+
+    resp = await alice.graphQL(pageQuery, { first: 2, after: resp.data.economicEvents.pageInfo.startCursor })
+    t.deepEqual(ids(resp), [e5], 'final forwards page contains remaining most recent event')
+    t.equal(resp.data.economicEvents.pageInfo.hasPreviousPage, false, 'final forwards page has no newer results')
+    t.equal(resp.data.economicEvents.pageInfo.hasNextPage, true, 'final forwards page has older results')
+
+    // page exactly filling the index
+    resp = await alice.graphQL(pageQuery, { last: 5 })
+    t.deepEqual(ids(resp), [e5, e4, e3, e2, e1], 'page of index size returns all events without duplicates')
+    t.equal(resp.data.economicEvents.pageInfo.hasNextPage, false, 'page of index size has no older results')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('EconomicEvent list pagination with re-indexed events', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const eventIds = []
+    for (let i = 0; i < 5; i++) {
+      const resp = await observation.call('economic_event', 'create_economic_event', {
+        event: {
+          action: 'raise',
+          note: `event ${i}`,
+          provider: mockAddress(false),
+          receiver: mockAddress(false),
+          resourceClassifiedAs: ['some-type-of-resource'],
+          resourceQuantity: { hasNumericalValue: 1.0, hasUnit: mockIdentifier(false) },
+          hasPointInTime: new Date().toISOString(),
+        },
+      })
+      eventIds.push(resp.economicEvent.id)
+      await pause(100)
+    }
+    const [e1, e2, e3, e4, e5] = eventIds.map(serializeId)
+
+    // SCENARIO: the second oldest event is indexed again at a later time than all others
+    await observation.call('economic_event_index', 'record_new_economic_event', { address: eventIds[1], timestamp: new Date().toISOString() })
+    await pause(100)
+
+    const ids = (resp) => resp.data.economicEvents.edges.map(e => e.node.id)
+
+    // ASSERT: re-indexed entries retain their original position when paging backwards
+    let resp = await alice.graphQL(pageQuery, { last: 2 })
+    t.deepEqual(ids(resp), [e5, e4], 'first backwards page ignores later index of re-indexed event')
+
+    resp = await alice.graphQL(pageQuery, { last: 2, before: resp.data.economicEvents.pageInfo.endCursor })
+    t.deepEqual(ids(resp), [e3, e2], 'second backwards page returns re-indexed event at its original position')
+
+    resp = await alice.graphQL(pageQuery, { last: 2, before: resp.data.economicEvents.pageInfo.endCursor })
+    t.deepEqual(ids(resp), [e1], 'cursor of re-indexed event does not skip older events')
+    t.equal(resp.data.economicEvents.pageInfo.hasNextPage, false, 'final backwards page has no older results')
+
+    // ASSERT: forwards paging uses the same position
+    resp = await alice.graphQL(pageQuery, { first: 3 })
+    t.deepEqual(ids(resp), [e3, e2, e1], 'forwards page returns re-indexed event at its original position')
+
+    resp = await alice.graphQL(pageQuery, { first: 3, after: resp.data.economicEvents.pageInfo.startCursor })
+    t.deepEqual(ids(resp), [e5, e4], 're-indexed event is not repeated on subsequent pages')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
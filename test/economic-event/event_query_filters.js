@@ -0,0 +1,106 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+  resourceClassifiedAs: ['http://www.productontology.org/doc/Apple.ttl'],
+}
+
+test('EconomicEvent query filter intersection and paging', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+
+    // SCENARIO: two processes consuming from two resources
+    const pResp1 = await observation.call('process', 'create_process', { process: { name: 'process A' } })
+    const pResp2 = await observation.call('process', 'create_process', { process: { name: 'process B' } })
+    await pause(100)
+    const processId1 = pResp1.process.id
+    const processId2 = pResp2.process.id
+
+    const raiseEvent = {
+      action: 'raise',
+      resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+      ...testEventProps,
+    }
+    const rResp1 = await observation.call('economic_event', 'create_economic_event', { event: raiseEvent, newInventoriedResource: { note: 'resource A' } })
+    const rResp2 = await observation.call('economic_event', 'create_economic_event', { event: raiseEvent, newInventoriedResource: { note: 'resource B' } })
+    await pause(100)
+    const resourceId1 = rResp1.economicResource.id
+    const resourceId2 = rResp2.economicResource.id
+
+    const consume = async (note, processId, resourceId) => {
+      const resp = await observation.call('economic_event', 'create_economic_event', {
+        event: {
+          note,
+          action: 'consume',
+          inputOf: processId,
+          resourceInventoriedAs: resourceId,
+          resourceQuantity: { hasNumericalValue: 1, hasUnit: resourceUnitId },
+          ...testEventProps,
+        },
+      })
+      await pause(100)
+      t.ok(resp.economicEvent && resp.economicEvent.id, `${note} created`)
+      return resp.economicEvent.id
+    }
+    const matchingId1 = await consume('process A consuming resource A (1)', processId1, resourceId1)
+    await consume('process A consuming resource B', processId1, resourceId2)
+    const matchingId2 = await consume('process A consuming resource A (2)', processId1, resourceId1)
+    await consume('process B consuming resource A', processId2, resourceId1)
+    const matchingId3 = await consume('process A consuming resource A (3)', processId1, resourceId1)
+
+    const ids = (resp) => resp.edges.map(e => e.node.id)
+
+    // ASSERT: single filters match every linked event
+    let resp = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId1 } })
+    t.equal(resp.edges.length, 4, 'single inputOf filter matches all inputs of the process')
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { affects: resourceId1 } })
+    t.equal(resp.edges.length, 5, 'single affects filter matches all events affecting the resource')
+
+    // ASSERT: combined filters only match events linked from both
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId1, affects: resourceId1 } })
+    t.deepLooseEqual(ids(resp), [matchingId3, matchingId2, matchingId1], 'intersecting filters match events linked from both, most recent first')
+    t.equal(resp.pageInfo.hasPreviousPage, false, 'full result set has no newer results')
+    t.equal(resp.pageInfo.hasNextPage, false, 'full result set has no older results')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId2, affects: resourceId2 } })
+    t.equal(resp.edges.length, 0, 'disjoint filters match nothing')
+
+    // ASSERT: cursor paging backwards through intersecting results
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId1, affects: resourceId1 }, last: 2 })
+    t.deepLooseEqual(ids(resp), [matchingId3, matchingId2], 'first backwards page OK')
+    t.equal(resp.pageInfo.hasNextPage, true, 'first backwards page has older results')
+    t.equal(resp.pageInfo.hasPreviousPage, false, 'first backwards page has no newer results')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId1, affects: resourceId1 }, last: 2, before: resp.pageInfo.endCursor })
+    t.deepLooseEqual(ids(resp), [matchingId1], 'second backwards page continues from cursor')
+    t.equal(resp.pageInfo.hasNextPage, false, 'second backwards page has no older results')
+    t.equal(resp.pageInfo.hasPreviousPage, true, 'second backwards page has newer results')
+
+    // ASSERT: cursor paging forwards through intersecting results
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId1, affects: resourceId1 }, first: 1 })
+    t.deepLooseEqual(ids(resp), [matchingId1], 'first forwards page starts from oldest match')
+    t.equal(resp.pageInfo.hasPreviousPage, true, 'first forwards page has newer results')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId1, affects: resourceId1 }, first: 2, after: resp.pageInfo.startCursor })
+    t.deepLooseEqual(ids(resp), [matchingId3, matchingId2], 'second forwards page continues from cursor')
+    t.equal(resp.pageInfo.hasPreviousPage, false, 'second forwards page has no newer results')
+    t.equal(resp.pageInfo.hasNextPage, true, 'second forwards page has older results')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
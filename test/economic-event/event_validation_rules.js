@@ -0,0 +1,99 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+  resourceClassifiedAs: ['http://www.productontology.org/doc/Apple.ttl'],
+}
+
+test('EconomicEvent action rule validation', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+    const qty = (hasNumericalValue) => ({ hasNumericalValue, hasDecimalValue: String(hasNumericalValue), hasUnit: resourceUnitId })
+    const createEvent = (event, newInventoriedResource) => observation.call('economic_event', 'create_economic_event', { event: { ...testEventProps, ...event }, newInventoriedResource })
+    const rejection = async (call) => {
+      try {
+        await call()
+      } catch (e) {
+        return e.data.data
+      }
+      return ''
+    }
+
+    // SCENARIO: an inventoried resource to act upon
+    let resp = await createEvent({ action: 'raise', resourceQuantity: qty(10) }, { name: 'apples' })
+    await pause(100)
+    const resourceId = resp.economicResource.id
+
+    // ASSERT: transfer & move events must pair their inventories
+    let err = await rejection(() => createEvent({ action: 'transfer', resourceInventoriedAs: resourceId, resourceQuantity: qty(1) }))
+    t.notEqual(err.indexOf('requires both source and destination inventory fields'), -1, 'unpaired transfer rejected')
+    err = await rejection(() => createEvent({ action: 'move', toResourceInventoriedAs: resourceId, resourceQuantity: qty(1) }))
+    t.notEqual(err.indexOf('must omit inventory fields'), -1, 'move with only a destination inventory rejected')
+
+    // ASSERT: process and quantity requirements of the action
+    err = await rejection(() => createEvent({ action: 'consume', resourceInventoriedAs: resourceId, resourceQuantity: qty(1) }))
+    t.notEqual(err.indexOf('input process required'), -1, 'input event without a process rejected')
+    err = await rejection(() => createEvent({ action: 'lower', resourceInventoriedAs: resourceId, effortQuantity: qty(1) }))
+    t.notEqual(err.indexOf('requires a resource quantity'), -1, 'inventory effect without a resource quantity rejected')
+    err = await rejection(() => createEvent({ action: 'not-an-action', resourceQuantity: qty(1) }))
+    t.notEqual(err.indexOf('Unknown action'), -1, 'unknown action rejected')
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(resp.economicResource.accountingQuantity, qty(10), 'rejected events had no effect on the resource')
+
+    // ASSERT: flow fields cannot be altered by updates
+    resp = await createEvent({ action: 'lower', resourceInventoriedAs: resourceId, resourceQuantity: qty(2) })
+    await pause(100)
+    const lowerEvent = resp.economicEvent
+    resp = await observation.call('economic_event', 'update_economic_event', {
+      event: { revisionId: lowerEvent.revisionId, note: 'revised', action: 'raise', resourceQuantity: qty(5), provider: mockAddress(false) },
+    })
+    await pause(100)
+    t.equal(resp.economicEvent.note, 'revised', 'descriptive fields updated')
+    t.equal(resp.economicEvent.action, 'lower', 'action unchanged by update')
+    t.deepLooseEqual(resp.economicEvent.resourceQuantity, qty(2), 'quantity unchanged by update')
+    t.deepLooseEqual(resp.economicEvent.provider, testEventProps.provider, 'provider unchanged by update')
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(resp.economicResource.accountingQuantity, qty(8), 'resource unaffected by update')
+
+    // ASSERT: delete rules
+    err = await rejection(() => observation.call('economic_event', 'delete_economic_event', { revisionId: lowerEvent.revisionId }))
+    t.notEqual(err.indexOf('must be corrected rather than deleted'), -1, 'inventory-affecting event cannot be deleted')
+
+    resp = await createEvent({ action: 'transfer', resourceQuantity: qty(3) })
+    await pause(100)
+    const transferEvent = resp.economicEvent
+    resp = await observation.call('economic_event', 'correct_economic_event', {
+      event: transferEvent.id,
+      replacement: { ...testEventProps, action: 'transfer', resourceQuantity: qty(4) },
+    })
+    await pause(100)
+    const { reversal, replacement } = resp
+    err = await rejection(() => observation.call('economic_event', 'delete_economic_event', { revisionId: reversal.economicEvent.revisionId }))
+    t.notEqual(err.indexOf('part of a correction'), -1, 'reversal cannot be deleted')
+    err = await rejection(() => observation.call('economic_event', 'delete_economic_event', { revisionId: replacement.economicEvent.revisionId }))
+    t.notEqual(err.indexOf('part of a correction'), -1, 'replacement cannot be deleted')
+
+    resp = await createEvent({ action: 'transfer', resourceQuantity: qty(1) })
+    await pause(100)
+    resp = await observation.call('economic_event', 'delete_economic_event', { revisionId: resp.economicEvent.revisionId })
+    t.ok(resp, 'non-inventoried, uncorrected event can be deleted')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
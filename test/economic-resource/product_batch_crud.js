@@ -0,0 +1,100 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const exampleEntry = {
+  batchNumber: 'BATCH-001',
+  productionDate: '2019-11-19T12:12:42.739+01:00',
+  expiryDate: '2020-11-19T12:12:42.739+01:00',
+}
+
+const testEventProps = {
+  action: 'raise',
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T12:12:42.739+01:00',
+  resourceClassifiedAs: ['resource-type-uri'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+}
+
+test('ProductBatch record API', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    const createResp = await observation.call('product_batch', 'create_product_batch', { productBatch: exampleEntry })
+    await pause(100)
+    const batchId = createResp.productBatch.id
+    t.ok(batchId, 'record created')
+
+    let readResp = await observation.call('product_batch', 'get_product_batch', { address: batchId })
+    t.equal(readResp.productBatch.batchNumber, exampleEntry.batchNumber, 'batch number read OK')
+    t.equal(new Date(readResp.productBatch.productionDate).getTime(), new Date(exampleEntry.productionDate).getTime(), 'production date read OK')
+    t.equal(new Date(readResp.productBatch.expiryDate).getTime(), new Date(exampleEntry.expiryDate).getTime(), 'expiry date read OK')
+    t.deepLooseEqual(readResp.productBatch.economicResources || [], [], 'no resources in new batch')
+
+    // ACT: create two resources in the batch, and one outside of it
+    const r1Resp = await observation.call('economic_event', 'create_economic_event', {
+      event: testEventProps,
+      newInventoriedResource: { name: 'batched resource 1', lot: batchId },
+    })
+    const r2Resp = await observation.call('economic_event', 'create_economic_event', {
+      event: testEventProps,
+      newInventoriedResource: { name: 'batched resource 2', lot: batchId },
+    })
+    await observation.call('economic_event', 'create_economic_event', {
+      event: testEventProps,
+      newInventoriedResource: { name: 'unbatched resource' },
+    })
+    await pause(100)
+    const r1Id = r1Resp.economicResource.id
+    const r2Id = r2Resp.economicResource.id
+    t.deepLooseEqual(r1Resp.economicResource.lot, batchId, 'resource lot reference OK')
+
+    // ASSERT: resources indexed by lot
+    readResp = await observation.call('economic_resource_index', 'query_economic_resources', { params: { lot: batchId } })
+    t.equal(readResp.edges.length, 2, 'only batched resources queryable by lot')
+    t.ok(readResp.edges.some(e => JSON.stringify(e.node.id) === JSON.stringify(r1Id)), 'first resource in lot query')
+    t.ok(readResp.edges.some(e => JSON.stringify(e.node.id) === JSON.stringify(r2Id)), 'second resource in lot query')
+
+    readResp = await observation.call('product_batch', 'get_product_batch', { address: batchId })
+    t.equal(readResp.productBatch.economicResources.length, 2, 'reciprocal resource index OK')
+
+    readResp = await observation.call('product_batch_index', 'query_product_batches', { params: { economicResources: r1Id } })
+    t.equal(readResp.edges.length, 1, 'batch queryable by resource')
+    t.deepLooseEqual(readResp.edges[0].node.id, batchId, 'batch resource query OK')
+
+    // ACT: update the batch
+    const updateResp = await observation.call('product_batch', 'update_product_batch', {
+      productBatch: { revisionId: createResp.productBatch.revisionId, batchNumber: 'BATCH-002' },
+    })
+    await pause(100)
+    t.deepLooseEqual(updateResp.productBatch.id, batchId, 'record identity retained on update')
+    t.equal(updateResp.productBatch.batchNumber, 'BATCH-002', 'record updated')
+    t.equal(updateResp.productBatch.economicResources.length, 2, 'resource index retained on update')
+
+    // ACT: delete the batch
+    const deleteResp = await observation.call('product_batch', 'delete_product_batch', { revisionId: updateResp.productBatch.revisionId })
+    await pause(100)
+    t.equal(deleteResp, true, 'record deleted')
+
+    let err
+    try {
+      await observation.call('product_batch', 'get_product_batch', { address: batchId })
+    } catch (e) {
+      err = e
+    }
+    t.ok(err, 'querying deleted record is an error')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
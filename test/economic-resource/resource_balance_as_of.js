@@ -0,0 +1,90 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress,
+  mockIdentifier,
+  buildPlayer,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  resourceClassifiedAs: ['http://www.productontology.org/doc/Apple.ttl'],
+}
+
+test('EconomicResource point-in-time balances', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+    const qty = (hasNumericalValue) => ({ hasNumericalValue, hasUnit: resourceUnitId })
+    const createEvent = async (event, newInventoriedResource) => {
+      const resp = await observation.call('economic_event', 'create_economic_event', { event: { ...testEventProps, ...event }, newInventoriedResource })
+      await pause(100)
+      return resp
+    }
+    const balanceAt = (address, timestamp) => observation.call('economic_resource', 'economic_resource_as_of', { address, timestamp })
+
+    // SCENARIO: events observed out of chronological order
+    let resp = await createEvent({ action: 'raise', resourceQuantity: qty(10), hasPointInTime: '2019-01-01T00:00:00.000Z' }, { name: 'apples' })
+    const resourceId = resp.economicResource.id
+    resp = await createEvent({ action: 'raise', resourceQuantity: qty(0), hasPointInTime: '2019-01-01T00:00:00.000Z' }, { name: 'stored apples' })
+    const destResourceId = resp.economicResource.id
+    await createEvent({ action: 'lower', resourceInventoriedAs: resourceId, resourceQuantity: qty(2), hasPointInTime: '2019-03-01T00:00:00.000Z' })
+    await createEvent({ action: 'raise', resourceInventoriedAs: resourceId, resourceQuantity: qty(5), hasPointInTime: '2019-02-01T00:00:00.000Z' })
+    const newLocation = mockAddress(false)
+    await createEvent({
+      action: 'move',
+      resourceInventoriedAs: resourceId,
+      toResourceInventoriedAs: destResourceId,
+      atLocation: newLocation,
+      resourceQuantity: qty(1),
+      hasPointInTime: '2019-04-01T00:00:00.000Z',
+    })
+
+    // ASSERT: balances at each point in the resource's history
+    resp = await balanceAt(resourceId, '2018-12-01T00:00:00.000Z')
+    t.equal(resp.eventsReplayed, 0, 'no events replayed before the resource existed')
+    t.equal(resp.accountingQuantity.hasNumericalValue, 0, 'resource is empty before it was created')
+
+    resp = await balanceAt(resourceId, '2019-01-15T00:00:00.000Z')
+    t.deepLooseEqual(resp.economicResource, resourceId, 'balance resource identified')
+    t.equal(new Date(resp.asOf).getTime(), new Date('2019-01-15T00:00:00.000Z').getTime(), 'balance time returned')
+    t.equal(resp.eventsReplayed, 1, 'creating event replayed')
+    t.equal(resp.accountingQuantity.hasNumericalValue, 10, 'accounting quantity after creation OK')
+    t.equal(resp.onhandQuantity.hasNumericalValue, 10, 'onhand quantity after creation OK')
+
+    resp = await balanceAt(resourceId, '2019-02-15T00:00:00.000Z')
+    t.equal(resp.eventsReplayed, 2, 'events ordered by observation time rather than authoring order')
+    t.equal(resp.accountingQuantity.hasNumericalValue, 15, 'earlier event observed later is included')
+
+    resp = await balanceAt(resourceId, '2019-03-01T00:00:00.000Z')
+    t.equal(resp.eventsReplayed, 3, 'events at the requested time are included')
+    t.equal(resp.accountingQuantity.hasNumericalValue, 13, 'accounting quantity at event time OK')
+    t.equal(resp.currentLocation, undefined, 'resource has no location before it was moved')
+
+    resp = await balanceAt(resourceId, '2019-04-15T00:00:00.000Z')
+    t.equal(resp.eventsReplayed, 4, 'all events replayed after the last')
+    t.equal(resp.accountingQuantity.hasNumericalValue, 12, 'accounting quantity after all events OK')
+    t.deepLooseEqual(resp.currentLocation, newLocation, 'location as of the move event OK')
+
+    resp = await balanceAt(destResourceId, '2019-03-15T00:00:00.000Z')
+    t.equal(resp.accountingQuantity.hasNumericalValue, 0, 'receiving resource empty before the move')
+    resp = await balanceAt(destResourceId, '2019-04-15T00:00:00.000Z')
+    t.equal(resp.accountingQuantity.hasNumericalValue, 1, 'receiving resource incremented by the move')
+
+    // ASSERT: the latest balance matches the stored resource
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    const stored = resp.economicResource
+    resp = await balanceAt(resourceId, new Date().toISOString())
+    t.equal(resp.accountingQuantity.hasNumericalValue, stored.accountingQuantity.hasNumericalValue, 'current balance matches stored accounting quantity')
+    t.equal(resp.onhandQuantity.hasNumericalValue, stored.onhandQuantity.hasNumericalValue, 'current balance matches stored onhand quantity')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
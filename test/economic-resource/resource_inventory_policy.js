@@ -0,0 +1,117 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+// mirrors `bundles/dna_templates/observation/dna.yaml`, with negative balances rejected
+const observationProperties = {
+  time_index_chunk_interval_ms: 30000,
+  process: { index_zome: 'process_index', economic_resource_index_zome: 'economic_resource_index' },
+  process_index: { record_storage_zome: 'process' },
+  economic_event: {
+    index_zome: 'economic_event_index',
+    process_index_zome: 'process_index',
+    economic_resource_index_zome: 'economic_resource_index',
+    economic_resource_zome: 'economic_resource',
+  },
+  economic_event_index: { record_storage_zome: 'economic_event' },
+  economic_resource: {
+    index_zome: 'economic_resource_index',
+    product_batch_index_zome: 'product_batch_index',
+    inventory_policy: { reject_negative_balances: true },
+  },
+  economic_resource_index: { record_storage_zome: 'economic_resource' },
+  fulfillment: { index_zome: 'fulfillment_index', economic_event_index_zome: 'economic_event_index' },
+  fulfillment_index: { record_storage_zome: 'fulfillment' },
+  satisfaction: { index_zome: 'satisfaction_index', economic_event_index_zome: 'economic_event_index' },
+  satisfaction_index: { record_storage_zome: 'satisfaction' },
+  claim: { index_zome: 'claim_index', economic_event_index_zome: 'economic_event_index' },
+  claim_index: { record_storage_zome: 'claim' },
+  settlement: { index_zome: 'settlement_index', economic_event_index_zome: 'economic_event_index', claim_index_zome: 'claim_index' },
+  settlement_index: { record_storage_zome: 'settlement' },
+  appreciation: { index_zome: 'appreciation_index', economic_event_index_zome: 'economic_event_index' },
+  appreciation_index: { record_storage_zome: 'appreciation' },
+  product_batch: { index_zome: 'product_batch_index' },
+  product_batch_index: { record_storage_zome: 'product_batch' },
+  remote_auth: { permissions: [] },
+}
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('EconomicResource inventory policy', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'], undefined, { observation: observationProperties })
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+    const qty = (hasNumericalValue) => ({ hasNumericalValue, hasDecimalValue: String(hasNumericalValue), hasUnit: resourceUnitId })
+
+    // SCENARIO: raise a resource in two events and consume most of it
+    const rResp = await observation.call('economic_event', 'create_economic_event', {
+      event: { action: 'raise', resourceQuantity: qty(10), resourceClassifiedAs: ['http://www.productontology.org/doc/Apple.ttl'], ...testEventProps },
+      newInventoriedResource: { name: 'apples' },
+    })
+    await pause(100)
+    const resourceId = rResp.economicResource.id
+
+    const raiseResp = await observation.call('economic_event', 'create_economic_event', {
+      event: { action: 'raise', resourceInventoriedAs: resourceId, resourceQuantity: qty(5), ...testEventProps },
+    })
+    await pause(100)
+    await observation.call('economic_event', 'create_economic_event', {
+      event: { action: 'consume', resourceInventoriedAs: resourceId, resourceQuantity: qty(12), ...testEventProps },
+    })
+    await pause(100)
+
+    // ASSERT: events which would leave a negative balance are rejected
+    let err
+    try {
+      await observation.call('economic_event', 'create_economic_event', {
+        event: { action: 'consume', resourceInventoriedAs: resourceId, resourceQuantity: qty(4), ...testEventProps },
+      })
+    } catch (e) {
+      err = e
+    }
+    t.ok(err && err.data.data.indexOf('below zero') !== -1, 'event reducing balance below zero is rejected')
+
+    // ASSERT: events cannot pass themselves off as reversals to avoid the guards
+    err = undefined
+    try {
+      await observation.call('economic_event', 'create_economic_event', {
+        event: { action: 'raise', corrects: raiseResp.economicEvent.id, reversal: true, resourceInventoriedAs: resourceId, resourceQuantity: qty(-20), ...testEventProps },
+      })
+    } catch (e) {
+      err = e
+    }
+    t.ok(err && err.data.data.indexOf('below zero') !== -1, 'event claiming to be a reversal is subject to negative balance guard')
+
+    // ACT: correct the second raise, whose reversal takes the balance below zero before the replacement is applied
+    const correctionResp = await observation.call('economic_event', 'correct_economic_event', {
+      event: raiseResp.economicEvent.id,
+      replacement: { action: 'raise', resourceInventoriedAs: resourceId, resourceQuantity: qty(2), ...testEventProps },
+    })
+    await pause(100)
+    t.ok(correctionResp.reversal && correctionResp.replacement, 'reversal is not subject to negative balance guard')
+
+    let readResp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(readResp.economicResource.accountingQuantity, qty(0), 'accounting quantity reflects corrected event')
+
+    // ASSERT: replaying event history is not subject to the guards either
+    readResp = await observation.call('economic_resource', 'recalculate_economic_resource', { address: resourceId })
+    t.equal(readResp.eventsReplayed, 5, 'all events are replayed')
+    t.equal(readResp.diverged, false, 'replayed quantities match those stored')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
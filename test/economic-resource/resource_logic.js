@@ -165,8 +165,8 @@ test('EconomicResource & EconomicEvent record interactions', async (t) => {
     readResp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
     readResource = readResp.economicResource
     t.ok(readResource.id, 'resource retrieval OK')
-    t.deepLooseEqual(readResource.accountingQuantity, { hasNumericalValue: 15, hasUnit: resourceUnitId }, 'incrementing events increase the accounting quantity of a resource')
-    t.deepLooseEqual(readResource.onhandQuantity, { hasNumericalValue: 15, hasUnit: resourceUnitId }, 'incrementing events increase the on-hand quantity of a resource')
+    t.deepLooseEqual(readResource.accountingQuantity, { hasNumericalValue: 15, hasDecimalValue: '15', hasUnit: resourceUnitId }, 'incrementing events increase the accounting quantity of a resource')
+    t.deepLooseEqual(readResource.onhandQuantity, { hasNumericalValue: 15, hasDecimalValue: '15', hasUnit: resourceUnitId }, 'incrementing events increase the on-hand quantity of a resource')
 
     newEvent = {
       resourceInventoriedAs: resourceId,
@@ -180,8 +180,8 @@ test('EconomicResource & EconomicEvent record interactions', async (t) => {
 
     readResp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
     readResource = readResp.economicResource
-    t.deepLooseEqual(readResource.accountingQuantity, { hasNumericalValue: 13, hasUnit: resourceUnitId }, 'decrementing events decrease the accounting quantity of a resource')
-    t.deepLooseEqual(readResource.onhandQuantity, { hasNumericalValue: 13, hasUnit: resourceUnitId }, 'decrementing events decrease the on-hand quantity of a resource')
+    t.deepLooseEqual(readResource.accountingQuantity, { hasNumericalValue: 13, hasDecimalValue: '13', hasUnit: resourceUnitId }, 'decrementing events decrease the accounting quantity of a resource')
+    t.deepLooseEqual(readResource.onhandQuantity, { hasNumericalValue: 13, hasDecimalValue: '13', hasUnit: resourceUnitId }, 'decrementing events decrease the on-hand quantity of a resource')
 
     newEvent = {
       resourceInventoriedAs: resourceId,
@@ -195,8 +195,8 @@ test('EconomicResource & EconomicEvent record interactions', async (t) => {
 
     readResp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
     readResource = readResp.economicResource
-    t.deepLooseEqual(readResource.accountingQuantity, { hasNumericalValue: 13, hasUnit: resourceUnitId }, 'transfer-custody does not update accountingQuantity')
-    t.deepLooseEqual(readResource.onhandQuantity, { hasNumericalValue: 12, hasUnit: resourceUnitId }, 'transfer-custody updates onhandQuantity')
+    t.deepLooseEqual(readResource.accountingQuantity, { hasNumericalValue: 13, hasDecimalValue: '13', hasUnit: resourceUnitId }, 'transfer-custody does not update accountingQuantity')
+    t.deepLooseEqual(readResource.onhandQuantity, { hasNumericalValue: 12, hasDecimalValue: '12', hasUnit: resourceUnitId }, 'transfer-custody updates onhandQuantity')
 
     newEvent = {
       resourceInventoriedAs: resourceId,
@@ -210,8 +210,8 @@ test('EconomicResource & EconomicEvent record interactions', async (t) => {
 
     readResp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
     readResource = readResp.economicResource
-    t.deepLooseEqual(readResource.accountingQuantity, { hasNumericalValue: 12, hasUnit: resourceUnitId }, 'transfer-all-rights updates accountingQuantity')
-    t.deepLooseEqual(readResource.onhandQuantity, { hasNumericalValue: 12, hasUnit: resourceUnitId }, 'transfer-all-rights does not update onhandQuantity')
+    t.deepLooseEqual(readResource.accountingQuantity, { hasNumericalValue: 12, hasDecimalValue: '12', hasUnit: resourceUnitId }, 'transfer-all-rights updates accountingQuantity')
+    t.deepLooseEqual(readResource.onhandQuantity, { hasNumericalValue: 12, hasDecimalValue: '12', hasUnit: resourceUnitId }, 'transfer-all-rights does not update onhandQuantity')
 
     // SCENARIO: secondary resource for inventory transfer tests
     const inputEvent2 = {
@@ -281,13 +281,13 @@ test('EconomicResource & EconomicEvent record interactions', async (t) => {
 
     readResp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
     readResource = readResp.economicResource
-    t.deepLooseEqual(readResource.accountingQuantity, { hasNumericalValue: 9, hasUnit: resourceUnitId }, 'transfer events decrease the accounting quantity of the sending resource')
-    t.deepLooseEqual(readResource.onhandQuantity, { hasNumericalValue: 9, hasUnit: resourceUnitId }, 'transfer events decrease the onhand quantity of the sending resource')
+    t.deepLooseEqual(readResource.accountingQuantity, { hasNumericalValue: 9, hasDecimalValue: '9', hasUnit: resourceUnitId }, 'transfer events decrease the accounting quantity of the sending resource')
+    t.deepLooseEqual(readResource.onhandQuantity, { hasNumericalValue: 9, hasDecimalValue: '9', hasUnit: resourceUnitId }, 'transfer events decrease the onhand quantity of the sending resource')
 
     readResp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId2 })
     readResource = readResp.economicResource
-    t.deepLooseEqual(readResource.accountingQuantity, { hasNumericalValue: 3, hasUnit: resourceUnitId }, 'transfer events increase the accounting quantity of the receiving resource')
-    t.deepLooseEqual(readResource.onhandQuantity, { hasNumericalValue: 3, hasUnit: resourceUnitId }, 'transfer events increase the onhand quantity of the receiving resource')
+    t.deepLooseEqual(readResource.accountingQuantity, { hasNumericalValue: 3, hasDecimalValue: '3', hasUnit: resourceUnitId }, 'transfer events increase the accounting quantity of the receiving resource')
+    t.deepLooseEqual(readResource.onhandQuantity, { hasNumericalValue: 3, hasDecimalValue: '3', hasUnit: resourceUnitId }, 'transfer events increase the onhand quantity of the receiving resource')
 
     // SCENARIO: field update tests for event bindings
     newEvent = {
@@ -0,0 +1,81 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress,
+  mockIdentifier,
+  buildPlayer,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+  resourceClassifiedAs: ['http://www.productontology.org/doc/Apple.ttl'],
+}
+
+test('EconomicResource quantity recalculation', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+    const qty = (hasNumericalValue) => ({ hasNumericalValue, hasUnit: resourceUnitId })
+    const createEvent = async (event, newInventoriedResource) => {
+      const resp = await observation.call('economic_event', 'create_economic_event', { event: { ...testEventProps, ...event }, newInventoriedResource })
+      await pause(100)
+      return resp
+    }
+
+    // SCENARIO: a resource affected by incrementing, decrementing and transfer events
+    let resp = await createEvent({ action: 'raise', resourceQuantity: qty(8) }, { name: 'apples' })
+    const resourceId = resp.economicResource.id
+    resp = await createEvent({ action: 'raise', resourceQuantity: qty(0) }, { name: 'more apples' })
+    const destResourceId = resp.economicResource.id
+    await createEvent({ action: 'lower', resourceInventoriedAs: resourceId, resourceQuantity: qty(2) })
+    await createEvent({ action: 'transfer-custody', resourceInventoriedAs: resourceId, resourceQuantity: qty(1) })
+    await createEvent({ action: 'move', resourceInventoriedAs: resourceId, toResourceInventoriedAs: destResourceId, resourceQuantity: qty(1) })
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    const stored = resp.economicResource
+
+    // ACT: recalculate without writing
+    resp = await observation.call('economic_resource', 'recalculate_economic_resource', { address: resourceId })
+    t.deepLooseEqual(resp.economicResource, resourceId, 'recalculated resource identified')
+    t.equal(resp.eventsReplayed, 4, 'all affecting events replayed, including the creating event')
+    t.equal(resp.accountingQuantity.hasNumericalValue, 5, 'accounting quantity derived from event history')
+    t.equal(resp.onhandQuantity.hasNumericalValue, 4, 'onhand quantity derived from event history')
+    t.equal(resp.storedAccountingQuantity.hasNumericalValue, stored.accountingQuantity.hasNumericalValue, 'stored accounting quantity returned')
+    t.equal(resp.storedOnhandQuantity.hasNumericalValue, stored.onhandQuantity.hasNumericalValue, 'stored onhand quantity returned')
+    t.deepLooseEqual(resp.accountingQuantity.hasUnit, resourceUnitId, 'derived quantities retain the resource unit')
+    t.equal(resp.diverged, false, 'derived quantities match those stored')
+    t.equal(resp.written, false, 'nothing written for read-only recalculation')
+
+    // ACT: recalculate the receiving side of the move
+    resp = await observation.call('economic_resource', 'recalculate_economic_resource', { address: destResourceId })
+    t.equal(resp.eventsReplayed, 2, 'incoming transfer events replayed')
+    t.equal(resp.accountingQuantity.hasNumericalValue, 1, 'incoming accounting quantity derived')
+    t.equal(resp.onhandQuantity.hasNumericalValue, 1, 'incoming onhand quantity derived')
+    t.equal(resp.diverged, false, 'receiving resource matches derived quantities')
+
+    // ACT: recalculate with writing enabled
+    resp = await observation.call('economic_resource', 'recalculate_economic_resource', { address: resourceId, write: true })
+    await pause(100)
+    t.equal(resp.written, false, 'resources matching their event history are not rewritten')
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.deepLooseEqual(resp.economicResource.revisionId, stored.revisionId, 'resource revision unchanged')
+
+    // ASSERT: recalculation is reported against later events
+    await createEvent({ action: 'raise', resourceInventoriedAs: resourceId, resourceQuantity: qty(3) })
+    resp = await observation.call('economic_resource', 'recalculate_economic_resource', { address: resourceId })
+    t.equal(resp.eventsReplayed, 5, 'newly observed events replayed')
+    t.equal(resp.accountingQuantity.hasNumericalValue, 8, 'newly observed events included in derived quantity')
+    t.equal(resp.diverged, false, 'stored quantities kept in step with new events')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,97 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress,
+  mockIdentifier,
+  buildPlayer,
+  serializeId,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+  resourceClassifiedAs: ['http://www.productontology.org/doc/Apple.ttl'],
+}
+
+// depth at which a record was reached by a traversal, or undefined if it was not reached
+const depthOf = (graph, type, id) => {
+  const node = graph.nodes.find(({ item }) => item[type] && serializeId(item[type]) === serializeId(id))
+  return node ? node.depth : undefined
+}
+
+test('EconomicResource track & trace', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const resourceUnitId = mockIdentifier(false)
+    const qty = (hasNumericalValue) => ({ hasNumericalValue, hasUnit: resourceUnitId })
+    const createEvent = async (event, newInventoriedResource) => {
+      const resp = await observation.call('economic_event', 'create_economic_event', { event: { ...testEventProps, ...event }, newInventoriedResource })
+      await pause(100)
+      return resp
+    }
+
+    // SCENARIO: apples are raised, partly consumed to bake pies, and some are discarded
+    let resp = await observation.call('process', 'create_process', { process: { name: 'bake pies' } })
+    const bakeId = resp.process.id
+    resp = await observation.call('process', 'create_process', { process: { name: 'serve pies' } })
+    const serveId = resp.process.id
+    await pause(100)
+
+    resp = await createEvent({ action: 'raise', resourceQuantity: qty(10) }, { name: 'apples' })
+    const raiseId = resp.economicEvent.id
+    const applesId = resp.economicResource.id
+    resp = await createEvent({ action: 'consume', resourceInventoriedAs: applesId, inputOf: bakeId, resourceQuantity: qty(4) })
+    const consumeId = resp.economicEvent.id
+    resp = await createEvent({ action: 'lower', resourceInventoriedAs: applesId, resourceQuantity: qty(1) })
+    const lowerId = resp.economicEvent.id
+    resp = await createEvent({ action: 'produce', outputOf: bakeId, resourceQuantity: qty(2) }, { name: 'pies' })
+    const produceId = resp.economicEvent.id
+    const piesId = resp.economicResource.id
+    resp = await createEvent({ action: 'pass', resourceInventoriedAs: piesId, outputOf: bakeId, resourceQuantity: qty(2) })
+    const passId = resp.economicEvent.id
+    resp = await createEvent({ action: 'use', resourceInventoriedAs: piesId, inputOf: serveId, resourceQuantity: qty(2) })
+    const useId = resp.economicEvent.id
+
+    // ASSERT: tracing the pies back to the apples they were made from
+    let graph = await observation.call('economic_event', 'trace', { start: { EconomicResource: piesId } })
+    t.equal(depthOf(graph, 'EconomicResource', piesId), 0, 'trace starts at the requested record')
+    t.equal(depthOf(graph, 'EconomicEvent', produceId), 1, 'produce event traced as an inflow of its output resource')
+    t.equal(depthOf(graph, 'EconomicEvent', passId), 1, 'non-inventory output event traced as an inflow')
+    t.equal(depthOf(graph, 'Process', bakeId), 2, 'producing process traced')
+    t.equal(depthOf(graph, 'EconomicEvent', consumeId), 3, 'process inputs traced')
+    t.equal(depthOf(graph, 'EconomicResource', applesId), 4, 'consumed resource traced')
+    t.equal(depthOf(graph, 'EconomicEvent', raiseId), 5, 'raise event traced as an inflow')
+    t.equal(depthOf(graph, 'EconomicEvent', lowerId), undefined, 'lower event is not an inflow of the resource')
+    t.equal(depthOf(graph, 'EconomicEvent', useId), undefined, 'non-inventory input event is not an inflow of the resource')
+    t.equal(depthOf(graph, 'Process', serveId), undefined, 'downstream processes not traced')
+    t.ok(graph.edges.some(({ from, to }) => from.EconomicEvent && to.EconomicResource &&
+      serializeId(from.EconomicEvent) === serializeId(produceId) && serializeId(to.EconomicResource) === serializeId(piesId)
+    ), 'traced edges point in the direction of flow')
+
+    // ASSERT: tracking the apples forward into the pies
+    graph = await observation.call('economic_event', 'track', { start: { EconomicResource: applesId } })
+    t.equal(depthOf(graph, 'EconomicEvent', consumeId), 1, 'consume event tracked as an outflow')
+    t.equal(depthOf(graph, 'EconomicEvent', lowerId), 1, 'lower event tracked as an outflow')
+    t.equal(depthOf(graph, 'EconomicEvent', raiseId), undefined, 'raise event is not an outflow of the resource')
+    t.equal(depthOf(graph, 'Process', bakeId), 2, 'consuming process tracked')
+    t.equal(depthOf(graph, 'EconomicEvent', produceId), 3, 'process outputs tracked')
+    t.equal(depthOf(graph, 'EconomicResource', piesId), 4, 'produced resource tracked')
+    t.equal(depthOf(graph, 'EconomicEvent', useId), 5, 'non-inventory input event tracked as an outflow')
+    t.equal(depthOf(graph, 'Process', serveId), 6, 'using process tracked')
+
+    // ASSERT: traversal depth is limited
+    graph = await observation.call('economic_event', 'track', { start: { EconomicResource: applesId }, maxDepth: 2 })
+    t.equal(depthOf(graph, 'Process', bakeId), 2, 'records within maximum depth tracked')
+    t.equal(depthOf(graph, 'EconomicEvent', produceId), undefined, 'records beyond maximum depth not tracked')
+    t.ok(graph.nodes.every(({ depth }) => depth <= 2), 'no records beyond maximum depth')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
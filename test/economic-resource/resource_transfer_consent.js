@@ -0,0 +1,200 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  buildPlayers,
+  mockIdentifier,
+} from '../init.js'
+
+// mirrors `bundles/dna_templates/observation/dna.yaml`, with transfer consent enabled
+const observationProperties = {
+  time_index_chunk_interval_ms: 30000,
+  process: { index_zome: 'process_index', economic_resource_index_zome: 'economic_resource_index' },
+  process_index: { record_storage_zome: 'process' },
+  economic_event: {
+    index_zome: 'economic_event_index',
+    process_index_zome: 'process_index',
+    economic_resource_index_zome: 'economic_resource_index',
+    economic_resource_zome: 'economic_resource',
+  },
+  economic_event_index: { record_storage_zome: 'economic_event' },
+  economic_resource: {
+    index_zome: 'economic_resource_index',
+    product_batch_index_zome: 'product_batch_index',
+    require_transfer_consent: true,
+  },
+  economic_resource_index: { record_storage_zome: 'economic_resource' },
+  fulfillment: { index_zome: 'fulfillment_index', economic_event_index_zome: 'economic_event_index' },
+  fulfillment_index: { record_storage_zome: 'fulfillment' },
+  satisfaction: { index_zome: 'satisfaction_index', economic_event_index_zome: 'economic_event_index' },
+  satisfaction_index: { record_storage_zome: 'satisfaction' },
+  claim: { index_zome: 'claim_index', economic_event_index_zome: 'economic_event_index' },
+  claim_index: { record_storage_zome: 'claim' },
+  settlement: { index_zome: 'settlement_index', economic_event_index_zome: 'economic_event_index', claim_index_zome: 'claim_index' },
+  settlement_index: { record_storage_zome: 'settlement' },
+  appreciation: { index_zome: 'appreciation_index', economic_event_index_zome: 'economic_event_index' },
+  appreciation_index: { record_storage_zome: 'appreciation' },
+  product_batch: { index_zome: 'product_batch_index' },
+  product_batch_index: { record_storage_zome: 'product_batch' },
+  remote_auth: { permissions: [] },
+}
+
+const TRANSFER_ACTIONS = ['transfer', 'transfer-all-rights', 'transfer_all_rights', 'transfer-custody', 'transfer_custody']
+
+test('EconomicResource transfer consent', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'agent'], undefined, { observation: observationProperties })
+  try {
+    const { cells: [observation, agent] } = alice
+    const resourceUnitId = mockIdentifier(false)
+
+    // SCENARIO: Alice is accountable for one resource and Bob for another
+    const aResp = await agent.call('agent', 'create_agent', { agent: { name: 'Alice', agentType: 'Person' } })
+    const bResp = await agent.call('agent', 'create_agent', { agent: { name: 'Bob', agentType: 'Person' } })
+    await pause(100)
+    const aliceId = aResp.agent.id
+    const bobId = bResp.agent.id
+    await agent.call('agent', 'associate_my_agent', { agentAddress: aliceId })
+    await pause(100)
+
+    const raise = async (receiver, name) => {
+      const resp = await observation.call('economic_event', 'create_economic_event', {
+        event: {
+          action: 'raise',
+          provider: receiver,
+          receiver,
+          resourceClassifiedAs: ['some-resource-type'],
+          resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+          hasPointInTime: new Date(),
+        },
+        newInventoriedResource: { name },
+      })
+      await pause(100)
+      return resp.economicResource.id
+    }
+    const aliceResourceId = await raise(aliceId, 'alice resource')
+    const bobResourceId = await raise(bobId, 'bob resource')
+
+    const transfer = (action, resourceId, provider) => observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action,
+        provider,
+        receiver: aliceId,
+        resourceInventoriedAs: resourceId,
+        toResourceInventoriedAs: resourceId,
+        resourceClassifiedAs: ['some-resource-type'],
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: resourceUnitId },
+        hasPointInTime: new Date(),
+      },
+    })
+
+    for (const action of TRANSFER_ACTIONS) {
+      // ASSERT: transfers from resources the current agent is not accountable for are rejected
+      let err
+      try {
+        await transfer(action, bobResourceId, bobId)
+      } catch (e) {
+        err = e
+      }
+      t.ok(err && err.data.data.indexOf('requires the consent of its primary accountable agent') !== -1, `${action} without consent of the accountable agent is rejected`)
+
+      // ASSERT: transfers from resources the current agent is accountable for are permitted
+      const resp = await transfer(action, aliceResourceId, aliceId)
+      await pause(100)
+      t.ok(resp.economicEvent && resp.economicEvent.id, `${action} by the accountable agent is permitted`)
+    }
+
+    const readResp = await observation.call('economic_resource', 'get_economic_resource', { address: bobResourceId })
+    t.deepLooseEqual(readResp.economicResource.primaryAccountable, bobId, 'rejected transfers leave accountability unchanged')
+    t.equal(readResp.economicResource.accountingQuantity.hasNumericalValue, 10, 'rejected transfers leave quantities unchanged')
+
+    // ASSERT: other actions are not subject to transfer consent
+    const consumeResp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'consume',
+        provider: bobId,
+        receiver: bobId,
+        resourceInventoriedAs: bobResourceId,
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: resourceUnitId },
+        hasPointInTime: new Date(),
+      },
+    })
+    t.ok(consumeResp.economicEvent && consumeResp.economicEvent.id, 'non-transfer actions do not require consent')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('EconomicResource transfer consent validation', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const { players: [alice, bob], scenario } = await buildPlayers(2, ['observation', 'agent'], undefined, { observation: observationProperties })
+  try {
+    const { cells: [aliceObservation, aliceAgent] } = alice
+    const { cells: [bobObservation, bobAgent] } = bob
+    const resourceUnitId = mockIdentifier(false)
+
+    // SCENARIO: Alice and Bob are each associated with an Agent
+    const aResp = await aliceAgent.call('agent', 'create_agent', { agent: { name: 'Alice', agentType: 'Person' } })
+    const bResp = await bobAgent.call('agent', 'create_agent', { agent: { name: 'Bob', agentType: 'Person' } })
+    const aliceId = aResp.agent.id
+    const bobId = bResp.agent.id
+    await aliceAgent.call('agent', 'associate_my_agent', { agentAddress: aliceId })
+    await bobAgent.call('agent', 'associate_my_agent', { agentAddress: bobId })
+    await pause(1000)
+
+    const raise = async (observation, name) => {
+      const resp = await observation.call('economic_event', 'create_economic_event', {
+        event: {
+          action: 'raise',
+          provider: bobId,
+          receiver: bobId,
+          resourceClassifiedAs: ['some-resource-type'],
+          resourceQuantity: { hasNumericalValue: 10, hasUnit: resourceUnitId },
+          hasPointInTime: new Date(),
+        },
+        newInventoriedResource: { name },
+      })
+      await pause(1000)
+      return resp.economicResource.id
+    }
+    const transferCustody = (observation, resourceId) => observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'transfer-custody',
+        provider: bobId,
+        receiver: aliceId,
+        resourceInventoriedAs: resourceId,
+        resourceClassifiedAs: ['some-resource-type'],
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: resourceUnitId },
+        hasPointInTime: new Date(),
+      },
+    })
+
+    // ASSERT: Bob is accountable for a resource which Alice created, but validation requires the creator's consent
+    const aliceResourceId = await raise(aliceObservation, 'resource created by alice')
+    let err
+    try {
+      await transferCustody(bobObservation, aliceResourceId)
+    } catch (e) {
+      err = e
+    }
+    t.ok(err && err.data.data.indexOf('require the consent of the agent who created them') !== -1, 'transfer not consented to by the resource creator is invalid')
+
+    const readResp = await aliceObservation.call('economic_resource', 'get_economic_resource', { address: aliceResourceId })
+    t.equal(readResp.economicResource.onhandQuantity.hasNumericalValue, 10, 'invalid transfer leaves quantities unchanged')
+
+    // ASSERT: transfers from resources created by the transferring agent are valid
+    const bobResourceId = await raise(bobObservation, 'resource created by bob')
+    const resp = await transferCustody(bobObservation, bobResourceId)
+    t.ok(resp.economicEvent && resp.economicEvent.id, 'transfer by the resource creator is valid')
+  } catch (e) {
+    await scenario.cleanUp()
+    throw e
+  }
+  await scenario.cleanUp()
+})
@@ -0,0 +1,69 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress,
+  buildPlayer,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+  resourceClassifiedAs: ['http://www.productontology.org/doc/Apple.ttl'],
+}
+
+test('EconomicResource unit conversion', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'specification'])
+  try {
+    const { cells: [observation, specification] } = alice
+    const createUnit = async (unit) => {
+      const resp = await specification.call('unit', 'create_unit', { unit })
+      return resp.unit.id
+    }
+
+    // SCENARIO: units of the same dimension, and a unit with no registered conversion
+    const kiloId = await createUnit({ label: 'test kilograms', symbol: 'test-kg', dimension: 'mass', conversionFactor: '1' })
+    const gramId = await createUnit({ label: 'test grams', symbol: 'test-g', dimension: 'mass', conversionFactor: '0.001' })
+    const crateId = await createUnit({ label: 'test crates', symbol: 'test-crate' })
+    await pause(100)
+
+    let resp = await observation.call('economic_event', 'create_economic_event', {
+      event: { action: 'raise', resourceQuantity: { hasNumericalValue: 2, hasUnit: kiloId }, ...testEventProps },
+      newInventoriedResource: { name: 'apples' },
+    })
+    await pause(100)
+    const resourceId = resp.economicResource.id
+
+    // ACT: observe an event measured in another unit of the same dimension
+    await observation.call('economic_event', 'create_economic_event', {
+      event: { action: 'raise', resourceInventoriedAs: resourceId, resourceQuantity: { hasNumericalValue: 500, hasUnit: gramId }, ...testEventProps },
+    })
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(resp.economicResource.accountingQuantity.hasDecimalValue, '2.5', 'accounting quantity converted to resource units')
+    t.equal(resp.economicResource.onhandQuantity.hasDecimalValue, '2.5', 'onhand quantity converted to resource units')
+    t.deepLooseEqual(resp.economicResource.accountingQuantity.hasUnit, kiloId, 'resource retains its own unit')
+
+    // ASSERT: units without a registered conversion are reported as such
+    let err
+    try {
+      await observation.call('economic_event', 'create_economic_event', {
+        event: { action: 'raise', resourceInventoriedAs: resourceId, resourceQuantity: { hasNumericalValue: 1, hasUnit: crateId }, ...testEventProps },
+      })
+    } catch (e) {
+      err = e
+    }
+    t.notEqual(err.data.data.indexOf('has no registered dimension and conversion factor'), -1, 'unconvertible unit rejected with a distinct error')
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(resp.economicResource.accountingQuantity.hasDecimalValue, '2.5', 'rejected event has no effect on resource')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
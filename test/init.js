@@ -62,6 +62,7 @@ const dnaPaths = {
     '../bundles/dna/specification/hrea_specification.dna',
   ),
   plan: path.resolve(__dirname, '../bundles/dna/plan/hrea_plan.dna'),
+  recipe: path.resolve(__dirname, '../bundles/dna/recipe/hrea_recipe.dna'),
 }
 const getDNA = (name) => dnaPaths[name]
 
@@ -107,64 +108,84 @@ const buildGraphQL = async (player, apiOptions = {}, appCellMapping) => {
   }
 }
 
+/**
+ * Describes a hApp bundle installing the named hREA DNAs.
+ *
+ * `dnaProperties` may be keyed by DNA name to install DNAs with different properties
+ * to those in their bundle. Note that these replace the bundled properties entirely.
+ */
+const buildAppBundle = (dnasToInstall, dnaProperties = {}) => ({
+  bundle: {
+    manifest: {
+      name: 'installed-app-id',
+      manifest_version: '1',
+      roles: dnasToInstall.map((name) => ({
+        name: `hrea_${name}_1`,
+        // https://docs.rs/holochain_types/0.1.0-beta-rc.1/src/holochain_types/app/app_manifest/app_manifest_v1.rs.html#165-180
+        provisioning: {
+          strategy: 'create',
+          deferred: false,
+        },
+        dna: {
+          path: getDNA(name),
+          // modifiers: {
+          //   network_seed: Math.random().toString(),
+          // },
+          ...(dnaProperties[name] ? { modifiers: { properties: dnaProperties[name] } } : {}),
+        },
+      })),
+    },
+    resources: {},
+  },
+})
+
+/**
+ * Binds the hREA DNA `cells` of an installed player, in the order of `dnasToInstall`.
+ */
+const bindPlayerCells = (player, dnasToInstall) => {
+  const cellIdsKeyedByRole = {}
+  const cellsKeyedByRole = {}
+  for (const [name, cell] of player.namedCells.entries()) {
+    const hreaCellMatch = name.match(/hrea_(\w+)_\d+/)
+    if (hreaCellMatch) {
+      const hreaRole = hreaCellMatch[1]
+      cellIdsKeyedByRole[hreaRole] = cell.cell_id
+      cellsKeyedByRole[hreaRole] = cell
+    }
+  }
+
+  const cells = dnasToInstall.map((name) => cellsKeyedByRole[name]).map((cell) => {
+    // patch for old syntax for calling
+    cell.call = (zomeName, fnName, payload) => {
+      return cell.callZome({
+        zome_name: zomeName,
+        fn_name: fnName,
+        payload,
+      }, 60000)
+    }
+    return cell
+  })
+
+  return { cells, cellIdsKeyedByRole }
+}
+
 /**
  * Creates bindings for a player against a single hApp, returning a GraphQL client
  * as well as the underlying Holochain DNA `cells`.
+ *
+ * @see buildAppBundle for `dnaProperties`
  */
-const buildPlayer = async (dnasToInstall, graphQLAPIOptions) => {
+const buildPlayer = async (dnasToInstall, graphQLAPIOptions, dnaProperties = {}) => {
   // Create an empty scenario.
   const scenario = new Scenario({
     timeout: 60000,
   })
   try {
-    const player = await scenario.addPlayerWithApp({
-      bundle: {
-        manifest: {
-          name: 'installed-app-id',
-          manifest_version: '1',
-          roles: dnasToInstall.map((name) => ({
-            name: `hrea_${name}_1`,
-            // https://docs.rs/holochain_types/0.1.0-beta-rc.1/src/holochain_types/app/app_manifest/app_manifest_v1.rs.html#165-180
-            provisioning: {
-              strategy: 'create',
-              deferred: false,
-            },
-            dna: {
-              path: getDNA(name),
-              // modifiers: {
-              //   network_seed: Math.random().toString(),
-              // },
-            },
-          })),
-        },
-        resources: {},
-      },
-    })
+    const player = await scenario.addPlayerWithApp(buildAppBundle(dnasToInstall, dnaProperties))
 
     console.info(`Created new player with admin URI ${player.conductor.adminWs().client.socket._url}`)
 
-    const cellIdsKeyedByRole = {}
-    const cellsKeyedByRole = {}
-    for (const [name, cell] of player.namedCells.entries()) {
-      const hreaCellMatch = name.match(/hrea_(\w+)_\d+/)
-      if (hreaCellMatch) {
-        const hreaRole = hreaCellMatch[1]
-        cellIdsKeyedByRole[hreaRole] = cell.cell_id
-        cellsKeyedByRole[hreaRole] = cell
-      }
-    }
-
-    const cells = dnasToInstall.map((name) => cellsKeyedByRole[name]).map((cell) => {
-      // patch for old syntax for calling
-      cell.call = (zomeName, fnName, payload) => {
-        return cell.callZome({
-          zome_name: zomeName,
-          fn_name: fnName,
-          payload,
-        }, 60000)
-      }
-      return cell
-    })
+    const { cells, cellIdsKeyedByRole } = bindPlayerCells(player, dnasToInstall)
 
     try {
       const graphQL = await buildGraphQL(player, graphQLAPIOptions, cellIdsKeyedByRole)
@@ -187,6 +208,34 @@ const buildPlayer = async (dnasToInstall, graphQLAPIOptions) => {
   }
 }
 
+/**
+ * Creates `count` players sharing a single network, for tests which require
+ * interaction between agents. Each player is bound as in `buildPlayer`.
+ *
+ * @see buildAppBundle for `dnaProperties`
+ */
+const buildPlayers = async (count, dnasToInstall, graphQLAPIOptions, dnaProperties = {}) => {
+  const scenario = new Scenario({
+    timeout: 60000,
+  })
+  try {
+    const players = await scenario.addPlayersWithApps(Array.from({ length: count }, () => buildAppBundle(dnasToInstall, dnaProperties)))
+    await scenario.shareAllAgents()
+
+    const boundPlayers = []
+    for (const player of players) {
+      const { cells, cellIdsKeyedByRole } = bindPlayerCells(player, dnasToInstall)
+      const graphQL = await buildGraphQL(player, graphQLAPIOptions, cellIdsKeyedByRole)
+      boundPlayers.push({ graphQL, cells, player })
+    }
+    return { players: boundPlayers, scenario }
+  } catch (e) {
+    await scenario.cleanUp()
+    console.error('error during scenario.addPlayersWithApps: ', e)
+    throw e
+  }
+}
+
 // @see https://crates.io/crates/holo_hash
 const HOLOCHAIN_RAW_IDENTIFIER_LEN = 36
 // @see holo_hash::hash_type::primitive
@@ -287,6 +336,7 @@ const mockIdentifier = (asStr = true) => {
 export {
   getDNA,
   buildPlayer,
+  buildPlayers,
   buildGraphQL,
   serializeId,
   mockAgentId,
@@ -0,0 +1,126 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAgentId,
+  mockIdentifier,
+} from '../init.js'
+
+const HOUR_MS = 3600000
+
+test('Plan generation from a two-level recipe', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['plan', 'recipe', 'observation', 'planning', 'specification'])
+  try {
+    const { cells: [plan, recipe, observation, planning, specification] } = alice
+    const unitId = mockIdentifier(false)
+    const qty = (hasNumericalValue) => ({ hasNumericalValue, hasUnit: unitId })
+
+    // SCENARIO: bread is baked from dough, dough is mixed from flour
+    const createSpec = async (name) => {
+      const resp = await specification.call('resource_specification', 'create_resource_specification', { resourceSpecification: { name } })
+      return resp.resourceSpecification.id
+    }
+    const breadSpecId = await createSpec('bread')
+    const doughSpecId = await createSpec('dough')
+    const flourSpecId = await createSpec('flour')
+    await pause(100)
+
+    const createRecipeResource = async (name, resourceConformsTo) => {
+      const resp = await recipe.call('recipe_resource', 'create_recipe_resource', { recipeResource: { name, resourceConformsTo } })
+      return resp.recipeResource.id
+    }
+    const breadId = await createRecipeResource('bread', breadSpecId)
+    const doughId = await createRecipeResource('dough', doughSpecId)
+    const flourId = await createRecipeResource('flour', flourSpecId)
+
+    const createRecipeProcess = async (name, hours) => {
+      const resp = await recipe.call('recipe_process', 'create_recipe_process', {
+        recipeProcess: { name, hasDuration: { numericDuration: hours, unitType: 'hour' } },
+      })
+      return resp.recipeProcess.id
+    }
+    const bakeId = await createRecipeProcess('bake', 2)
+    const mixId = await createRecipeProcess('mix', 1)
+    await pause(100)
+
+    const createRecipeFlow = async (recipeFlow) => {
+      const resp = await recipe.call('recipe_flow', 'create_recipe_flow', { recipeFlow })
+      t.ok(resp.recipeFlow && resp.recipeFlow.id, `recipe flow ${recipeFlow.action} created`)
+    }
+    // baking produces 10 bread from 5 dough
+    await createRecipeFlow({ action: 'produce', recipeFlowResource: breadId, resourceQuantity: qty(10), recipeOutputOf: bakeId })
+    await createRecipeFlow({ action: 'consume', recipeFlowResource: doughId, resourceQuantity: qty(5), recipeInputOf: bakeId })
+    // mixing produces 5 dough from 4 flour
+    await createRecipeFlow({ action: 'produce', recipeFlowResource: doughId, resourceQuantity: qty(5), recipeOutputOf: mixId })
+    await createRecipeFlow({ action: 'consume', recipeFlowResource: flourId, resourceQuantity: qty(4), recipeInputOf: mixId })
+    await pause(100)
+
+    // ACT: generate a plan for 20 bread
+    const due = new Date(Date.now() + 48 * HOUR_MS)
+    due.setMilliseconds(0)
+    const genResp = await plan.call('plan', 'generate_plan', {
+      plan: { name: 'bread run' },
+      resourceConformsTo: breadSpecId,
+      resourceQuantity: qty(20),
+      due: due.toISOString(),
+      agent: mockAgentId(false),
+      cells: {
+        recipe: recipe.cell_id[0],
+        observation: observation.cell_id[0],
+        planning: planning.cell_id[0],
+      },
+    })
+    await pause(100)
+    t.ok(genResp.plan && genResp.plan.id, 'plan generated')
+    t.equal(genResp.plan.processes.length, 2, 'a process is generated for each recipe level')
+    t.equal(genResp.plan.independentDemands.length, 1, 'demanded output is recorded as the independent demand')
+
+    const processes = {}
+    for (const processId of genResp.plan.processes) {
+      const resp = await observation.call('process', 'get_process', { address: processId })
+      processes[resp.process.name] = resp.process
+    }
+    t.ok(processes.bake, 'bake process generated')
+    t.ok(processes.mix, 'mix process generated')
+
+    const flowsOf = async (processId) => {
+      const inputs = await planning.call('commitment_index', 'query_commitments', { params: { inputOf: processId } })
+      const outputs = await planning.call('commitment_index', 'query_commitments', { params: { outputOf: processId } })
+      return {
+        inputs: inputs.edges.map(e => e.node),
+        outputs: outputs.edges.map(e => e.node),
+      }
+    }
+
+    // ASSERT: top-level process is scaled to the demanded quantity and ends when due
+    const bake = await flowsOf(processes.bake.id)
+    t.equal(bake.outputs.length, 1, 'bake has one output commitment')
+    t.equal(bake.inputs.length, 1, 'bake has one input commitment')
+    t.equal(bake.outputs[0].resourceQuantity.hasNumericalValue, 20, 'bake output scaled to demanded quantity')
+    t.deepLooseEqual(bake.outputs[0].resourceConformsTo, breadSpecId, 'bake output conforms to demanded specification')
+    t.deepLooseEqual(bake.outputs[0].independentDemandOf, genResp.plan.id, 'bake output is the independent demand of the plan')
+    t.equal(bake.inputs[0].resourceQuantity.hasNumericalValue, 10, 'bake input scaled by recipe ratio')
+    t.equal(new Date(processes.bake.hasEnd).getTime(), due.getTime(), 'bake ends when demand is due')
+    t.equal(new Date(processes.bake.hasBeginning).getTime(), due.getTime() - 2 * HOUR_MS, 'bake begins its duration before it ends')
+    t.equal(new Date(bake.inputs[0].due).getTime(), due.getTime() - 2 * HOUR_MS, 'bake input due when bake begins')
+
+    // ASSERT: dependent demand is scaled to the consuming input and scheduled backwards from it
+    const mix = await flowsOf(processes.mix.id)
+    t.equal(mix.outputs.length, 1, 'mix has one output commitment')
+    t.equal(mix.inputs.length, 1, 'mix has one input commitment')
+    t.equal(mix.outputs[0].resourceQuantity.hasNumericalValue, 10, 'mix output scaled to dough consumed by bake')
+    t.deepLooseEqual(mix.outputs[0].resourceConformsTo, doughSpecId, 'mix output conforms to dough specification')
+    t.notOk(mix.outputs[0].independentDemandOf, 'dependent demand is not an independent demand of the plan')
+    t.equal(mix.inputs[0].resourceQuantity.hasNumericalValue, 8, 'mix input scaled by both recipe ratios')
+    t.deepLooseEqual(mix.inputs[0].resourceConformsTo, flourSpecId, 'mix input conforms to flour specification')
+    t.equal(new Date(processes.mix.hasEnd).getTime(), due.getTime() - 2 * HOUR_MS, 'mix ends when bake begins')
+    t.equal(new Date(processes.mix.hasBeginning).getTime(), due.getTime() - 3 * HOUR_MS, 'mix begins its duration before it ends')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,156 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+test('Scenario and ScenarioDefinition record API', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['plan', 'planning'])
+  try {
+    const { cells: [plan, planning] } = alice
+    const unitId = mockIdentifier(false)
+    const qty = (hasNumericalValue) => ({ hasNumericalValue, hasUnit: unitId })
+    const flowProps = {
+      provider: mockAddress(false),
+      receiver: mockAddress(false),
+      resourceClassifiedAs: ['some-resource-type'],
+      hasPointInTime: '2019-11-19T04:29:55.056Z',
+    }
+
+    // SCENARIO: a budget definition with a base scenario, refined by a more detailed one
+    const definitionResp = await plan.call('scenario_definition', 'create_scenario_definition', {
+      scenarioDefinition: { name: 'annual budget', note: 'budget template' },
+    })
+    await pause(100)
+    const definitionId = definitionResp.scenarioDefinition.id
+    t.ok(definitionId, 'scenario definition created')
+
+    const baseResp = await plan.call('scenario', 'create_scenario', {
+      scenario: { name: 'base budget', definedAs: definitionId, hasBeginning: '2019-01-01T00:00:00.000Z' },
+    })
+    await pause(100)
+    const baseId = baseResp.scenario.id
+    const refinementResp = await plan.call('scenario', 'create_scenario', {
+      scenario: { name: 'detailed budget', refinementOf: baseId },
+    })
+    await pause(100)
+    const refinementId = refinementResp.scenario.id
+    t.ok(baseId && refinementId, 'scenarios created')
+
+    let readResp = await plan.call('scenario', 'get_scenario', { address: baseId })
+    t.equal(readResp.scenario.name, 'base budget', 'scenario name read OK')
+    t.deepLooseEqual(readResp.scenario.definedAs, definitionId, 'scenario definition reference OK')
+    t.deepLooseEqual(readResp.scenario.refinements, [refinementId], 'reciprocal refinements index OK')
+
+    readResp = await plan.call('scenario_definition', 'get_scenario_definition', { address: definitionId })
+    t.deepLooseEqual(readResp.scenarioDefinition.definesScenario, [baseId], 'reciprocal definition index OK')
+
+    readResp = await plan.call('scenario_index', 'query_scenarios', { params: { refinementOf: baseId } })
+    t.equal(readResp.edges.length, 1, 'scenario queryable by refined scenario')
+    t.deepLooseEqual(readResp.edges[0].node.id, refinementId, 'refinement query OK')
+
+    readResp = await plan.call('scenario_definition_index', 'query_scenario_definitions', { params: { definesScenario: baseId } })
+    t.equal(readResp.edges.length, 1, 'scenario definition queryable by scenario')
+    t.deepLooseEqual(readResp.edges[0].node.id, definitionId, 'scenario definition query OK')
+
+    // ACT: scope planned flows to the scenarios
+    const createCommitment = async (action, inScenario, props) => {
+      const resp = await planning.call('commitment', 'create_commitment', { commitment: { action, inScenario, ...flowProps, ...props } })
+      return resp.commitment.id
+    }
+    const createIntent = async (action, inScenario, props) => {
+      const resp = await planning.call('intent', 'create_intent', { intent: { action, inScenario, ...flowProps, ...props } })
+      return resp.intent.id
+    }
+    const consumeId = await createCommitment('consume', baseId, { resourceQuantity: qty(4) })
+    await createCommitment('work', baseId, { effortQuantity: qty(8) })
+    await createCommitment('produce', refinementId, { resourceQuantity: qty(10) })
+    const transferIntentId = await createIntent('transfer', baseId, { resourceQuantity: qty(2) })
+    const satisfiedIntentId = await createIntent('consume', refinementId, { resourceQuantity: qty(4) })
+    await pause(100)
+    await planning.call('satisfaction', 'create_satisfaction', {
+      satisfaction: { satisfies: satisfiedIntentId, satisfiedBy: consumeId, resourceQuantity: qty(4) },
+    })
+    await pause(100)
+
+    // ASSERT: scoped flow indexes
+    readResp = await plan.call('scenario', 'get_scenario', { address: baseId })
+    t.equal(readResp.scenario.commitments.length, 2, 'scenario commitments index OK')
+    t.deepLooseEqual(readResp.scenario.intents, [transferIntentId], 'scenario intents index OK')
+
+    readResp = await planning.call('commitment_index', 'query_commitments', { params: { inScenario: refinementId } })
+    t.equal(readResp.edges.length, 1, 'commitments queryable by scenario')
+
+    readResp = await plan.call('scenario_index', 'query_scenarios', { params: { commitments: consumeId } })
+    t.equal(readResp.edges.length, 1, 'scenario queryable by commitment')
+    t.deepLooseEqual(readResp.edges[0].node.id, baseId, 'scenario commitment query OK')
+
+    // ASSERT: totals include refinements, split by flow direction
+    readResp = await plan.call('scenario', 'compare_scenarios', { scenarios: [baseId, refinementId] })
+    const [baseTotals, refinementTotals] = readResp.scenarios
+    t.deepLooseEqual(baseTotals.scenario, baseId, 'totals returned in requested order')
+    t.equal(baseTotals.commitmentCount, 3, 'commitments of refinements included in totals')
+    t.equal(baseTotals.intentCount, 1, 'satisfied intents excluded from totals')
+    t.equal(baseTotals.inputs.resourceQuantities.length, 1, 'input quantities totalled per unit')
+    t.equal(baseTotals.inputs.resourceQuantities[0].hasNumericalValue, 4, 'input resource total OK')
+    t.equal(baseTotals.inputs.effortQuantities[0].hasNumericalValue, 8, 'input effort total OK')
+    t.equal(baseTotals.outputs.resourceQuantities[0].hasNumericalValue, 10, 'output resource total OK')
+    t.equal(baseTotals.transfers.resourceQuantities[0].hasNumericalValue, 2, 'transfer resource total OK')
+
+    t.equal(refinementTotals.commitmentCount, 1, 'refined scenario totals exclude parent scenario')
+    t.equal(refinementTotals.intentCount, 0, 'refined scenario has no outstanding intents')
+    t.equal(refinementTotals.inputs.resourceQuantities.length, 0, 'refined scenario has no inputs')
+    t.equal(refinementTotals.outputs.resourceQuantities[0].hasNumericalValue, 10, 'refined scenario output total OK')
+
+    // ACT: detach the refinement and redefine the scenario
+    const updateResp = await plan.call('scenario', 'update_scenario', {
+      scenario: { revisionId: refinementResp.scenario.revisionId, refinementOf: null, definedAs: definitionId },
+    })
+    await pause(100)
+    t.equal(updateResp.scenario.refinementOf, undefined, 'refinement removed')
+    t.deepLooseEqual(updateResp.scenario.definedAs, definitionId, 'definition added')
+
+    readResp = await plan.call('scenario', 'get_scenario', { address: baseId })
+    t.deepLooseEqual(readResp.scenario.refinements || [], [], 'stale refinements index removed')
+    readResp = await plan.call('scenario_definition', 'get_scenario_definition', { address: definitionId })
+    t.equal(readResp.scenarioDefinition.definesScenario.length, 2, 'updated definition index created')
+
+    readResp = await plan.call('scenario', 'compare_scenarios', { scenarios: [baseId] })
+    t.equal(readResp.scenarios[0].commitmentCount, 2, 'detached refinement excluded from totals')
+
+    // ACT: update & delete the definition
+    const definitionUpdateResp = await plan.call('scenario_definition', 'update_scenario_definition', {
+      scenarioDefinition: { revisionId: definitionResp.scenarioDefinition.revisionId, name: 'quarterly budget' },
+    })
+    await pause(100)
+    t.equal(definitionUpdateResp.scenarioDefinition.name, 'quarterly budget', 'scenario definition updated')
+
+    let deleteResp = await plan.call('scenario', 'delete_scenario', { revisionId: updateResp.scenario.revisionId })
+    await pause(100)
+    t.equal(deleteResp, true, 'scenario deleted')
+
+    readResp = await plan.call('scenario_definition', 'get_scenario_definition', { address: definitionId })
+    t.deepLooseEqual(readResp.scenarioDefinition.definesScenario, [baseId], 'definition index removed on scenario delete')
+
+    deleteResp = await plan.call('scenario_definition', 'delete_scenario_definition', { revisionId: definitionUpdateResp.scenarioDefinition.revisionId })
+    await pause(100)
+    t.equal(deleteResp, true, 'scenario definition deleted')
+
+    let err
+    try {
+      await plan.call('scenario', 'get_scenario', { address: refinementId })
+    } catch (e) {
+      err = e
+    }
+    t.ok(err, 'querying deleted record is an error')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,82 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+  serializeId,
+} from '../init.js'
+
+test('Intent matching', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+    const unitId = mockIdentifier(false)
+    const otherUnitId = mockIdentifier(false)
+    const qty = (hasNumericalValue, hasUnit = unitId) => ({ hasNumericalValue, hasUnit })
+    const createIntent = async (intent) => {
+      const resp = await planning.call('intent', 'create_intent', { intent: { action: 'transfer', ...intent } })
+      return resp.intent.id
+    }
+    const offer = (props) => createIntent({
+      provider: mockAddress(false),
+      resourceClassifiedAs: ['apples', 'fruit'],
+      resourceQuantity: qty(10),
+      hasPointInTime: '2020-01-05T00:00:00.000Z',
+      ...props,
+    })
+
+    // SCENARIO: a request for apples, and a range of offers which fit it to varying degrees
+    const requestId = await createIntent({
+      receiver: mockAddress(false),
+      resourceClassifiedAs: ['apples', 'fruit'],
+      resourceQuantity: qty(10),
+      due: '2020-01-10T00:00:00.000Z',
+    })
+    const exactId = await offer({ note: 'exact fit' })
+    const partialId = await offer({ note: 'partial fit', resourceClassifiedAs: ['apples'], resourceQuantity: qty(5) })
+    const stockId = await offer({ note: 'large stock', resourceQuantity: qty(2), availableQuantity: qty(20) })
+    const excluded = [
+      await offer({ note: 'too late', hasPointInTime: '2020-02-01T00:00:00.000Z' }),
+      await offer({ note: 'incompatible unit', resourceQuantity: qty(10, otherUnitId) }),
+      await offer({ note: 'unrelated resource', resourceClassifiedAs: ['pears'] }),
+      await offer({ note: 'different action', action: 'transfer-custody' }),
+      await offer({ note: 'already finished', finished: true }),
+      await createIntent({ note: 'another request', receiver: mockAddress(false), resourceClassifiedAs: ['apples'], resourceQuantity: qty(10) }),
+    ]
+    await pause(100)
+
+    // ACT: match the request against open offers
+    let resp = await planning.call('intent', 'match_intent', { address: requestId })
+    const matchedIds = resp.matches.map(m => serializeId(m.intent.id))
+    t.deepEqual(matchedIds, [exactId, stockId, partialId].map(serializeId), 'compatible offers matched in order of fit')
+    t.ok(resp.matches.every(m => m.score > 0 && m.score <= 1), 'match scores are normalised')
+    t.ok(resp.matches[0].score > resp.matches[1].score, 'exact fit scored highest')
+    t.ok(excluded.every(id => !matchedIds.includes(serializeId(id))), 'incompatible intents are not matched')
+    t.notOk(matchedIds.includes(serializeId(requestId)), 'intent does not match itself')
+
+    // ACT: limit the number of results
+    resp = await planning.call('intent', 'match_intent', { address: requestId, limit: 1 })
+    t.equal(resp.matches.length, 1, 'match results limited')
+    t.deepLooseEqual(resp.matches[0].intent.id, exactId, 'best match returned first')
+
+    // ACT: match from the other side of the trade
+    resp = await planning.call('intent', 'match_intent', { address: partialId })
+    t.ok(resp.matches.some(m => serializeId(m.intent.id) === serializeId(requestId)), 'offers match complementary requests')
+    t.ok(resp.matches.every(m => !m.intent.provider), 'offers only match requests')
+
+    // ACT: close the request
+    const requestResp = await planning.call('intent', 'get_intent', { address: requestId })
+    await planning.call('intent', 'update_intent', { intent: { revisionId: requestResp.intent.revisionId, finished: true } })
+    await pause(100)
+    resp = await planning.call('intent', 'match_intent', { address: requestId })
+    t.equal(resp.matches.length, 0, 'finished intents have no matches')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
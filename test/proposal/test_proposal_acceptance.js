@@ -0,0 +1,99 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+  serializeId,
+} from '../init.js'
+
+test('Proposal acceptance', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['proposal', 'planning', 'agreement'])
+  try {
+    const { cells: [proposal, planning, agreement] } = alice
+    const cells = { planning: planning.cell_id[0], agreement: agreement.cell_id[0] }
+    const unitId = mockIdentifier(false)
+    const qty = (hasNumericalValue) => ({ hasNumericalValue, hasDecimalValue: String(hasNumericalValue), hasUnit: unitId })
+    const sellerId = mockAddress(false)
+    const buyerId = mockAddress(false)
+
+    // SCENARIO: a unit-based offer of apples in exchange for payment
+    let resp = await planning.call('intent', 'create_intent', {
+      intent: { action: 'transfer', provider: sellerId, resourceClassifiedAs: ['apples'], resourceQuantity: qty(1) },
+    })
+    const offerId = resp.intent.id
+    resp = await planning.call('intent', 'create_intent', {
+      intent: { action: 'transfer', receiver: sellerId, resourceClassifiedAs: ['money'], resourceQuantity: qty(2) },
+    })
+    const paymentId = resp.intent.id
+    resp = await proposal.call('proposal', 'create_proposal', { proposal: { name: 'apples for sale', unitBased: true } })
+    const proposalId = resp.proposal.id
+    await pause(100)
+    await proposal.call('proposed_intent', 'create_proposed_intent', { proposedIntent: { reciprocal: false, publishedIn: proposalId, publishes: offerId } })
+    await proposal.call('proposed_intent', 'create_proposed_intent', { proposedIntent: { reciprocal: true, publishedIn: proposalId, publishes: paymentId } })
+    await pause(100)
+
+    // ASSERT: unit based proposals require a quantity
+    let err
+    try {
+      await proposal.call('proposal', 'accept_proposal', { proposal: proposalId, agent: buyerId, cells })
+    } catch (e) {
+      err = e
+    }
+    t.ok(err, 'unit based proposal cannot be accepted without a unit quantity')
+
+    // ACT: accept 3 units of the proposal
+    resp = await proposal.call('proposal', 'accept_proposal', { proposal: proposalId, agent: buyerId, unitQuantity: 3, note: 'buying apples', cells })
+    await pause(100)
+    const { agreement: agreementId, commitments, satisfactions } = resp
+    t.ok(agreementId, 'agreement created')
+    t.equal(commitments.length, 2, 'commitment created for each published intent')
+    t.equal(satisfactions.length, 2, 'satisfaction created for each published intent')
+
+    // ASSERT: commitments take the open role and scaled quantities of their intents
+    const readCommitments = await Promise.all(commitments.map(address => planning.call('commitment', 'get_commitment', { address })))
+    const delivery = readCommitments.map(r => r.commitment).find(c => c.resourceClassifiedAs[0] === 'apples')
+    const payment = readCommitments.map(r => r.commitment).find(c => c.resourceClassifiedAs[0] === 'money')
+    t.deepLooseEqual(delivery.provider, sellerId, 'intent provider retained')
+    t.deepLooseEqual(delivery.receiver, buyerId, 'accepting agent takes the open receiver role')
+    t.deepLooseEqual(delivery.resourceQuantity, qty(3), 'quantity scaled by unit quantity')
+    t.deepLooseEqual(delivery.clauseOf, agreementId, 'commitment is a clause of the agreement')
+    t.deepLooseEqual(payment.provider, buyerId, 'accepting agent takes the open provider role')
+    t.deepLooseEqual(payment.receiver, sellerId, 'intent receiver retained')
+    t.deepLooseEqual(payment.resourceQuantity, qty(6), 'reciprocal quantity scaled by unit quantity')
+
+    resp = await agreement.call('agreement', 'get_agreement', { address: agreementId })
+    t.equal(resp.agreement.name, 'apples for sale', 'agreement named after proposal')
+    t.equal(resp.agreement.note, 'buying apples', 'agreement note set from acceptance')
+    t.deepLooseEqual(resp.agreement.commitments.map(serializeId).sort(), commitments.map(serializeId).sort(), 'agreement commitments index OK')
+
+    resp = await planning.call('intent', 'get_intent', { address: offerId })
+    t.equal(resp.intent.satisfiedBy.length, 1, 'offer satisfied by acceptance')
+    resp = await planning.call('satisfaction', 'get_satisfaction', { address: resp.intent.satisfiedBy[0] })
+    t.deepLooseEqual(resp.satisfaction.satisfies, offerId, 'satisfaction references intent')
+    t.deepLooseEqual(resp.satisfaction.satisfiedBy, delivery.id, 'satisfaction references commitment')
+
+    // ACT: finish one of the intents, so that the proposal can no longer be accepted
+    resp = await planning.call('intent', 'get_intent', { address: paymentId })
+    await planning.call('intent', 'update_intent', { intent: { revisionId: resp.intent.revisionId, finished: true } })
+    await pause(100)
+
+    err = undefined
+    try {
+      await proposal.call('proposal', 'accept_proposal', { proposal: proposalId, agent: buyerId, unitQuantity: 1, cells })
+    } catch (e) {
+      err = e
+    }
+    t.ok(err, 'proposal with finished intents cannot be accepted')
+
+    resp = await planning.call('commitment_index', 'read_all_commitments', {})
+    t.equal(resp.edges.length, 2, 'no commitments written for refused acceptance')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
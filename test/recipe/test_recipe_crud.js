@@ -0,0 +1,129 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+} from '../init.js'
+
+test('Recipe record API', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['recipe'])
+  try {
+    const { cells: [recipe] } = alice
+    const unitId = mockIdentifier(false)
+    const qty = (hasNumericalValue) => ({ hasNumericalValue, hasDecimalValue: String(hasNumericalValue), hasUnit: unitId })
+
+    // SCENARIO: a recipe process consuming flour to produce bread, exchanged for money
+    const flourResp = await recipe.call('recipe_resource', 'create_recipe_resource', { recipeResource: { name: 'flour', substitutable: true } })
+    const breadResp = await recipe.call('recipe_resource', 'create_recipe_resource', { recipeResource: { name: 'bread' } })
+    const processResp = await recipe.call('recipe_process', 'create_recipe_process', {
+      recipeProcess: { name: 'bake', hasDuration: { numericDuration: 2, unitType: 'hour' } },
+    })
+    const exchangeResp = await recipe.call('recipe_exchange', 'create_recipe_exchange', { recipeExchange: { name: 'sale of bread' } })
+    await pause(100)
+    const flourId = flourResp.recipeResource.id
+    const breadId = breadResp.recipeResource.id
+    const processId = processResp.recipeProcess.id
+    const exchangeId = exchangeResp.recipeExchange.id
+    t.ok(flourId && breadId, 'recipe resources created')
+    t.ok(processId, 'recipe process created')
+    t.ok(exchangeId, 'recipe exchange created')
+
+    let readResp = await recipe.call('recipe_resource', 'get_recipe_resource', { address: flourId })
+    t.equal(readResp.recipeResource.name, 'flour', 'recipe resource read OK')
+    t.equal(readResp.recipeResource.substitutable, true, 'recipe resource substitutability read OK')
+    readResp = await recipe.call('recipe_process', 'get_recipe_process', { address: processId })
+    t.deepLooseEqual(readResp.recipeProcess.hasDuration, { numericDuration: 2, unitType: 'hour' }, 'recipe process duration read OK')
+
+    // ACT: link the records with flows
+    const inputResp = await recipe.call('recipe_flow', 'create_recipe_flow', {
+      recipeFlow: { action: 'consume', recipeFlowResource: flourId, resourceQuantity: qty(4), recipeInputOf: processId },
+    })
+    const outputResp = await recipe.call('recipe_flow', 'create_recipe_flow', {
+      recipeFlow: { action: 'produce', recipeFlowResource: breadId, resourceQuantity: qty(10), recipeOutputOf: processId },
+    })
+    const clauseResp = await recipe.call('recipe_flow', 'create_recipe_flow', {
+      recipeFlow: { action: 'transfer', recipeFlowResource: breadId, resourceQuantity: qty(1), recipeClauseOf: exchangeId },
+    })
+    await pause(100)
+    const inputId = inputResp.recipeFlow.id
+    const outputId = outputResp.recipeFlow.id
+    const clauseId = clauseResp.recipeFlow.id
+
+    readResp = await recipe.call('recipe_flow', 'get_recipe_flow', { address: inputId })
+    t.deepLooseEqual(readResp.recipeFlow.recipeFlowResource, flourId, 'flow resource reference OK')
+    t.deepLooseEqual(readResp.recipeFlow.recipeInputOf, processId, 'flow process reference OK')
+    t.deepLooseEqual(readResp.recipeFlow.resourceQuantity, qty(4), 'flow quantity read OK')
+
+    // ASSERT: reciprocal indexes
+    readResp = await recipe.call('recipe_process', 'get_recipe_process', { address: processId })
+    t.deepLooseEqual(readResp.recipeProcess.recipeInputs, [inputId], 'process inputs index OK')
+    t.deepLooseEqual(readResp.recipeProcess.recipeOutputs, [outputId], 'process outputs index OK')
+
+    readResp = await recipe.call('recipe_resource', 'get_recipe_resource', { address: breadId })
+    t.equal(readResp.recipeResource.recipeFlows.length, 2, 'resource flows index OK')
+
+    readResp = await recipe.call('recipe_exchange', 'get_recipe_exchange', { address: exchangeId })
+    t.deepLooseEqual(readResp.recipeExchange.recipeStipulates, [clauseId], 'exchange clauses index OK')
+
+    readResp = await recipe.call('recipe_flow_index', 'query_recipe_flows', { params: { recipeInputOf: processId } })
+    t.equal(readResp.edges.length, 1, 'flows queryable by consuming process')
+    t.deepLooseEqual(readResp.edges[0].node.id, inputId, 'flow input query OK')
+
+    readResp = await recipe.call('recipe_flow_index', 'query_recipe_flows', { params: { recipeClauseOf: exchangeId } })
+    t.equal(readResp.edges.length, 1, 'flows queryable by exchange')
+    t.deepLooseEqual(readResp.edges[0].node.id, clauseId, 'flow clause query OK')
+
+    readResp = await recipe.call('recipe_process_index', 'query_recipe_processes', { params: { recipeOutputs: outputId } })
+    t.equal(readResp.edges.length, 1, 'process queryable by output flow')
+    t.deepLooseEqual(readResp.edges[0].node.id, processId, 'process output query OK')
+
+    // ACT: move the input flow to the bread resource
+    const updateResp = await recipe.call('recipe_flow', 'update_recipe_flow', {
+      recipeFlow: { revisionId: inputResp.recipeFlow.revisionId, recipeFlowResource: breadId, resourceQuantity: qty(5) },
+    })
+    await pause(100)
+    t.deepLooseEqual(updateResp.recipeFlow.recipeFlowResource, breadId, 'flow updated')
+    t.deepLooseEqual(updateResp.recipeFlow.resourceQuantity, qty(5), 'flow quantity updated')
+
+    readResp = await recipe.call('recipe_resource', 'get_recipe_resource', { address: flourId })
+    t.equal((readResp.recipeResource.recipeFlows || []).length, 0, 'stale resource index removed')
+    readResp = await recipe.call('recipe_resource', 'get_recipe_resource', { address: breadId })
+    t.equal(readResp.recipeResource.recipeFlows.length, 3, 'updated resource index created')
+
+    // ACT: update the other record types
+    const processUpdateResp = await recipe.call('recipe_process', 'update_recipe_process', {
+      recipeProcess: { revisionId: processResp.recipeProcess.revisionId, name: 'bake loaves' },
+    })
+    t.equal(processUpdateResp.recipeProcess.name, 'bake loaves', 'recipe process updated')
+    const exchangeUpdateResp = await recipe.call('recipe_exchange', 'update_recipe_exchange', {
+      recipeExchange: { revisionId: exchangeResp.recipeExchange.revisionId, note: 'priced per loaf' },
+    })
+    t.equal(exchangeUpdateResp.recipeExchange.note, 'priced per loaf', 'recipe exchange updated')
+    await pause(100)
+
+    // ACT: delete a flow
+    const deleteResp = await recipe.call('recipe_flow', 'delete_recipe_flow', { revisionId: updateResp.recipeFlow.revisionId })
+    await pause(100)
+    t.equal(deleteResp, true, 'flow deleted')
+
+    readResp = await recipe.call('recipe_process', 'get_recipe_process', { address: processId })
+    t.deepLooseEqual(readResp.recipeProcess.recipeInputs || [], [], 'process input index removed on delete')
+    readResp = await recipe.call('recipe_flow_index', 'query_recipe_flows', { params: { recipeInputOf: processId } })
+    t.equal(readResp.edges.length, 0, 'flow input query index removed on delete')
+
+    let err
+    try {
+      await recipe.call('recipe_flow', 'get_recipe_flow', { address: inputId })
+    } catch (e) {
+      err = e
+    }
+    t.ok(err, 'querying deleted record is an error')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
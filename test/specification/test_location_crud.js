@@ -0,0 +1,113 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const exampleEntry = {
+  name: 'warehouse',
+  lat: 51.5,
+  long: -0.12,
+  mappableAddress: '1 Test Street',
+  note: 'test location',
+}
+const updatedExampleEntry = {
+  name: 'storage yard',
+  alt: 10,
+}
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T12:12:42.739+01:00',
+  resourceClassifiedAs: ['resource-type-uri'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+}
+
+test('Location record API', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification', 'observation', 'planning'])
+  try {
+    const { cells: [specification, observation, planning] } = alice
+
+    const createResp = await specification.call('location', 'create_location', { location: exampleEntry })
+    await pause(100)
+    const locationId = createResp.location.id
+    t.ok(locationId, 'record created')
+
+    let readResp = await specification.call('location', 'get_location', { address: locationId })
+    t.equal(readResp.location.name, exampleEntry.name, 'name read OK')
+    t.equal(readResp.location.lat, exampleEntry.lat, 'latitude read OK')
+    t.equal(readResp.location.long, exampleEntry.long, 'longitude read OK')
+    t.equal(readResp.location.mappableAddress, exampleEntry.mappableAddress, 'address read OK')
+
+    // ACT: reference the location from an event, a commitment and a new inventoried resource
+    const eventResp = await observation.call('economic_event', 'create_economic_event', {
+      event: { action: 'raise', atLocation: locationId, ...testEventProps },
+      newInventoriedResource: { name: 'located resource', currentLocation: locationId },
+    })
+    const commitmentResp = await planning.call('commitment', 'create_commitment', {
+      commitment: { action: 'produce', atLocation: locationId, ...testEventProps },
+    })
+    await pause(100)
+    const eventId = eventResp.economicEvent.id
+    const resourceId = eventResp.economicResource.id
+    const commitmentId = commitmentResp.commitment.id
+    t.deepLooseEqual(eventResp.economicEvent.atLocation, locationId, 'event location reference OK')
+    t.deepLooseEqual(eventResp.economicResource.currentLocation, locationId, 'resource location reference OK')
+
+    // ASSERT: indexes from referencing records
+    readResp = await observation.call('economic_event_index', 'query_economic_events', { params: { atLocation: locationId } })
+    t.equal(readResp.edges.length, 1, 'event queryable by location')
+    t.deepLooseEqual(readResp.edges[0].node.id, eventId, 'event location query OK')
+
+    readResp = await observation.call('economic_resource_index', 'query_economic_resources', { params: { currentLocation: locationId } })
+    t.equal(readResp.edges.length, 1, 'resource queryable by location')
+    t.deepLooseEqual(readResp.edges[0].node.id, resourceId, 'resource location query OK')
+
+    // ASSERT: reciprocal indexes in the specification DNA
+    readResp = await specification.call('location_index', 'query_locations', { params: { economicEvents: eventId } })
+    t.equal(readResp.edges.length, 1, 'location queryable by event')
+    t.deepLooseEqual(readResp.edges[0].node.id, locationId, 'reciprocal event index OK')
+
+    readResp = await specification.call('location_index', 'query_locations', { params: { economicResources: resourceId } })
+    t.equal(readResp.edges.length, 1, 'location queryable by resource')
+    t.deepLooseEqual(readResp.edges[0].node.id, locationId, 'reciprocal resource index OK')
+
+    readResp = await specification.call('location_index', 'query_locations', { params: { commitments: commitmentId } })
+    t.equal(readResp.edges.length, 1, 'location queryable by commitment')
+    t.deepLooseEqual(readResp.edges[0].node.id, locationId, 'reciprocal commitment index OK')
+
+    // ACT: update the location
+    const updateResp = await specification.call('location', 'update_location', {
+      location: { revisionId: createResp.location.revisionId, ...updatedExampleEntry },
+    })
+    await pause(100)
+    t.deepLooseEqual(updateResp.location.id, locationId, 'record identity retained on update')
+    readResp = await specification.call('location', 'get_location', { address: locationId })
+    t.equal(readResp.location.name, updatedExampleEntry.name, 'updated name read OK')
+    t.equal(readResp.location.alt, updatedExampleEntry.alt, 'updated altitude read OK')
+    t.equal(readResp.location.lat, exampleEntry.lat, 'unchanged fields retained')
+
+    // ACT: delete the location
+    const deleteResp = await specification.call('location', 'delete_location', { revisionId: updateResp.location.revisionId })
+    await pause(100)
+    t.equal(deleteResp, true, 'record deleted')
+
+    let err
+    try {
+      await specification.call('location', 'get_location', { address: locationId })
+    } catch (e) {
+      err = e
+    }
+    t.ok(err, 'querying deleted record is an error')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,64 @@
+import test from 'tape'
+import {
+  buildPlayer,
+} from '../init.js'
+
+// mirrors `bundles/dna_templates/specification/dna.yaml`, with the given standard units seeded
+const specificationProperties = (seedUnits) => ({
+  time_index_chunk_interval_ms: 30000,
+  process_specification: { index_zome: 'process_specification_index' },
+  process_specification_index: { record_storage_zome: 'process_specification' },
+  resource_specification: { index_zome: 'resource_specification_index' },
+  resource_specification_index: { record_storage_zome: 'resource_specification' },
+  unit: { index_zome: 'unit_index', seed_units: seedUnits },
+  unit_index: { record_storage_zome: 'unit' },
+  location: { index_zome: 'location_index' },
+  location_index: { record_storage_zome: 'location' },
+  remote_auth: { permissions: [] },
+})
+
+test('Unit seeding', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification'], undefined, { specification: specificationProperties(['kg', 'h']) })
+  try {
+    const { cells: [specification] } = alice
+    const dnaHash = specification.cell_id[0]
+
+    // ASSERT: configured units are created on genesis
+    let resp = await specification.call('unit', 'get_unit', { id: [dnaHash, 'kg'] })
+    t.equal(resp.unit.label, 'kilogram', 'seeded unit label OK')
+    t.equal(resp.unit.dimension, 'mass', 'seeded unit dimension OK')
+    resp = await specification.call('unit', 'get_unit', { id: [dnaHash, 'h'] })
+    t.equal(resp.unit.symbol, 'h', 'all configured units seeded')
+
+    resp = await specification.call('unit_index', 'read_all_units', {})
+    t.equal(resp.edges.length, 2, 'only configured units seeded')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('Unit seeding with invalid configuration', async (t) => {
+  const alice = await buildPlayer(['specification'], undefined, { specification: specificationProperties(['not-a-unit']) })
+  try {
+    const { cells: [specification] } = alice
+
+    // ASSERT: the zome refuses to initialise with unknown units configured
+    let err
+    try {
+      await specification.call('unit_index', 'read_all_units', {})
+    } catch (e) {
+      err = e
+    }
+    t.ok(err, 'initialisation fails')
+    t.notEqual(JSON.stringify(err).indexOf('not-a-unit'), -1, 'unknown unit reported')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -8,6 +8,7 @@
  * @package hREA
  */
 use hdi::prelude::*;
+use hdk_records::validation::{ validate_update_authorship, validate_delete_authorship };
 pub use hc_zome_rea_agent_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
 
 #[hdk_extern]
@@ -27,4 +28,17 @@ pub fn __num_entry_types() -> u8 {
 #[no_mangle]
 pub fn __num_link_types() -> u8 {
     LinkTypes::len()
-}
\ No newline at end of file
+}
+
+#[hdk_extern]
+fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op {
+        Op::StoreRecord { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::StoreEntry { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterCreateLink { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterDeleteLink { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterUpdate(update) => validate_update_authorship(&update),
+        Op::RegisterDelete(delete) => validate_delete_authorship(&delete),
+        Op::RegisterAgentActivity { .. } => Ok(ValidateCallbackResult::Valid),
+    }
+}
@@ -134,6 +134,10 @@ fn construct_response<'a>(
         economic_events_as_provider,
         economic_events_as_receiver,
         inventoried_economic_resources,
+        claims_as_provider,
+        claims_as_receiver,
+        relationships_as_subject,
+        relationships_as_object,
     ): (
         // Vec<CommitmentAddress>,
         // Vec<EconomicEventAddress>,
@@ -149,6 +153,10 @@ fn construct_response<'a>(
         Vec<EconomicEventAddress>,
         Vec<EconomicEventAddress>,
         Vec<EconomicResourceAddress>,
+        Vec<ClaimAddress>,
+        Vec<ClaimAddress>,
+        Vec<AgentRelationshipAddress>,
+        Vec<AgentRelationshipAddress>,
     ),
 ) -> RecordAPIResult<ResponseData> {
     Ok(ResponseData {
@@ -175,6 +183,10 @@ fn construct_response<'a>(
             economic_events_as_provider: economic_events_as_provider.to_owned(),
             economic_events_as_receiver: economic_events_as_receiver.to_owned(),
             inventoried_economic_resources: inventoried_economic_resources.to_owned(),
+            claims_as_provider: claims_as_provider.to_owned(),
+            claims_as_receiver: claims_as_receiver.to_owned(),
+            relationships_as_subject: relationships_as_subject.to_owned(),
+            relationships_as_object: relationships_as_object.to_owned(),
         }
     })
 }
@@ -202,6 +214,10 @@ fn get_link_fields(base_address: &AgentAddress) -> RecordAPIResult<(
     Vec<EconomicEventAddress>,
     Vec<EconomicEventAddress>,
     Vec<EconomicResourceAddress>,
+    Vec<ClaimAddress>,
+    Vec<ClaimAddress>,
+    Vec<AgentRelationshipAddress>,
+    Vec<AgentRelationshipAddress>,
 )> {
     Ok((
         // read_index!(agent(base_address).commitments)?,
@@ -218,5 +234,9 @@ fn get_link_fields(base_address: &AgentAddress) -> RecordAPIResult<(
         read_index!(agent(base_address).economic_events_as_provider)?,
         read_index!(agent(base_address).economic_events_as_receiver)?,
         read_index!(agent(base_address).inventoried_economic_resources)?,
+        read_index!(agent(base_address).claims_as_provider)?,
+        read_index!(agent(base_address).claims_as_receiver)?,
+        read_index!(agent(base_address).relationships_as_subject)?,
+        read_index!(agent(base_address).relationships_as_object)?,
     ))
 }
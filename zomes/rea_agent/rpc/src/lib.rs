@@ -11,10 +11,12 @@ use hdk_uuid_types::{ DnaHash, addressable_identifier };
 pub use hdk::prelude::*;
 pub use vf_attributes_hdk::{
     AgentAddress,
+    AgentRelationshipAddress,
     ProcessAddress,
     EconomicEventAddress,
     CommitmentAddress,
     IntentAddress,
+    ClaimAddress,
     ActionHash,
     ExternalURL,
     EconomicResourceAddress,
@@ -65,6 +67,18 @@ pub struct Response {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub inventoried_economic_resources: Vec<EconomicResourceAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub claims_as_provider: Vec<ClaimAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub claims_as_receiver: Vec<ClaimAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub relationships_as_subject: Vec<AgentRelationshipAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub relationships_as_object: Vec<AgentRelationshipAddress>,
 }
 
 /// I/O struct to describe what is returned outside the gateway.
@@ -152,6 +166,10 @@ pub struct QueryParams {
     pub economic_events_as_provider: Option<EconomicEventAddress>,
     pub economic_events_as_receiver: Option<EconomicEventAddress>,
     pub inventoried_economic_resources: Option<EconomicResourceAddress>,
+    pub claims_as_provider: Option<ClaimAddress>,
+    pub claims_as_receiver: Option<ClaimAddress>,
+    pub relationships_as_subject: Option<AgentRelationshipAddress>,
+    pub relationships_as_object: Option<AgentRelationshipAddress>,
 
     // for internal use in order to query for people or organizations specifically
     pub agent_type: Option<String>,
@@ -0,0 +1,132 @@
+/**
+ * hREA appreciation zome library API
+ *
+ * Contains helper methods that can be used to manipulate `Appreciation` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_appreciation_storage::*;
+use hc_zome_rea_appreciation_rpc::*;
+
+pub use hc_zome_rea_appreciation_storage::APPRECIATION_ENTRY_TYPE;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.appreciation.index_zome)
+}
+
+pub fn handle_create_appreciation<S>(entry_def_id: S, appreciation: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, appreciation)?;
+
+    // handle link fields
+    let e = create_index!(appreciation.appreciation_of(&entry_resp.appreciation_of), economic_event.appreciated_by(&base_address));
+    hdk::prelude::debug!("handle_create_appreciation::appreciation_of index {:?}", e);
+    let e = create_index!(appreciation.appreciation_with(&entry_resp.appreciation_with), economic_event.appreciation(&base_address));
+    hdk::prelude::debug!("handle_create_appreciation::appreciation_with index {:?}", e);
+
+    construct_response(&base_address, &meta, &entry_resp)
+}
+
+pub fn handle_get_appreciation(address: AppreciationAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_update_appreciation(appreciation: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    let revision_hash = appreciation.get_revision_id().clone();
+    let (meta, base_address, new_entry, prev_entry): (_, AppreciationAddress, EntryData, EntryData) = update_record(&revision_hash, appreciation)?;
+
+    // handle link fields
+    if new_entry.appreciation_of != prev_entry.appreciation_of {
+        let new_value = vec![new_entry.appreciation_of.to_owned()];
+        let prev_value = vec![prev_entry.appreciation_of.to_owned()];
+        let e = update_index!(
+            appreciation
+                .appreciation_of(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            economic_event.appreciated_by(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_appreciation::appreciation_of index {:?}", e);
+    }
+    if new_entry.appreciation_with != prev_entry.appreciation_with {
+        let new_value = vec![new_entry.appreciation_with.to_owned()];
+        let prev_value = vec![prev_entry.appreciation_with.to_owned()];
+        let e = update_index!(
+            appreciation
+                .appreciation_with(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            economic_event.appreciation(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_appreciation::appreciation_with index {:?}", e);
+    }
+
+    construct_response(&base_address, &meta, &new_entry)
+}
+
+pub fn handle_delete_appreciation(revision_id: ActionHash) -> RecordAPIResult<bool>
+{
+    // load the record to ensure it is of the correct type
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // handle link fields
+    let e = update_index!(appreciation.appreciation_of.not(&vec![entry.appreciation_of]), economic_event.appreciated_by(&base_address));
+    hdk::prelude::debug!("handle_delete_appreciation::appreciation_of index {:?}", e);
+    let e = update_index!(appreciation.appreciation_with.not(&vec![entry.appreciation_with]), economic_event.appreciation(&base_address));
+    hdk::prelude::debug!("handle_delete_appreciation::appreciation_with index {:?}", e);
+
+    // delete entry last, as it must be present in order for links to be removed
+    delete_record::<EntryStorage>(&revision_id)
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &AppreciationAddress, meta: &SignedActionHashed, e: &EntryData,
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        appreciation: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            appreciation_of: e.appreciation_of.to_owned(),
+            appreciation_with: e.appreciation_with.to_owned(),
+            note: e.note.to_owned(),
+        }
+    })
+}
+
+//---------------- READ ----------------
+
+/// Properties accessor for zome config
+fn read_appreciation_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.appreciation.index_zome)
+}
+
+/// Properties accessor for zome config
+fn read_economic_event_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.appreciation.economic_event_index_zome
+}
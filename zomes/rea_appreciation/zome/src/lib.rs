@@ -0,0 +1,55 @@
+/**
+ * hREA Appreciation zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+
+use hc_zome_rea_appreciation_rpc::*;
+use hc_zome_rea_appreciation_lib::*;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub appreciation: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_appreciation(CreateParams { appreciation }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_appreciation(APPRECIATION_ENTRY_TYPE, appreciation)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: AppreciationAddress,
+}
+
+#[hdk_extern]
+fn get_appreciation(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_appreciation(address)?)
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub appreciation: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_appreciation(UpdateParams { appreciation }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_appreciation(appreciation)?)
+}
+
+#[hdk_extern]
+fn delete_appreciation(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
+    Ok(handle_delete_appreciation(revision_id)?)
+}
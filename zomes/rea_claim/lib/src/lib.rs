@@ -0,0 +1,174 @@
+/**
+ * hREA claim zome library API
+ *
+ * Contains helper methods that can be used to manipulate `Claim` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_claim_storage::*;
+use hc_zome_rea_claim_rpc::*;
+
+pub use hc_zome_rea_claim_storage::CLAIM_ENTRY_TYPE;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.claim.index_zome)
+}
+
+pub fn handle_create_claim<S>(entry_def_id: S, claim: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, claim)?;
+
+    // handle link fields
+    let e = create_index!(claim.triggered_by(&entry_resp.triggered_by), economic_event.triggers(&base_address));
+    hdk::prelude::debug!("handle_create_claim::triggered_by index {:?}", e);
+
+    create_index!(claim.provider(&entry_resp.provider), agent.claims_as_provider(&base_address))?;
+    create_index!(claim.receiver(&entry_resp.receiver), agent.claims_as_receiver(&base_address))?;
+
+    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_claim(address: ClaimAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_update_claim(claim: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    let revision_hash = claim.get_revision_id().clone();
+    let (meta, base_address, new_entry, prev_entry): (_, ClaimAddress, EntryData, EntryData) = update_record(&revision_hash, claim)?;
+
+    // handle link fields
+    if new_entry.triggered_by != prev_entry.triggered_by {
+        let new_value = vec![new_entry.triggered_by.to_owned()];
+        let prev_value = vec![prev_entry.triggered_by.to_owned()];
+        let e = update_index!(
+            claim
+                .triggered_by(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            economic_event.triggers(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_claim::triggered_by index {:?}", e);
+    }
+    if new_entry.provider != prev_entry.provider {
+        let new_value = vec![new_entry.provider.to_owned()];
+        let prev_value = vec![prev_entry.provider.to_owned()];
+        update_index!(
+            claim
+                .provider(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            agent.claims_as_provider(&base_address)
+        )?;
+    }
+    if new_entry.receiver != prev_entry.receiver {
+        let new_value = vec![new_entry.receiver.to_owned()];
+        let prev_value = vec![prev_entry.receiver.to_owned()];
+        update_index!(
+            claim
+                .receiver(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            agent.claims_as_receiver(&base_address)
+        )?;
+    }
+
+    construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_delete_claim(revision_id: ActionHash) -> RecordAPIResult<bool>
+{
+    // load the record to ensure it is of the correct type
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // handle link fields
+    let e = update_index!(claim.triggered_by.not(&vec![entry.triggered_by]), economic_event.triggers(&base_address));
+    hdk::prelude::debug!("handle_delete_claim::triggered_by index {:?}", e);
+    let e = update_index!(claim.provider.not(&vec![entry.provider]), agent.claims_as_provider(&base_address));
+    hdk::prelude::debug!("handle_delete_claim::provider index {:?}", e);
+    let e = update_index!(claim.receiver.not(&vec![entry.receiver]), agent.claims_as_receiver(&base_address));
+    hdk::prelude::debug!("handle_delete_claim::receiver index {:?}", e);
+
+    // delete entry last, as it must be present in order for links to be removed
+    delete_record::<EntryStorage>(&revision_id)
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &ClaimAddress, meta: &SignedActionHashed, e: &EntryData, (
+        settlements,
+    ): (
+        Vec<SettlementAddress>,
+    ),
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        claim: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            action: e.action.to_owned(),
+            provider: e.provider.to_owned(),
+            receiver: e.receiver.to_owned(),
+            triggered_by: e.triggered_by.to_owned(),
+            resource_classified_as: e.resource_classified_as.to_owned(),
+            resource_conforms_to: e.resource_conforms_to.to_owned(),
+            resource_quantity: e.resource_quantity.to_owned(),
+            effort_quantity: e.effort_quantity.to_owned(),
+            due: e.due.to_owned(),
+            created: e.created.to_owned(),
+            agreed_in: e.agreed_in.to_owned(),
+            note: e.note.to_owned(),
+            in_scope_of: e.in_scope_of.to_owned(),
+            finished: e.finished.to_owned(),
+            settled_by: settlements.to_owned(),
+        }
+    })
+}
+
+//---------------- READ ----------------
+
+/// Properties accessor for zome config
+fn read_claim_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.claim.index_zome)
+}
+
+/// Properties accessor for zome config
+fn read_economic_event_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.claim.economic_event_index_zome
+}
+
+/// Properties accessor for zome config
+fn read_agent_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.claim.agent_index_zome
+}
+
+// @see construct_response
+fn get_link_fields(base_address: &ClaimAddress) -> RecordAPIResult<(
+    Vec<SettlementAddress>,
+)> {
+    Ok((
+        read_index!(claim(base_address).settled_by)?,
+    ))
+}
@@ -0,0 +1,55 @@
+/**
+ * hREA Claim zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+
+use hc_zome_rea_claim_rpc::*;
+use hc_zome_rea_claim_lib::*;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub claim: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_claim(CreateParams { claim }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_claim(CLAIM_ENTRY_TYPE, claim)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: ClaimAddress,
+}
+
+#[hdk_extern]
+fn get_claim(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_claim(address)?)
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub claim: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_claim(UpdateParams { claim }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_claim(claim)?)
+}
+
+#[hdk_extern]
+fn delete_claim(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
+    Ok(handle_delete_claim(revision_id)?)
+}
@@ -0,0 +1,18 @@
+/**
+ * Claim query indexes for observation DNA
+ *
+ * @package hREA
+ * @since   2026-10-18
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_claim_rpc::*;
+
+#[index_zome]
+struct Claim {
+    triggered_by: Local<economic_event, triggers>,
+    settled_by: Local<settlement, settles>,
+
+    // internal indexes (not part of VF spec)
+    provider: Local<agent, claims_as_provider>,
+    receiver: Local<agent, claims_as_receiver>,
+}
@@ -60,6 +60,18 @@ pub fn handle_create_commitment<S>(entry_def_id: S, commitment: CreateRequest) -
         let e = create_index!(commitment.planned_within(planned_within), plan.non_process_commitments(&base_address));
         hdk::prelude::debug!("handle_create_commitment::planned_within index {:?}", e);
     };
+    if let CreateRequest { in_scenario: MaybeUndefined::Some(in_scenario), .. } = &commitment {
+        let e = create_index!(commitment.in_scenario(in_scenario), scenario.commitments(&base_address));
+        hdk::prelude::debug!("handle_create_commitment::in_scenario index {:?}", e);
+    };
+    if let CreateRequest { at_location: MaybeUndefined::Some(at_location), .. } = &commitment {
+        let e = create_index!(commitment.at_location(at_location), location.commitments(&base_address));
+        hdk::prelude::debug!("handle_create_commitment::at_location index {:?}", e);
+    };
+    if let CreateRequest { resource_conforms_to: MaybeUndefined::Some(resource_conforms_to), .. } = &commitment {
+        let e = create_index!(commitment.resource_conforms_to(resource_conforms_to), resource_specification.commitments(&base_address));
+        hdk::prelude::debug!("handle_create_commitment::resource_conforms_to index {:?}", e);
+    };
     // // TODO: because commitment.in_scope_of is a vec of ids rather than one id, make sure this is still handled properly
     // if let CreateRequest { in_scope_of: MaybeUndefined::Some(in_scope_of), .. } = &commitment {
     //     let e = create_index!(commitment.in_scope_of(in_scope_of), agent.commitments(&base_address));
@@ -143,6 +155,39 @@ pub fn handle_update_commitment(commitment: UpdateRequest) -> RecordAPIResult<Re
         );
         hdk::prelude::debug!("handle_update_commitment::planned_within index {:?}", e);
     }
+    if new_entry.in_scenario != prev_entry.in_scenario {
+        let new_value = match &new_entry.in_scenario { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.in_scenario { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            commitment
+                .in_scenario(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            scenario.commitments(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_commitment::in_scenario index {:?}", e);
+    }
+    if new_entry.at_location != prev_entry.at_location {
+        let new_value = match &new_entry.at_location { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.at_location { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            commitment
+                .at_location(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            location.commitments(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_commitment::at_location index {:?}", e);
+    }
+    if new_entry.resource_conforms_to != prev_entry.resource_conforms_to {
+        let new_value = match &new_entry.resource_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.resource_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            commitment
+                .resource_conforms_to(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            resource_specification.commitments(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_commitment::resource_conforms_to index {:?}", e);
+    }
     // // TODO: ensure handling of vec of ids
     // if new_entry.in_scope_of != prev_entry.in_scope_of {
     //     let new_value = match &new_entry.in_scope_of { Some(val) => vec![val.to_owned()], None => vec![] };
@@ -203,6 +248,18 @@ pub fn handle_delete_commitment(revision_id: ActionHash) -> RecordAPIResult<bool
         let e = update_index!(commitment.independent_demand_of.not(&vec![plan_address]), plan.independent_demands(&base_address));
         hdk::prelude::debug!("handle_delete_commitment::independent_demand_of index {:?}", e);
     }
+    if let Some(scenario_address) = entry.in_scenario {
+        let e = update_index!(commitment.in_scenario.not(&vec![scenario_address]), scenario.commitments(&base_address));
+        hdk::prelude::debug!("handle_delete_commitment::in_scenario index {:?}", e);
+    }
+    if let Some(location_address) = entry.at_location {
+        let e = update_index!(commitment.at_location.not(&vec![location_address]), location.commitments(&base_address));
+        hdk::prelude::debug!("handle_delete_commitment::at_location index {:?}", e);
+    }
+    if let Some(resource_specification_address) = entry.resource_conforms_to {
+        let e = update_index!(commitment.resource_conforms_to.not(&vec![resource_specification_address]), resource_specification.commitments(&base_address));
+        hdk::prelude::debug!("handle_delete_commitment::resource_conforms_to index {:?}", e);
+    }
     // if let Some(agent_address) = entry.in_scope_of {
     //     let e = update_index!(commitment.in_scope_of.not(&vec![agent_address]), agent.commitments(&base_address));
     //     hdk::prelude::debug!("handle_delete_commitment::in_scope_of index {:?}", e);
@@ -255,6 +312,7 @@ fn construct_response<'a>(
             independent_demand_of: e.independent_demand_of.to_owned(),
             finished: e.finished.to_owned(),
             in_scope_of: e.in_scope_of.to_owned(),
+            in_scenario: e.in_scenario.to_owned(),
             fulfilled_by: fulfillments.to_owned(),
             satisfies: satisfactions.to_owned(),
             involved_agents: involved_agents.to_owned(),
@@ -290,6 +348,21 @@ fn read_plan_index_zome(conf: DnaConfigSlice) -> Option<String> {
     conf.commitment.plan_index_zome
 }
 
+/// Properties accessor for zome config
+fn read_scenario_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.commitment.scenario_index_zome
+}
+
+/// Properties accessor for zome config
+fn read_location_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.commitment.location_index_zome
+}
+
+/// Properties accessor for zome config
+fn read_resource_specification_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.commitment.resource_specification_index_zome
+}
+
 // @see construct_response
 fn get_link_fields(commitment: &CommitmentAddress) -> RecordAPIResult<(
     Vec<FulfillmentAddress>,
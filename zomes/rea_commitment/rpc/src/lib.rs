@@ -24,6 +24,7 @@ pub use vf_attributes_hdk::{
     AgreementAddress,
     FulfillmentAddress,
     SatisfactionAddress,
+    ScenarioAddress,
 };
 
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
@@ -71,6 +72,8 @@ pub struct Response {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub in_scope_of: Option<Vec<String>>, // Why is this not considered a link field like those below? Or is this just out of date?
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_scenario: Option<ScenarioAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub agreed_in: Option<ExternalURL>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub clause_of: Option<AgreementAddress>,
@@ -151,6 +154,8 @@ pub struct CreateRequest {
     pub finished: MaybeUndefined<bool>,
     #[serde(default)]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
+    #[serde(default)]
+    pub in_scenario: MaybeUndefined<ScenarioAddress>,
 }
 
 impl<'a> CreateRequest {
@@ -209,6 +214,8 @@ pub struct UpdateRequest {
     pub finished: MaybeUndefined<bool>,
     #[serde(default)]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
+    #[serde(default)]
+    pub in_scenario: MaybeUndefined<ScenarioAddress>,
 }
 
 impl<'a> UpdateRequest {
@@ -233,4 +240,7 @@ pub struct QueryParams {
     pub receiver: Option<AgentAddress>,
     pub independent_demand_of: Option<PlanAddress>,
     pub planned_within: Option<PlanAddress>,
+    pub at_location: Option<LocationAddress>,
+    pub resource_conforms_to: Option<ResourceSpecificationAddress>,
+    pub in_scenario: Option<ScenarioAddress>,
 }
@@ -29,6 +29,7 @@ pub use vf_attributes_hdk::{
     ResourceSpecificationAddress,
     AgreementAddress,
     PlanAddress,
+    ScenarioAddress,
 };
 
 use vf_actions::{ validate_flow_action };
@@ -53,6 +54,9 @@ pub struct CommitmentZomeConfig {
     pub agreement_index_zome: Option<String>,
     pub agent_index_zome: Option<String>,
     pub plan_index_zome: Option<String>,
+    pub scenario_index_zome: Option<String>,
+    pub location_index_zome: Option<String>,
+    pub resource_specification_index_zome: Option<String>,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -80,6 +84,7 @@ pub struct EntryData {
     pub planned_within: Option<PlanAddress>,
     pub finished: bool,
     pub in_scope_of: Option<Vec<String>>, // should this be changed to `Option<Vec<AgentAddress>>`?
+    pub in_scenario: Option<ScenarioAddress>,
     pub note: Option<String>,
     pub _nonce: Bytes,
 }
@@ -169,6 +174,7 @@ impl TryFrom<CreateRequest> for EntryData {
             independent_demand_of: e.independent_demand_of.into(),
             finished: e.finished.to_option().unwrap(),  // :NOTE: unsafe, would crash if not for "default_false" binding via Serde
             in_scope_of: e.in_scope_of.into(),
+            in_scenario: e.in_scenario.into(),
             _nonce: random_bytes(32)?,
         })
     }
@@ -201,6 +207,7 @@ impl Updateable<UpdateRequest> for EntryData {
             independent_demand_of: if e.independent_demand_of == MaybeUndefined::Undefined { self.independent_demand_of.clone() } else { e.independent_demand_of.clone().into() },
             finished: if e.finished == MaybeUndefined::Undefined { self.finished.clone() } else { e.finished.clone().to_option().unwrap() },
             in_scope_of: if e.in_scope_of== MaybeUndefined::Undefined { self.in_scope_of.clone() } else { e.in_scope_of.clone().into() },
+            in_scenario: if e.in_scenario == MaybeUndefined::Undefined { self.in_scenario.clone() } else { e.in_scenario.clone().into() },
             note: if e.note== MaybeUndefined::Undefined { self.note.clone() } else { e.note.clone().into() },
             _nonce: self._nonce.to_owned(),
         })
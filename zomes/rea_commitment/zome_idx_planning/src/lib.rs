@@ -20,5 +20,8 @@ struct Commitment {
     receiver: Local<agent, commitments_as_receiver>,
     independent_demand_of: Local<plan, independent_demands>,
     planned_within: Local<plan, non_process_commitments>,
+    in_scenario: Local<scenario, commitments>,
+    at_location: Local<location, commitments>,
+    resource_conforms_to: Local<resource_specification, commitments>,
     // in_scope_of: Local<agent, commitments>,
 }
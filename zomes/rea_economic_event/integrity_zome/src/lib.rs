@@ -8,7 +8,8 @@
  * @package hREA
  */
 use hdi::prelude::*;
-pub use hc_zome_rea_economic_event_storage::{Identified, EntryStorage, EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_records::validation::{ validate_update_authorship, validate_delete_authorship, validate_record_author };
+pub use hc_zome_rea_economic_event_storage::{Identified, EntryData, EntryStorage, EntryTypes, EntryTypesUnit, LinkTypes, transfer_consent_required};
 
 #[hdk_extern]
 pub fn entry_defs(_: ()) -> ExternResult<EntryDefsCallbackResult> {
@@ -29,12 +30,12 @@ pub fn __num_link_types() -> u8 {
     LinkTypes::len()
 }
 
-/// Macro to generate a default (permissable) validation function for EconomicResource
-/// records in the local zome (local field checks only).
+/// Macro to generate a default validation function for EconomicEvent records in the
+/// local zome (local field and action rule checks only).
 /// This is the minimum validation required by any zome, and regardless of other
 /// validation rules being implemented it is critical that
 /// `record.validate_or_fields()` and `record.validate_action()` be peformed upon
-/// `EntryStorage` creation.
+/// `EntryStorage` creation, and `record.validate_update()` upon update.
 ///
 /// Use this method as a reference, and always call the below logic before any
 /// application-specific validation in custom validation rules.
@@ -46,23 +47,135 @@ macro_rules! declare_economic_event_zome_validation_defaults {
         fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
             match op {
                 Op::StoreRecord { .. } => Ok(ValidateCallbackResult::Valid),
-                Op::StoreEntry(StoreEntry { entry, .. }) => validate_entry(entry),
+                Op::StoreEntry(StoreEntry { action, entry }) => validate_entry(&action.hashed.content, entry),
                 Op::RegisterCreateLink { .. } => Ok(ValidateCallbackResult::Valid),
                 Op::RegisterDeleteLink { .. } => Ok(ValidateCallbackResult::Valid),
-                Op::RegisterUpdate { .. } => Ok(ValidateCallbackResult::Valid),
-                Op::RegisterDelete { .. } => Ok(ValidateCallbackResult::Valid),
+                Op::RegisterUpdate(update) => match validate_update_authorship(&update)? {
+                    ValidateCallbackResult::Valid => validate_update(update.new_entry, update.original_entry),
+                    invalid => Ok(invalid),
+                },
+                Op::RegisterDelete(delete) => match validate_delete_authorship(&delete)? {
+                    ValidateCallbackResult::Valid => validate_delete(delete.original_entry),
+                    invalid => Ok(invalid),
+                },
                 Op::RegisterAgentActivity { .. } => Ok(ValidateCallbackResult::Valid),
             }
         }
 
-        fn validate_entry(entry: Entry) -> ExternResult<ValidateCallbackResult> {
+        // :NOTE: events which have been corrected by others are only guarded against deletion by the
+        // coordinator zome, since the `corrected_by` index is not visible to validation
+        fn validate_delete(original_entry: Entry) -> ExternResult<ValidateCallbackResult> {
+            match EntryStorage::try_from(&original_entry) {
+                Ok(original_storage) => original_storage.entry().validate_delete()
+                    .and_then(|()| { Ok(ValidateCallbackResult::Valid) })
+                    .or_else(|e| { Ok(ValidateCallbackResult::Invalid(e)) }),
+                _ => Ok(ValidateCallbackResult::Valid),
+            }
+        }
+
+        fn validate_entry(action: &EntryCreationAction, entry: Entry) -> ExternResult<ValidateCallbackResult> {
+            let author = action.author();
+            let signers: Vec<AgentPubKey> = match &entry {
+                Entry::CounterSign(session, _) => session.preflight_request().signing_agents().iter()
+                    .map(|(agent, _roles)| agent.to_owned())
+                    .collect(),
+                _ => vec![],
+            };
             match EntryStorage::try_from(&entry) {
                 Ok(event_storage) => {
                     let record = event_storage.entry();
-                    record.validate_or_fields()
-                        .and_then(|()| { record.validate_action() })
-                        .and_then(|()| { Ok(ValidateCallbackResult::Valid) })
-                        .or_else(|e| { Ok(ValidateCallbackResult::Invalid(e)) })
+                    // revisions retain the flag of the original entry, which `validate_update` ensures is unchanged
+                    if let EntryCreationAction::Create(_) = action {
+                        if record.countersigned != !signers.is_empty() {
+                            return Ok(ValidateCallbackResult::Invalid("EconomicEvents are flagged as countersigned only when written as countersigned entries".into()));
+                        }
+                    }
+                    match record.validate_or_fields().and_then(|()| { record.validate_action() }) {
+                        Ok(()) => match validate_correction(author, &record)? {
+                            ValidateCallbackResult::Valid => match validate_reversal(&record)? {
+                                ValidateCallbackResult::Valid => validate_transfer_consent(author, &signers, &record),
+                                invalid => Ok(invalid),
+                            },
+                            invalid => Ok(invalid),
+                        },
+                        Err(e) => Ok(ValidateCallbackResult::Invalid(e)),
+                    }
+                },
+                _ => Ok(ValidateCallbackResult::Valid),
+            }
+        }
+
+        // where `require_transfer_consent` is enabled, transfers from inventoried resources must be authored or
+        // countersigned by the agent who created the providing resource, or be authored by a record administrator.
+        // :NOTE: validation cannot resolve signing keys to the resource's `primary_accountable` agent, as agent records
+        // reside in another DNA; the coordinator zome additionally checks consent at the level of `Agent` records.
+        fn validate_transfer_consent(author: &AgentPubKey, signers: &[AgentPubKey], record: &EntryData) -> ExternResult<ValidateCallbackResult> {
+            if !record.is_inventory_transfer() || !transfer_consent_required()? {
+                return Ok(ValidateCallbackResult::Valid);
+            }
+            let (resource, inventoried_action) = match (&record.resource_inventoried_as, &record.inventoried_action) {
+                (Some(resource), Some(inventoried_action)) => (resource, inventoried_action),
+                _ => return Ok(ValidateCallbackResult::Invalid("EconomicEvent transfers must reference the creation of the resource they transfer from".into())),
+            };
+            let resource_record = must_get_valid_record(inventoried_action.to_owned())?;
+            match resource_record.action() {
+                Action::Create(create) if create.entry_hash == *resource.as_ref() => {
+                    if signers.contains(&create.author) {
+                        return Ok(ValidateCallbackResult::Valid);
+                    }
+                    match validate_record_author(author, &create.author)? {
+                        ValidateCallbackResult::Valid => Ok(ValidateCallbackResult::Valid),
+                        _ => Ok(ValidateCallbackResult::Invalid("Transfers from inventoried EconomicResources require the consent of the agent who created them".into())),
+                    }
+                },
+                _ => Ok(ValidateCallbackResult::Invalid("EconomicEvent transfers must reference the creation of the resource they transfer from".into())),
+            }
+        }
+
+        // corrections must be authored by the author of the event they correct, or a record administrator
+        fn validate_correction(author: &AgentPubKey, record: &EntryData) -> ExternResult<ValidateCallbackResult> {
+            let (corrects, corrected_action) = match (&record.corrects, &record.corrected_action) {
+                (None, _) => return Ok(ValidateCallbackResult::Valid),
+                (Some(corrects), Some(corrected_action)) => (corrects, corrected_action),
+                (Some(_), None) => return Ok(ValidateCallbackResult::Invalid("EconomicEvent corrections must reference the creation of the event they correct".into())),
+            };
+            let original = must_get_valid_record(corrected_action.to_owned())?;
+            match original.action() {
+                Action::Create(create) if create.entry_hash == *corrects.as_ref() => match validate_record_author(author, &create.author)? {
+                    ValidateCallbackResult::Valid => Ok(ValidateCallbackResult::Valid),
+                    _ => Ok(ValidateCallbackResult::Invalid("EconomicEvents may only be corrected by their original author or a record administrator".into())),
+                },
+                _ => Ok(ValidateCallbackResult::Invalid("EconomicEvent corrections must reference the creation of the event they correct".into())),
+            }
+        }
+
+        // reversals are exempted from inventory guards, and so must exactly negate the event they correct
+        fn validate_reversal(record: &EntryData) -> ExternResult<ValidateCallbackResult> {
+            let corrected_action = match (record.reversal, &record.corrected_action) {
+                (false, _) => return Ok(ValidateCallbackResult::Valid),
+                (true, Some(corrected_action)) => corrected_action,
+                (true, None) => return Ok(ValidateCallbackResult::Invalid("EconomicEvent reversals must reference the creation of the event they correct".into())),
+            };
+            let original = must_get_valid_record(corrected_action.to_owned())?;
+            match original.entry().as_option().map(EntryStorage::try_from) {
+                Some(Ok(original_storage)) if record.is_reversal_of(&original_storage.entry()) => Ok(ValidateCallbackResult::Valid),
+                _ => Ok(ValidateCallbackResult::Invalid("EconomicEvent reversals must exactly negate the quantities of the event they correct".into())),
+            }
+        }
+
+        fn validate_update(new_entry: Option<Entry>, original_entry: Option<Entry>) -> ExternResult<ValidateCallbackResult> {
+            match (new_entry, original_entry) {
+                (Some(new_entry), Some(original_entry)) => match (EntryStorage::try_from(&new_entry), EntryStorage::try_from(&original_entry)) {
+                    (Ok(new_storage), Ok(original_storage)) => {
+                        let record = new_storage.entry();
+                        record.validate_or_fields()
+                            .and_then(|()| { record.validate_action() })
+                            .and_then(|()| { record.validate_update(&original_storage.entry()) })
+                            .and_then(|()| { Ok(ValidateCallbackResult::Valid) })
+                            .or_else(|e| { Ok(ValidateCallbackResult::Invalid(e)) })
+                    },
+                    (Ok(_), Err(_)) => Ok(ValidateCallbackResult::Invalid("EconomicEvent can only update a previous EconomicEvent".into())),
+                    _ => Ok(ValidateCallbackResult::Valid),
                 },
                 _ => Ok(ValidateCallbackResult::Valid),
             }
@@ -6,9 +6,15 @@
  *
  * @package hREA
  */
+use std::collections::{HashSet, VecDeque};
+use std::convert::TryInto;
 use paste::paste;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use hdk::prelude::{AgentPubKey, PreflightRequest, PreflightResponse, sys_time, create_link, get_links};
 use hdk_records::{
-    RecordAPIResult, OtherCellResult, MaybeUndefined, SignedActionHashed,
+    RecordAPIResult, OtherCellResult, DataIntegrityError, MaybeUndefined, SignedActionHashed,
+    DnaAddressable,
+    agent_info, dna_info,
     rpc::{
         call_local_zome_method,
     },
@@ -16,12 +22,25 @@ use hdk_records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
+        get_creating_action,
         update_record,
         delete_record,
     },
+    identities::create_entry_identity,
+    countersigning::{
+        build_countersigning_request,
+        accept_countersigning_request,
+        read_countersigning_request_entry,
+        read_countersigning_agents,
+        create_countersigned_entry,
+    },
+    record_interface::Identifiable,
     metadata::read_revision_metadata_abbreviated,
+    validation::get_record_administrators,
 };
 use hdk_semantic_indexes_client_lib::*;
+use vf_measurement::negate;
+use vf_actions::{ActionEffect, ProcessType, get_builtin_action, is_builtin_action};
 
 pub use hc_zome_rea_economic_event_storage_consts::*;
 
@@ -39,8 +58,13 @@ use hc_zome_rea_economic_resource_storage::{
 use hc_zome_rea_economic_resource_lib::{
     construct_response_record as construct_resource_response,
     get_link_fields as get_resource_link_fields,
+    read_my_agent,
+    read_agent_whois,
 };
 
+/// Time allowed for all parties to commit a countersigned event, once its session has been proposed
+const COUNTERSIGNING_SESSION_MILLIS: u64 = 60_000;
+
 
 
 
@@ -59,6 +83,11 @@ fn read_agent_index_zome(conf: DnaConfigSlice) -> Option<String> {
     conf.economic_event.agent_index_zome
 }
 
+/// Properties accessor for zome config.
+fn read_location_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.economic_event.location_index_zome
+}
+
 /// Trait object defining the default ValueFlows EconomicResource zome API.
 /// 'Permissable' denotes the interface as a highly-permissable one, where little
 /// validation on entry contents is performed.
@@ -71,51 +100,8 @@ impl API for EconomicEventZomePermissableDefault {
         entry_def_id: Self::S,
         event: EconomicEventCreateRequest, new_inventoried_resource: Option<ResourceCreateRequest>
     ) -> RecordAPIResult<ResponseData> {
-        let mut resources_affected: Vec<(SignedActionHashed, EconomicResourceAddress, EconomicResourceData, EconomicResourceData)> = vec![];
-        let mut resource_created: Option<(SignedActionHashed, EconomicResourceAddress, EconomicResourceData)> = None;
-
-        // if the event observes a new resource, create that resource & return it in the response
-        if let Some(economic_resource) = new_inventoried_resource {
-            let new_resource = handle_create_inventory_from_event(
-                &economic_resource, &event,
-            )?;
-            resource_created = Some(new_resource.clone());
-            resources_affected.push((new_resource.0, new_resource.1, new_resource.2.clone(), new_resource.2));
-        }
-
-        // update any linked resources affected by the event
-        resources_affected.append(&mut handle_update_resource_inventory(&event)?);
-
-        // Now that the resource updates have succeeded, write the event.
-        // Note we ignore the revision ID because events can't be edited (only underwritten by subsequent events)
-        // :TODO: rethinking this, it's probably the event that should be written first, and the resource
-        // validation should eventually depend on an event already having been authored.
-        let (meta, event_address, event_entry) = handle_create_economic_event_record(
-            &entry_def_id,
-            &event, match &resource_created {
-                Some(data) => Some(data.1.to_owned()),
-                None => None,
-            },
-        )?;
-
-        // Link any affected resources to this event so that we can pull all the events which affect any resource
-        for resource_data in resources_affected.iter() {
-            let e = create_index!(economic_event.affects(resource_data.1), economic_resource.affected_by(&event_address));
-            hdk::prelude::debug!("create_economic_event::affects index {:?}", e);
-        }
-
-        match resource_created {
-            Some((resource_meta, resource_addr, resource_entry)) => {
-                construct_response_with_resource(
-                    &event_address, &meta, &event_entry, get_link_fields(&event_address)?,
-                    Some(resource_addr.clone()), &resource_meta, resource_entry, get_resource_link_fields(&resource_addr)?
-                )
-            },
-            None => {
-                // :TODO: pass results from link creation rather than re-reading
-                construct_response(&event_address, &meta, &event_entry, get_link_fields(&event_address)?)
-            },
-        }
+        // reversals may only be written by `correct_economic_event`
+        handle_create_economic_event(entry_def_id, EconomicEventCreateRequest { reversal: false, ..event }, new_inventoried_resource)
     }
 
     fn get_economic_event(address: EconomicEventAddress) -> RecordAPIResult<ResponseData> {
@@ -141,6 +127,15 @@ impl API for EconomicEventZomePermissableDefault {
         // read any referencing indexes
         let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
+        // events which have altered inventories or participate in corrections form part of the
+        // ledger, and can only be amended by appending further events
+        // (also enforced by validation, excepting `corrected_by` which is only known to the index)
+        entry.validate_delete().map_err(DataIntegrityError::EventHistoryImmutable)?;
+        let corrected_by: Vec<EconomicEventAddress> = read_index!(economic_event(&base_address).corrected_by)?;
+        if !corrected_by.is_empty() {
+            return Err(DataIntegrityError::EventHistoryImmutable("event is part of a correction and cannot be deleted".to_string()));
+        }
+
         // handle link fields
         if let Some(process_address) = entry.input_of {
             let e = update_index!(economic_event.input_of.not(&vec![process_address.to_owned()]), process.observed_inputs(&base_address));
@@ -158,6 +153,10 @@ impl API for EconomicEventZomePermissableDefault {
         hdk::prelude::debug!("delete_economic_event::provider index {:?}", e);
         let e = update_index!(economic_event.receiver.not(&vec![entry.receiver]), agent.economic_events_as_receiver(&base_address));
         hdk::prelude::debug!("delete_economic_event::receiver index {:?}", e);
+        if let Some(location_address) = entry.at_location {
+            let e = update_index!(economic_event.at_location.not(&vec![location_address.to_owned()]), location.economic_events(&base_address));
+            hdk::prelude::debug!("delete_economic_event::at_location index {:?}", e);
+        }
 
         // :TODO: handle cleanup of foreign key fields? (fulfillment, satisfaction)
         // May not be needed due to cross-record deletion validation logic.
@@ -165,6 +164,128 @@ impl API for EconomicEventZomePermissableDefault {
         // delete entry last as it must be present in order for links to be removed
         delete_record::<EntryStorage>(&revision_id)
     }
+
+    fn correct_economic_event(
+        entry_def_id: Self::S,
+        event: EconomicEventAddress, replacement: Option<EconomicEventCreateRequest>
+    ) -> RecordAPIResult<CorrectionResponseData> {
+        let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(event.as_ref())?;
+
+        // only the original author may correct an event (also enforced by validation)
+        let original_action = get_creating_action(base_address.as_ref())?;
+        let my_pub_key = agent_info()?.agent_latest_pubkey;
+        if *original_action.action().author() != my_pub_key && !get_record_administrators()?.contains(&my_pub_key) {
+            return Err(DataIntegrityError::CorrectionNotPermitted(format!("{:?}", base_address)));
+        }
+
+        let corrected_by: Vec<EconomicEventAddress> = read_index!(economic_event(&base_address).corrected_by)?;
+        if !corrected_by.is_empty() {
+            return Err(DataIntegrityError::EventHistoryImmutable(format!("event has already been corrected by {:?}", corrected_by)));
+        }
+        // :TODO: restore resource location & accountability changes, to allow correcting these events
+        if alters_resource_state(&entry) {
+            return Err(DataIntegrityError::UncorrectableEvent(entry.action.as_ref().to_string()));
+        }
+
+        // reverse the original event's effects on any linked resources first, so that the
+        // replacement is applied against the inventory state prior to the original observation
+        let now = sys_time()?.as_seconds_and_nanos();
+        let now_stamp = NaiveDateTime::from_timestamp_opt(now.0, now.1)
+            .map(|t| DateTime::<Utc>::from_utc(t, Utc))
+            .ok_or(DataIntegrityError::InvalidTimestamp(format!("{}s {}ns", now.0, now.1)))?;
+        let reversal = handle_create_economic_event(
+            entry_def_id, reversal_of(&base_address, original_action.as_hash(), &entry, now_stamp.into()), None,
+        )?;
+
+        let replacement = match replacement {
+            Some(replacement_event) => Some(Self::create_economic_event(
+                entry_def_id, replacement_event.with_corrects(&base_address).with_corrected_action(original_action.as_hash()), None,
+            )?),
+            None => None,
+        };
+
+        Ok(CorrectionResponseData { reversal, replacement })
+    }
+
+    fn trace(params: TrackTraceParams) -> RecordAPIResult<FlowGraph> {
+        handle_walk_flows(params, FlowDirection::Backwards)
+    }
+
+    fn track(params: TrackTraceParams) -> RecordAPIResult<FlowGraph> {
+        handle_walk_flows(params, FlowDirection::Forwards)
+    }
+
+    fn propose_countersigned_economic_event(
+        event: EconomicEventCreateRequest, counterparty: AgentPubKey,
+    ) -> RecordAPIResult<CountersigningProposal> {
+        let entry = EntryData {
+            countersigned: true,
+            reversal: false,
+            ..with_validation_references(event)?.try_into()?
+        };
+        let signing_agents = vec![agent_info()?.agent_latest_pubkey, counterparty];
+        check_countersigning_parties(&entry, &signing_agents)?;
+
+        let request = build_countersigning_request::<EntryTypes,_,_>(
+            entry.with_identity(None), signing_agents, COUNTERSIGNING_SESSION_MILLIS,
+        )?;
+        let response = accept_countersigning_request(request.to_owned())?;
+
+        Ok(CountersigningProposal { request, response })
+    }
+
+    fn accept_countersigned_economic_event(request: PreflightRequest) -> RecordAPIResult<PreflightResponse> {
+        let storage: EntryStorage = read_countersigning_request_entry(&request)?;
+        let signing_agents: Vec<AgentPubKey> = request.signing_agents().iter()
+            .map(|(agent, _roles)| agent.to_owned())
+            .collect();
+        check_countersigning_parties(&storage.entry(), &signing_agents)?;
+
+        accept_countersigning_request(request)
+    }
+
+    fn commit_countersigned_economic_event(responses: Vec<PreflightResponse>) -> RecordAPIResult<ResponseData> {
+        let request = match responses.first() {
+            Some(response) => response.request().to_owned(),
+            None => return Err(DataIntegrityError::CountersigningError("no session responses provided".to_string())),
+        };
+        let storage: EntryStorage = read_countersigning_request_entry(&request)?;
+
+        // No other writes are permitted alongside a countersigned entry, so indexing
+        // and inventory updates are deferred to `apply_countersigned_economic_event`.
+        let (meta, entry_hash) = create_countersigned_entry::<EntryTypes,_,_>(storage.to_owned(), responses)?;
+        let base_address = EconomicEventAddress::new(dna_info()?.hash, entry_hash);
+
+        construct_response(&base_address, &meta, &storage.entry(), get_link_fields(&base_address)?)
+    }
+
+    fn apply_countersigned_economic_event(entry_def_id: Self::S, revision_id: ActionHash) -> RecordAPIResult<ResponseData> {
+        let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+        // only the proposing agent applies the event, so that its effects are not duplicated by each signing party
+        let my_pub_key = agent_info()?.agent_latest_pubkey;
+        match read_countersigning_agents(&revision_id)?.first() {
+            Some(proposer) if *proposer == my_pub_key && *meta.action().author() == my_pub_key => (),
+            _ => return Err(DataIntegrityError::CountersigningError("countersigned events can only be applied by their proposing agent".to_string())),
+        };
+
+        if !get_links(revision_id.to_owned(), LinkTypes::CountersignedEventApplied, None)?.is_empty() {
+            return Err(DataIntegrityError::CountersigningError("countersigned event has already been applied".to_string()));
+        }
+        create_link(revision_id.to_owned(), base_address.as_ref().to_owned(), LinkTypes::CountersignedEventApplied, ())?;
+
+        let event = EconomicEventCreateRequest::from(entry.to_owned()).with_countersigned_action(&revision_id);
+        let resources_affected = handle_update_resource_inventory(&event)?;
+
+        create_entry_identity(read_index_zome, &entry_def_id, &base_address)?;
+        handle_create_economic_event_indexes(&base_address, &event)?;
+        for resource_data in resources_affected.iter() {
+            let e = create_index!(economic_event.affects(resource_data.1), economic_resource.affected_by(&base_address));
+            hdk::prelude::debug!("apply_countersigned_economic_event::affects index {:?}", e);
+        }
+
+        construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+    }
 }
 
 // API logic handlers
@@ -185,6 +306,60 @@ fn read_agreement_index_zome(conf: DnaConfigSlice) -> Option<String> {
     conf.economic_event.agreement_index_zome
 }
 
+fn handle_create_economic_event<S>(
+    entry_def_id: S,
+    event: EconomicEventCreateRequest, new_inventoried_resource: Option<ResourceCreateRequest>
+) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    let event = with_validation_references(event)?;
+    let mut resources_affected: Vec<(SignedActionHashed, EconomicResourceAddress, EconomicResourceData, EconomicResourceData)> = vec![];
+    let mut resource_created: Option<(SignedActionHashed, EconomicResourceAddress, EconomicResourceData)> = None;
+
+    // if the event observes a new resource, create that resource & return it in the response
+    if let Some(economic_resource) = new_inventoried_resource {
+        let new_resource = handle_create_inventory_from_event(
+            &economic_resource, &event,
+        )?;
+        resource_created = Some(new_resource.clone());
+        resources_affected.push((new_resource.0, new_resource.1, new_resource.2.clone(), new_resource.2));
+    }
+
+    // update any linked resources affected by the event
+    resources_affected.append(&mut handle_update_resource_inventory(&event)?);
+
+    // Now that the resource updates have succeeded, write the event.
+    // Note we ignore the revision ID because events can't be edited (only underwritten by subsequent events)
+    // :TODO: rethinking this, it's probably the event that should be written first, and the resource
+    // validation should eventually depend on an event already having been authored.
+    let (meta, event_address, event_entry) = handle_create_economic_event_record(
+        &entry_def_id,
+        &event, match &resource_created {
+            Some(data) => Some(data.1.to_owned()),
+            None => None,
+        },
+    )?;
+
+    // Link any affected resources to this event so that we can pull all the events which affect any resource
+    for resource_data in resources_affected.iter() {
+        let e = create_index!(economic_event.affects(resource_data.1), economic_resource.affected_by(&event_address));
+        hdk::prelude::debug!("create_economic_event::affects index {:?}", e);
+    }
+
+    match resource_created {
+        Some((resource_meta, resource_addr, resource_entry)) => {
+            construct_response_with_resource(
+                &event_address, &meta, &event_entry, get_link_fields(&event_address)?,
+                Some(resource_addr.clone()), &resource_meta, resource_entry, get_resource_link_fields(&resource_addr)?
+            )
+        },
+        None => {
+            // :TODO: pass results from link creation rather than re-reading
+            construct_response(&event_address, &meta, &event_entry, get_link_fields(&event_address)?)
+        },
+    }
+}
+
 fn handle_create_economic_event_record<S>(entry_def_id: S, event: &EconomicEventCreateRequest, resource_address: Option<EconomicResourceAddress>,
 ) -> RecordAPIResult<(SignedActionHashed, EconomicEventAddress, EntryData)>
     where S: AsRef<str> + std::fmt::Display,
@@ -193,32 +368,97 @@ fn handle_create_economic_event_record<S>(entry_def_id: S, event: &EconomicEvent
         read_index_zome,
         &entry_def_id,
         match resource_address {
-            Some(addr) => event.with_inventoried_resource(&addr),
+            Some(addr) => with_validation_references(event.with_inventoried_resource(&addr))?,
             None => event.to_owned(),
         }
     )?;
 
+    handle_create_economic_event_indexes(&base_address, event)?;
+
+    Ok((meta, base_address, entry_resp))
+}
+
+fn handle_create_economic_event_indexes(base_address: &EconomicEventAddress, event: &EconomicEventCreateRequest) -> RecordAPIResult<()> {
     // handle link fields
     // :TODO: handle errors better https://github.com/h-REA/hREA/issues/264
-    let e1 = create_index!(economic_event.provider(event.provider), agent.economic_events_as_provider(&base_address))?;
-    let e2 = create_index!(economic_event.receiver(event.receiver), agent.economic_events_as_receiver(&base_address))?;
+    let e1 = create_index!(economic_event.provider(event.provider), agent.economic_events_as_provider(base_address))?;
+    let e2 = create_index!(economic_event.receiver(event.receiver), agent.economic_events_as_receiver(base_address))?;
     hdk::prelude::debug!("handle_create_economic_event::provider index {:?}", e1);
     hdk::prelude::debug!("handle_create_economic_event::receiver index {:?}", e2);
 
     if let EconomicEventCreateRequest { input_of: MaybeUndefined::Some(input_of), .. } = event {
-        let e = create_index!(economic_event.input_of(input_of), process.observed_inputs(&base_address));
+        let e = create_index!(economic_event.input_of(input_of), process.observed_inputs(base_address));
         hdk::prelude::debug!("handle_create_economic_event_record::input_of index {:?}", e);
       };
       if let EconomicEventCreateRequest { output_of: MaybeUndefined::Some(output_of), .. } = event {
-        let e = create_index!(economic_event.output_of(output_of), process.observed_outputs(&base_address));
+        let e = create_index!(economic_event.output_of(output_of), process.observed_outputs(base_address));
         hdk::prelude::debug!("handle_create_economic_event_record::output_of index {:?}", e);
       };
       if let EconomicEventCreateRequest { realization_of: MaybeUndefined::Some(realization_of), .. } = event {
-        let e = create_index!(economic_event.realization_of(realization_of), agreement.economic_events(&base_address));
+        let e = create_index!(economic_event.realization_of(realization_of), agreement.economic_events(base_address));
         hdk::prelude::debug!("handle_create_economic_event_record::realization_of index {:?}", e);
     };
+    if let EconomicEventCreateRequest { at_location: MaybeUndefined::Some(at_location), .. } = event {
+        let e = create_index!(economic_event.at_location(at_location), location.economic_events(base_address));
+        hdk::prelude::debug!("handle_create_economic_event_record::at_location index {:?}", e);
+    };
+    if let EconomicEventCreateRequest { corrects: MaybeUndefined::Some(corrects), .. } = event {
+        let e = create_index!(economic_event.corrects(corrects), economic_event.corrected_by(base_address));
+        hdk::prelude::debug!("handle_create_economic_event_record::corrects index {:?}", e);
+    };
 
-    Ok((meta, base_address, entry_resp))
+    Ok(())
+}
+
+/// Reference the creation of any event corrected by `event` and of any resource it transfers from,
+/// so that validation can check the authorship of corrections and consent to transfers.
+fn with_validation_references(event: EconomicEventCreateRequest) -> RecordAPIResult<EconomicEventCreateRequest> {
+    let event = match (&event.corrects, &event.corrected_action) {
+        (MaybeUndefined::Some(corrects), None) => {
+            let original_action = get_creating_action(corrects.as_ref())?;
+            event.with_corrected_action(original_action.as_hash())
+        },
+        _ => event,
+    };
+    match (&event.resource_inventoried_as, &event.inventoried_action) {
+        (MaybeUndefined::Some(resource), None) if is_builtin_action(event.get_action(), &["transfer", "transfer-all-rights", "transfer-custody"]) => {
+            let resource_action = get_creating_action(resource.as_ref())?;
+            Ok(event.with_inventoried_action(resource_action.as_hash()))
+        },
+        _ => Ok(event),
+    }
+}
+
+/// Determines whether `event` changed the location or primary accountable agent of an inventoried resource,
+/// which reversals are unable to restore.
+fn alters_resource_state(event: &EntryData) -> bool {
+    if event.resource_inventoried_as.is_none() && event.to_resource_inventoried_as.is_none() {
+        return false;
+    }
+    is_builtin_action(event.action.as_ref(), &["move", "transfer", "transfer-all-rights"])
+}
+
+/// Build an event which negates the effects of `original` on any inventoried resources.
+///
+/// Quantities are inverted rather than the action being changed, so that `update_quantity`
+/// applies the same inventory effects in reverse and the ledger nets to zero.
+/// The reversal is observed at the time of correction, not at the time of the original event.
+///
+fn reversal_of(
+    original_address: &EconomicEventAddress, original_action: &ActionHash, original: &EntryData, corrected_at: DateTime<FixedOffset>,
+) -> EconomicEventCreateRequest {
+    EconomicEventCreateRequest {
+        note: MaybeUndefined::Some("Reversal of corrected economic event".to_string()),
+        resource_quantity: original.resource_quantity.to_owned().map(negate).into(),
+        effort_quantity: original.effort_quantity.to_owned().map(negate).into(),
+        has_point_in_time: MaybeUndefined::Some(corrected_at),
+        has_beginning: MaybeUndefined::Undefined,
+        has_end: MaybeUndefined::Undefined,
+        corrects: MaybeUndefined::Some(original_address.to_owned()),
+        corrected_action: Some(original_action.to_owned()),
+        reversal: true,
+        ..EconomicEventCreateRequest::from(original.to_owned())
+    }
 }
 
 /// Properties accessor for zome config.
@@ -273,9 +513,19 @@ pub fn construct_response_with_resource<'a>(
     event: &EntryData, (
         fulfillments,
         satisfactions,
+        claims,
+        settlements,
+        appreciations_received,
+        appreciations_given,
+        corrections,
     ): (
         Vec<FulfillmentAddress>,
         Vec<SatisfactionAddress>,
+        Vec<ClaimAddress>,
+        Vec<SettlementAddress>,
+        Vec<AppreciationAddress>,
+        Vec<AppreciationAddress>,
+        Vec<EconomicEventAddress>,
     ),
     resource_address: Option<EconomicResourceAddress>,
     resource_meta: &SignedActionHashed,
@@ -316,8 +566,15 @@ pub fn construct_response_with_resource<'a>(
             triggered_by: event.triggered_by.to_owned(),
             realization_of: event.realization_of.to_owned(),
             in_scope_of: event.in_scope_of.to_owned(),
+            corrects: event.corrects.to_owned(),
+            countersigned: event.countersigned,
             fulfills: fulfillments.to_owned(),
             satisfies: satisfactions.to_owned(),
+            triggers: claims.to_owned(),
+            settles: settlements.to_owned(),
+            appreciated_by: appreciations_received.to_owned(),
+            appreciation: appreciations_given.to_owned(),
+            corrected_by: corrections.to_owned(),
         },
         economic_resource: match resource_address {
             Some(addr) => Some(construct_resource_response(&addr, &resource_meta, &resource, (contained_in, stage, state, contains))?),
@@ -331,9 +588,19 @@ pub fn construct_response<'a>(
     address: &EconomicEventAddress, meta: &SignedActionHashed, e: &EntryData, (
         fulfillments,
         satisfactions,
+        claims,
+        settlements,
+        appreciations_received,
+        appreciations_given,
+        corrections,
     ): (
         Vec<FulfillmentAddress>,
         Vec<SatisfactionAddress>,
+        Vec<ClaimAddress>,
+        Vec<SettlementAddress>,
+        Vec<AppreciationAddress>,
+        Vec<AppreciationAddress>,
+        Vec<EconomicEventAddress>,
     )
 ) -> RecordAPIResult<ResponseData> {
     Ok(ResponseData {
@@ -361,8 +628,15 @@ pub fn construct_response<'a>(
             triggered_by: e.triggered_by.to_owned(),
             realization_of: e.realization_of.to_owned(),
             in_scope_of: e.in_scope_of.to_owned(),
+            corrects: e.corrects.to_owned(),
+            countersigned: e.countersigned,
             fulfills: fulfillments.to_owned(),
             satisfies: satisfactions.to_owned(),
+            triggers: claims.to_owned(),
+            settles: settlements.to_owned(),
+            appreciated_by: appreciations_received.to_owned(),
+            appreciation: appreciations_given.to_owned(),
+            corrected_by: corrections.to_owned(),
         },
         economic_resource: None,
     })
@@ -372,13 +646,163 @@ pub fn construct_response<'a>(
 pub fn get_link_fields(event: &EconomicEventAddress) -> RecordAPIResult<(
     Vec<FulfillmentAddress>,
     Vec<SatisfactionAddress>,
+    Vec<ClaimAddress>,
+    Vec<SettlementAddress>,
+    Vec<AppreciationAddress>,
+    Vec<AppreciationAddress>,
+    Vec<EconomicEventAddress>,
 )> {
     Ok((
         read_index!(economic_event(event).fulfills)?,
         read_index!(economic_event(event).satisfies)?,
+        read_index!(economic_event(event).triggers)?,
+        read_index!(economic_event(event).settles)?,
+        read_index!(economic_event(event).appreciated_by)?,
+        read_index!(economic_event(event).appreciation)?,
+        read_index!(economic_event(event).corrected_by)?,
     ))
 }
 
+// Countersigning
+
+/// Ensure a countersigning session is between the provider and receiver of `event`, one of whom
+/// is the current agent. Signing agents are resolved to their `Agent` records via the agent DNA
+/// which the event's provider belongs to.
+///
+fn check_countersigning_parties(event: &EntryData, signing_agents: &[AgentPubKey]) -> RecordAPIResult<()> {
+    event.validate_or_fields()
+        .and_then(|()| event.validate_action())
+        .map_err(DataIntegrityError::CountersigningError)?;
+
+    let my_pub_key = agent_info()?.agent_latest_pubkey;
+    let counterparty = match signing_agents {
+        [first, second] if *first == my_pub_key => second,
+        [first, second] if *second == my_pub_key => first,
+        _ => return Err(DataIntegrityError::CountersigningError("session must be between the current agent and a single counterparty".to_string())),
+    };
+
+    let my_agent = read_my_agent::<EntryTypes, _, _, _>(&event.provider, LinkTypes::AvailableCapability)?;
+    let counterparty_agent = read_agent_whois::<EntryTypes, _, _, _>(&event.provider, counterparty, LinkTypes::AvailableCapability)?;
+    if (event.provider == my_agent && event.receiver == counterparty_agent)
+        || (event.receiver == my_agent && event.provider == counterparty_agent)
+    {
+        return Ok(());
+    }
+    Err(DataIntegrityError::CountersigningError("signing agents must be the provider and receiver of the event".to_string()))
+}
+
+// Track & trace
+
+#[derive(Clone, Copy, PartialEq)]
+enum FlowDirection {
+    Backwards,
+    Forwards,
+}
+
+/// Breadth-first walk of resource -> event -> process -> event -> resource chains,
+/// starting from any record in the flow and stopping after `max_depth` hops.
+///
+/// Each record is expanded at most once, so cyclic flows (eg. resources which are
+/// repaired and returned to inventory) terminate without revisiting nodes.
+///
+fn handle_walk_flows(params: TrackTraceParams, direction: FlowDirection) -> RecordAPIResult<FlowGraph> {
+    let max_depth = params.max_depth.unwrap_or(DEFAULT_TRACK_TRACE_DEPTH).min(MAX_TRACK_TRACE_DEPTH);
+
+    let mut visited: HashSet<TrackTraceItem> = HashSet::new();
+    let mut queue: VecDeque<(TrackTraceItem, u32)> = VecDeque::new();
+    let mut nodes = vec![];
+    let mut edges = vec![];
+
+    visited.insert(params.start.to_owned());
+    queue.push_back((params.start, 0));
+
+    while let Some((item, depth)) = queue.pop_front() {
+        nodes.push(FlowNode { item: item.to_owned(), depth });
+        if depth >= max_depth {
+            continue;
+        }
+
+        for next in read_adjacent_flows(&item, direction)? {
+            edges.push(match direction {
+                FlowDirection::Backwards => FlowEdge { from: next.to_owned(), to: item.to_owned() },
+                FlowDirection::Forwards => FlowEdge { from: item.to_owned(), to: next.to_owned() },
+            });
+            if visited.insert(next.to_owned()) {
+                queue.push_back((next, depth + 1));
+            }
+        }
+    }
+
+    Ok(FlowGraph { nodes, edges })
+}
+
+/// Determine the records one hop away from `item` in the given direction of flow.
+fn read_adjacent_flows(item: &TrackTraceItem, direction: FlowDirection) -> RecordAPIResult<Vec<TrackTraceItem>> {
+    Ok(match item {
+        TrackTraceItem::EconomicResource(resource) => {
+            let events: Vec<EconomicEventAddress> = read_index!(economic_resource(resource).affected_by)?;
+            let mut found = vec![];
+            for event_address in events {
+                let (_meta, _base, event) = read_record_entry::<EntryData, EntryStorage, _>(event_address.as_ref())?;
+                let flows_in = is_resource_inflow(&event, resource);
+                if (direction == FlowDirection::Backwards) == flows_in {
+                    found.push(TrackTraceItem::EconomicEvent(event_address));
+                }
+            }
+            found
+        },
+        TrackTraceItem::EconomicEvent(event_address) => {
+            let (_meta, _base, event) = read_record_entry::<EntryData, EntryStorage, _>(event_address.as_ref())?;
+            let next = match direction {
+                FlowDirection::Backwards => match event.output_of {
+                    Some(process) => Some(TrackTraceItem::Process(process)),
+                    None => event.resource_inventoried_as.map(TrackTraceItem::EconomicResource),
+                },
+                FlowDirection::Forwards => match (event.input_of, event.to_resource_inventoried_as) {
+                    (Some(process), _) => Some(TrackTraceItem::Process(process)),
+                    (None, Some(to_resource)) => Some(TrackTraceItem::EconomicResource(to_resource)),
+                    (None, None) => event.resource_inventoried_as.map(TrackTraceItem::EconomicResource),
+                },
+            };
+            next.into_iter().collect()
+        },
+        TrackTraceItem::Process(process) => {
+            let events: Vec<EconomicEventAddress> = match direction {
+                FlowDirection::Backwards => read_index!(process(process).observed_inputs)?,
+                FlowDirection::Forwards => read_index!(process(process).observed_outputs)?,
+            };
+            events.into_iter().map(TrackTraceItem::EconomicEvent).collect()
+        },
+    })
+}
+
+/// Whether an event affecting `resource` moves value into it (as opposed to out of it).
+///
+/// The destination of a transfer or move is always an inflow. Otherwise the resource effect
+/// of the event's action decides: increments are inflows and decrements are outflows, with the
+/// onhand effect consulted where the action has no accounting effect. Actions which affect
+/// neither (eg. `use`, `cite`, `pass`) follow the flow of the process they are linked to.
+///
+fn is_resource_inflow(event: &EntryData, resource: &EconomicResourceAddress) -> bool {
+    if event.to_resource_inventoried_as.as_ref() == Some(resource) && event.resource_inventoried_as.as_ref() != Some(resource) {
+        return true;
+    }
+
+    let action = match get_builtin_action(event.action.as_ref()) {
+        Some(action) => action,
+        None => return false,
+    };
+    let effect = match action.accounting_effect {
+        ActionEffect::NoEffect => action.onhand_effect,
+        accounting_effect => accounting_effect,
+    };
+    match effect {
+        ActionEffect::Increment => true,
+        ActionEffect::Decrement | ActionEffect::DecrementIncrement => false,
+        ActionEffect::NoEffect => action.input_output == ProcessType::Output,
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use super::*;
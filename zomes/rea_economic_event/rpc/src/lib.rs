@@ -26,6 +26,9 @@ pub use vf_attributes_hdk::{
     CommitmentAddress,
     FulfillmentAddress,
     SatisfactionAddress,
+    ClaimAddress,
+    SettlementAddress,
+    AppreciationAddress,
     AgreementAddress,
     ProductBatchAddress,
     UnitId,
@@ -78,6 +81,11 @@ pub struct Response {
     pub triggered_by: Option<EconomicEventAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub in_scope_of: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub corrects: Option<EconomicEventAddress>,
+    // whether the event was countersigned by both its provider and receiver
+    #[serde(default)]
+    pub countersigned: bool,
 
     // LINK FIELDS
     #[serde(default)]
@@ -86,6 +94,21 @@ pub struct Response {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub satisfies: Vec<SatisfactionAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<ClaimAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub settles: Vec<SettlementAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub appreciated_by: Vec<AppreciationAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub appreciation: Vec<AppreciationAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub corrected_by: Vec<EconomicEventAddress>,
 }
 
 /// I/O struct to describe EconomicResources, including all managed link fields
@@ -237,10 +260,24 @@ pub struct CreateRequest {
     pub triggered_by: MaybeUndefined<EconomicEventAddress>,
     #[serde(default)]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
+    #[serde(default)]
+    pub corrects: MaybeUndefined<EconomicEventAddress>,
 
     // :SHONK: internal field used in updating linked resource quantities
     #[serde(default)]
     pub target_inventory_type: Option<ResourceInventoryType>,
+    // :SHONK: internal field referencing the countersigned record of an event, used in checking transfer consent
+    #[serde(default)]
+    pub countersigned_as: Option<ActionHash>,
+    // :SHONK: internal field referencing the creation of the event being corrected, used in validating correction authorship
+    #[serde(default)]
+    pub corrected_action: Option<ActionHash>,
+    // :SHONK: internal field referencing the creation of the resource being transferred from, used in validating transfer consent
+    #[serde(default)]
+    pub inventoried_action: Option<ActionHash>,
+    // :SHONK: internal field marking the reversal written by `correct_economic_event`, used in exempting it from inventory guards
+    #[serde(default)]
+    pub reversal: bool,
 }
 
 impl<'a> CreateRequest {
@@ -251,6 +288,27 @@ impl<'a> CreateRequest {
         }
     }
 
+    pub fn with_corrects(&self, event_address: &EconomicEventAddress) -> Self {
+        CreateRequest {
+            corrects: MaybeUndefined::Some(event_address.to_owned()),
+            ..self.to_owned()
+        }
+    }
+
+    pub fn with_corrected_action(&self, original_action: &ActionHash) -> Self {
+        CreateRequest {
+            corrected_action: Some(original_action.to_owned()),
+            ..self.to_owned()
+        }
+    }
+
+    pub fn with_inventoried_action(&self, resource_action: &ActionHash) -> Self {
+        CreateRequest {
+            inventoried_action: Some(resource_action.to_owned()),
+            ..self.to_owned()
+        }
+    }
+
     pub fn with_inventory_type(&self, t: ResourceInventoryType) -> Self {
         CreateRequest {
             target_inventory_type: Some(t),
@@ -258,6 +316,13 @@ impl<'a> CreateRequest {
         }
     }
 
+    pub fn with_countersigned_action(&self, revision_id: &ActionHash) -> Self {
+        CreateRequest {
+            countersigned_as: Some(revision_id.to_owned()),
+            ..self.to_owned()
+        }
+    }
+
     // accessors for field data
 
     pub fn get_action(&'a self) -> &str {
@@ -308,6 +373,28 @@ pub struct CreateParams {
     pub new_inventoried_resource: Option<ResourceCreateRequest>,
 }
 
+//---------------- CORRECTION REQUEST ----------------
+
+/// Parameters for correcting a previously observed event. Events are never rewritten-
+/// instead a reversing event negates the original's effects on any inventoried resources,
+/// and an optional replacement event records the corrected observation.
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrectionParams {
+    pub event: EconomicEventAddress,
+    #[serde(default)]
+    pub replacement: Option<CreateRequest>,
+}
+
+/// I/O struct to describe the records written when correcting an event
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrectionResponseData {
+    pub reversal: ResponseData,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<ResponseData>,
+}
+
 //---------------- UPDATE REQUEST ----------------
 
 /// I/O struct to describe the complete input record, including all managed links
@@ -350,8 +437,63 @@ pub struct QueryParams {
     pub output_of: Option<ProcessAddress>,
     pub satisfies: Option<IntentAddress>,
     pub fulfills: Option<CommitmentAddress>,
+    pub triggers: Option<ClaimAddress>,
+    pub settles: Option<SettlementAddress>,
+    pub appreciated_by: Option<AppreciationAddress>,
+    pub appreciation: Option<AppreciationAddress>,
+    pub corrects: Option<EconomicEventAddress>,
+    pub corrected_by: Option<EconomicEventAddress>,
     pub realization_of: Option<AgreementAddress>,
     pub affects: Option<EconomicResourceAddress>,
     pub provider: Option<AgentAddress>,
     pub receiver: Option<AgentAddress>,
+    pub at_location: Option<LocationAddress>,
+}
+
+//---------------- TRACK & TRACE ----------------
+
+/// Default number of hops walked by `track` & `trace` when no depth is requested
+pub const DEFAULT_TRACK_TRACE_DEPTH: u32 = 10;
+/// Upper bound on traversal depth, to keep the number of DHT reads in a single call bounded
+pub const MAX_TRACK_TRACE_DEPTH: u32 = 50;
+
+/// A record encountered whilst traversing resource flows
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug, PartialEq, Eq, Hash)]
+pub enum TrackTraceItem {
+    EconomicEvent(EconomicEventAddress),
+    EconomicResource(EconomicResourceAddress),
+    Process(ProcessAddress),
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackTraceParams {
+    pub start: TrackTraceItem,
+    #[serde(default)]
+    pub max_depth: Option<u32>,
+}
+
+/// A record in the flow graph, along with its distance (in hops) from the starting record
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FlowNode {
+    pub item: TrackTraceItem,
+    pub depth: u32,
+}
+
+/// A directed link between two records in the flow graph.
+/// Edges always point in the direction resources flow, regardless of traversal direction.
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FlowEdge {
+    pub from: TrackTraceItem,
+    pub to: TrackTraceItem,
+}
+
+/// Result of a `track` or `trace` query. Nodes are ordered by increasing depth.
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FlowGraph {
+    pub nodes: Vec<FlowNode>,
+    pub edges: Vec<FlowEdge>,
 }
@@ -16,7 +16,7 @@ use hdk_records::{
     record_interface::Updateable,
 };
 
-use vf_measurement::QuantityValue;
+use vf_measurement::{QuantityValue, negate};
 use vf_attributes_hdk::{
     EconomicEventAddress,
     ActionId,
@@ -29,7 +29,13 @@ use vf_attributes_hdk::{
     AgreementAddress,
     DateTime, FixedOffset,
 };
-use vf_actions::{ validate_flow_action, validate_move_inventories };
+use vf_actions::{
+    validate_flow_action,
+    validate_move_inventories,
+    validate_transfer_inventories,
+    validate_inventory_quantity,
+    is_builtin_action,
+};
 use hc_zome_rea_economic_event_rpc::*;
 
 // :SHONK: needed as re-export in zome logic to allow validation logic to parse entries
@@ -52,6 +58,27 @@ pub struct EconomicEventZomeConfig {
     pub process_index_zome: Option<String>,
     pub agreement_index_zome: Option<String>,
     pub agent_index_zome: Option<String>,
+    pub location_index_zome: Option<String>,
+}
+
+/// The `EconomicResource` zome configuration which governs validation of `EconomicEvents`
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+struct TransferConsentConfigSlice {
+    #[serde(default)]
+    pub economic_resource: TransferConsentConfig,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug, Default)]
+struct TransferConsentConfig {
+    #[serde(default)]
+    pub require_transfer_consent: bool,
+}
+
+/// Whether the DNA requires consent for transfers from inventoried resources
+pub fn transfer_consent_required() -> ExternResult<bool> {
+    let conf: TransferConsentConfigSlice = dna_info()?.properties.try_into()
+        .map_err(|e: SerializedBytesError| wasm_error!(WasmErrorInner::Guest(format!("Invalid economic_resource configuration in DNA properties: {}", e))))?;
+    Ok(conf.economic_resource.require_transfer_consent)
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -77,17 +104,92 @@ pub struct EntryData {
     pub realization_of: Option<AgreementAddress>,
     pub triggered_by: Option<EconomicEventAddress>,
     pub in_scope_of: Option<Vec<String>>,
+    pub corrects: Option<EconomicEventAddress>,
+    // `Create` action of the corrected event, so that validation can determine its author
+    #[serde(default)]
+    pub corrected_action: Option<ActionHash>,
+    // `Create` action of the `resource_inventoried_as` of transfers, so that validation can determine who may consent to them
+    #[serde(default)]
+    pub inventoried_action: Option<ActionHash>,
+    // whether the event was first written as a countersigned entry, so that reads need not inspect the original record
+    #[serde(default)]
+    pub countersigned: bool,
+    // whether the event is the reversal written when correcting `corrects`, which validation ensures exactly negates it
+    #[serde(default)]
+    pub reversal: bool,
     pub note: Option<String>,
     pub _nonce: Bytes,
 }
 
 impl EntryData {
     pub fn validate_action(&self) -> Result<(), String> {
-        let result = validate_flow_action(self.action.to_owned(), self.input_of.to_owned(), self.output_of.to_owned());
-        if result.is_ok() && self.action.as_ref() == "move" {
-            return validate_move_inventories(self.resource_inventoried_as.to_owned(), self.to_resource_inventoried_as.to_owned());
+        validate_flow_action(self.action.to_owned(), self.input_of.to_owned(), self.output_of.to_owned())?;
+        if is_builtin_action(self.action.as_ref(), &["move"]) {
+            validate_move_inventories(self.resource_inventoried_as.to_owned(), self.to_resource_inventoried_as.to_owned())?;
+        } else if is_builtin_action(self.action.as_ref(), &["transfer"]) {
+            validate_transfer_inventories(self.resource_inventoried_as.to_owned(), self.to_resource_inventoried_as.to_owned())?;
+        }
+        validate_inventory_quantity(
+            self.action.to_owned(),
+            self.resource_inventoried_as.is_some() || self.to_resource_inventoried_as.is_some(),
+            self.resource_quantity.is_some(),
+        )
+    }
+
+    /// Whether the event transfers rights or custody from an inventoried resource
+    pub fn is_inventory_transfer(&self) -> bool {
+        self.resource_inventoried_as.is_some()
+            && is_builtin_action(self.action.as_ref(), &["transfer", "transfer-all-rights", "transfer-custody"])
+    }
+
+    /// Whether the event reverses `original`, by negating its quantities while leaving the rest of the flow it observed unchanged
+    pub fn is_reversal_of(&self, original: &EntryData) -> bool {
+        self.reversal
+            && self.corrects.is_some()
+            && self.action == original.action
+            && self.provider == original.provider
+            && self.receiver == original.receiver
+            && self.resource_inventoried_as == original.resource_inventoried_as
+            && self.to_resource_inventoried_as == original.to_resource_inventoried_as
+            && self.resource_conforms_to == original.resource_conforms_to
+            && self.resource_quantity == original.resource_quantity.to_owned().map(negate)
+            && self.effort_quantity == original.effort_quantity.to_owned().map(negate)
+    }
+
+    /// Ensure an update leaves the observed flow untouched. Only descriptive metadata may be revised;
+    /// any change to what was observed must be recorded by correcting the event.
+    pub fn validate_update(&self, previous: &EntryData) -> Result<(), String> {
+        if self.action != previous.action
+            || self.provider != previous.provider
+            || self.receiver != previous.receiver
+            || self.input_of != previous.input_of
+            || self.output_of != previous.output_of
+            || self.resource_inventoried_as != previous.resource_inventoried_as
+            || self.to_resource_inventoried_as != previous.to_resource_inventoried_as
+            || self.resource_conforms_to != previous.resource_conforms_to
+            || self.resource_quantity != previous.resource_quantity
+            || self.effort_quantity != previous.effort_quantity
+            || self.corrects != previous.corrects
+            || self.corrected_action != previous.corrected_action
+            || self.inventoried_action != previous.inventoried_action
+            || self.countersigned != previous.countersigned
+            || self.reversal != previous.reversal
+        {
+            return Err("EconomicEvent flow fields cannot be changed after creation".into());
+        }
+        Ok(())
+    }
+
+    /// Ensure a deleted event does not form part of the ledger. Events which have altered inventories
+    /// or which correct other events can only be amended by appending further corrections.
+    pub fn validate_delete(&self) -> Result<(), String> {
+        if self.resource_inventoried_as.is_some() || self.to_resource_inventoried_as.is_some() {
+            return Err("EconomicEvent affects inventoried resources and must be corrected rather than deleted".into());
+        }
+        if self.corrects.is_some() {
+            return Err("EconomicEvent is part of a correction and cannot be deleted".into());
         }
-        return result;
+        Ok(())
     }
 
     pub fn validate_or_fields(&self) -> Result<(), String> {
@@ -138,7 +240,16 @@ impl TryFrom<AvailableCapability> for EntryTypes {
 pub enum LinkTypes {
     // relates to dna-auth-resolver mixin
     // and remote authorizations
-    AvailableCapability
+    AvailableCapability,
+    // marks countersigned event records whose effects have been applied to inventories
+    CountersignedEventApplied,
+}
+
+/// I/O struct for resolving the `Agent` associated with a public key, via the agent DNA
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WhoisRequest {
+    pub agent_pub_key: AgentPubKey,
 }
 
 //---------------- CREATE ----------------
@@ -171,11 +282,53 @@ impl TryFrom<CreateRequest> for EntryData {
             triggered_by: e.triggered_by.into(),
             at_location: e.at_location.into(),
             in_scope_of: e.in_scope_of.into(),
+            corrects: e.corrects.into(),
+            corrected_action: e.corrected_action,
+            inventoried_action: e.inventoried_action,
+            countersigned: false,
+            reversal: e.reversal,
             _nonce: random_bytes(32)?,
         })
     }
 }
 
+/**
+ * Rebuild an I/O record from stored entry data, so that previously observed events
+ * can be re-applied to the resources they affect.
+ */
+impl From<EntryData> for CreateRequest {
+    fn from(e: EntryData) -> CreateRequest {
+        CreateRequest {
+            action: e.action,
+            note: e.note.into(),
+            input_of: e.input_of.into(),
+            output_of: e.output_of.into(),
+            provider: e.provider,
+            receiver: e.receiver,
+            resource_inventoried_as: e.resource_inventoried_as.into(),
+            to_resource_inventoried_as: e.to_resource_inventoried_as.into(),
+            resource_classified_as: e.resource_classified_as.into(),
+            resource_conforms_to: e.resource_conforms_to.into(),
+            resource_quantity: e.resource_quantity.into(),
+            effort_quantity: e.effort_quantity.into(),
+            has_beginning: e.has_beginning.into(),
+            has_end: e.has_end.into(),
+            has_point_in_time: e.has_point_in_time.into(),
+            at_location: e.at_location.into(),
+            agreed_in: e.agreed_in.into(),
+            realization_of: e.realization_of.into(),
+            triggered_by: e.triggered_by.into(),
+            in_scope_of: e.in_scope_of.into(),
+            corrects: e.corrects.into(),
+            target_inventory_type: None,
+            countersigned_as: None,
+            corrected_action: e.corrected_action,
+            inventoried_action: e.inventoried_action,
+            reversal: e.reversal,
+        }
+    }
+}
+
 //---------------- UPDATE ----------------
 
 /// Handles update operations by merging any newly provided fields into
@@ -201,6 +354,11 @@ impl Updateable<UpdateRequest> for EntryData {
             realization_of: self.realization_of.to_owned(),
             at_location: self.at_location.to_owned(),
             in_scope_of: if e.in_scope_of== MaybeUndefined::Undefined { self.in_scope_of.to_owned() } else { e.in_scope_of.to_owned().into() },
+            corrects: self.corrects.to_owned(),
+            corrected_action: self.corrected_action.to_owned(),
+            inventoried_action: self.inventoried_action.to_owned(),
+            countersigned: self.countersigned,
+            reversal: self.reversal,
             note: if e.note== MaybeUndefined::Undefined { self.note.to_owned() } else { e.note.to_owned().into() },
             _nonce: self._nonce.to_owned(),
         })
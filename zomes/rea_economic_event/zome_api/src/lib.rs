@@ -1,6 +1,38 @@
+use hdk::prelude::*;
 use hdk_records::RecordAPIResult;
 pub use hc_zome_rea_economic_event_rpc::*;
 
+/// Parameters for proposing that an `EconomicEvent` be countersigned by its provider and receiver
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CountersigningProposalParams {
+    pub event: CreateRequest,
+    // public key of the other party to the event, as associated with their `Agent` in the agent DNA
+    pub counterparty: AgentPubKey,
+}
+
+/// A countersigning session request, along with the proposing agent's acceptance of it.
+/// The request is to be relayed to the counterparty for their acceptance.
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CountersigningProposal {
+    pub request: PreflightRequest,
+    pub response: PreflightResponse,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CountersigningAcceptanceParams {
+    pub request: PreflightRequest,
+}
+
+/// Parameters for committing a countersigned `EconomicEvent`, once all parties have accepted the session
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CountersigningCommitParams {
+    pub responses: Vec<PreflightResponse>,
+}
+
 /// API interface for the external zome gateway
 pub trait API {
     type S: AsRef<str>;
@@ -12,6 +44,23 @@ pub trait API {
     fn get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>;
     fn update_economic_event(event: UpdateRequest) -> RecordAPIResult<ResponseData>;
     fn delete_economic_event(revision_id: ActionHash) -> RecordAPIResult<bool>;
+    /// Appends a reversal of `event` and an optional replacement for it. Only the original author of an event (or a
+    /// record administrator) may correct it. `move`, `transfer` and `transfer-all-rights` events which affected inventories
+    /// cannot be corrected, since reversals do not restore resource locations or accountability.
+    fn correct_economic_event(entry_def_id: Self::S,
+        event: EconomicEventAddress, replacement: Option<CreateRequest>
+    ) -> RecordAPIResult<CorrectionResponseData>;
+
+    fn trace(params: TrackTraceParams) -> RecordAPIResult<FlowGraph>;
+    fn track(params: TrackTraceParams) -> RecordAPIResult<FlowGraph>;
+
+    fn propose_countersigned_economic_event(event: CreateRequest, counterparty: AgentPubKey) -> RecordAPIResult<CountersigningProposal>;
+    fn accept_countersigned_economic_event(request: PreflightRequest) -> RecordAPIResult<PreflightResponse>;
+    /// Writes the countersigned event only. Until its proposer calls `apply_countersigned_economic_event`, the event
+    /// is not indexed (so does not appear in any queries) and has no effect on the inventories it references.
+    fn commit_countersigned_economic_event(responses: Vec<PreflightResponse>) -> RecordAPIResult<ResponseData>;
+    /// Indexes a committed countersigned event and applies it to inventories. Only callable once, by the proposing agent.
+    fn apply_countersigned_economic_event(entry_def_id: Self::S, revision_id: ActionHash) -> RecordAPIResult<ResponseData>;
 }
 
 /// Macro to programatically and predictably bind an `API` implementation to a
@@ -50,5 +99,46 @@ macro_rules! declare_economic_event_zome_api {
         fn delete_economic_event(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
             Ok(<$zome_api>::delete_economic_event(revision_id)?)
         }
+
+        #[hdk_extern]
+        fn correct_economic_event(CorrectionParams { event, replacement }: CorrectionParams) -> ExternResult<CorrectionResponseData> {
+            Ok(<$zome_api>::correct_economic_event(
+                EVENT_ENTRY_TYPE,
+                event, replacement,
+            )?)
+        }
+
+        #[hdk_extern]
+        fn trace(params: TrackTraceParams) -> ExternResult<FlowGraph> {
+            Ok(<$zome_api>::trace(params)?)
+        }
+
+        #[hdk_extern]
+        fn track(params: TrackTraceParams) -> ExternResult<FlowGraph> {
+            Ok(<$zome_api>::track(params)?)
+        }
+
+        #[hdk_extern]
+        fn propose_countersigned_economic_event(CountersigningProposalParams { event, counterparty }: CountersigningProposalParams) -> ExternResult<CountersigningProposal> {
+            Ok(<$zome_api>::propose_countersigned_economic_event(event, counterparty)?)
+        }
+
+        #[hdk_extern]
+        fn accept_countersigned_economic_event(CountersigningAcceptanceParams { request }: CountersigningAcceptanceParams) -> ExternResult<PreflightResponse> {
+            Ok(<$zome_api>::accept_countersigned_economic_event(request)?)
+        }
+
+        #[hdk_extern]
+        fn commit_countersigned_economic_event(CountersigningCommitParams { responses }: CountersigningCommitParams) -> ExternResult<ResponseData> {
+            Ok(<$zome_api>::commit_countersigned_economic_event(responses)?)
+        }
+
+        #[hdk_extern]
+        fn apply_countersigned_economic_event(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+            Ok(<$zome_api>::apply_countersigned_economic_event(
+                EVENT_ENTRY_TYPE,
+                revision_id,
+            )?)
+        }
     };
 }
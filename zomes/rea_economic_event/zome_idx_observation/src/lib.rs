@@ -14,9 +14,16 @@ struct EconomicEvent {
     realization_of: Local<agreement, economic_events>,
     satisfies: Local<satisfaction, satisfied_by>,
     fulfills: Local<fulfillment, fulfilled_by>,
+    triggers: Local<claim, triggered_by>,
+    settles: Local<settlement, settled_by>,
+    appreciated_by: Local<appreciation, appreciation_of>,
+    appreciation: Local<appreciation, appreciation_with>,
+    corrects: Local<economic_event, corrected_by>,
+    corrected_by: Local<economic_event, corrects>,
 
     // internal indexes (not part of REA spec)
     affects: Local<economic_resource, affected_by>,
     provider: Local<agent, economic_events_as_provider>,
     receiver: Local<agent, economic_events_as_receiver>,
+    at_location: Local<location, economic_events>,
 }
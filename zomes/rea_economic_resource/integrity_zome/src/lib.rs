@@ -8,6 +8,7 @@
  * @package hREA
  */
 use hdi::prelude::*;
+use hdk_records::validation::{ validate_update_authorship, validate_delete_authorship };
 use hc_zome_rea_economic_resource_storage::Identified;
 use hc_zome_rea_economic_resource_storage::{EntryStorage, EntryTypes, EntryTypesUnit, LinkTypes};
 
@@ -48,12 +49,33 @@ macro_rules! declare_economic_resource_zome_validation_defaults {
                 Op::StoreEntry(StoreEntry { entry, .. }) => validate_entry(entry),
                 Op::RegisterCreateLink { .. } => Ok(ValidateCallbackResult::Valid),
                 Op::RegisterDeleteLink { .. } => Ok(ValidateCallbackResult::Valid),
-                Op::RegisterUpdate { .. } => Ok(ValidateCallbackResult::Valid),
-                Op::RegisterDelete { .. } => Ok(ValidateCallbackResult::Valid),
+                // resources are also updated by whichever agent observes an EconomicEvent affecting them,
+                // so other agents may alter their quantities. Authority over those changes is determined
+                // by validation of the events themselves.
+                Op::RegisterUpdate(update) => match validate_update_authorship(&update)? {
+                    ValidateCallbackResult::Valid => Ok(ValidateCallbackResult::Valid),
+                    _ => validate_event_update(update.new_entry, update.original_entry),
+                },
+                Op::RegisterDelete(delete) => validate_delete_authorship(&delete),
                 Op::RegisterAgentActivity { .. } => Ok(ValidateCallbackResult::Valid),
             }
         }
 
+        fn validate_event_update(new_entry: Option<Entry>, original_entry: Option<Entry>) -> ExternResult<ValidateCallbackResult> {
+            match (new_entry, original_entry) {
+                (Some(new_entry), Some(original_entry)) => match (EntryStorage::try_from(&new_entry), EntryStorage::try_from(&original_entry)) {
+                    (Ok(new_storage), Ok(original_storage)) => new_storage.entry()
+                        .validate_event_update(&original_storage.entry())
+                        .and_then(|()| Ok(ValidateCallbackResult::Valid))
+                        .or_else(|e| Ok(ValidateCallbackResult::Invalid(e))),
+                    (Ok(_), Err(_)) => Ok(ValidateCallbackResult::Invalid("EconomicResource can only update a previous EconomicResource".into())),
+                    _ => Ok(ValidateCallbackResult::Valid),
+                },
+                // entries are public, so their content is always present for validation
+                _ => Ok(ValidateCallbackResult::Invalid("EconomicResource updates by other agents must be validated against their content".into())),
+            }
+        }
+
         fn validate_entry(entry: Entry) -> ExternResult<ValidateCallbackResult> {
             match EntryStorage::try_from(&entry) {
                 Ok(resource_storage) => {
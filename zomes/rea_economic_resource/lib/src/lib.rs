@@ -6,9 +6,12 @@
  *
  * @package hREA
  */
+use std::convert::{TryFrom, TryInto};
 use paste::paste;
+use hdk::prelude::{Entry, EntryVisibility, LinkTypeFilterExt, ScopedEntryDefIndex, ScopedLinkType, WasmError};
 use hdk_records::{
-    DataIntegrityError, RecordAPIResult, MaybeUndefined,
+    DataIntegrityError, RecordAPIResult, MaybeUndefined, OtherCellResult,
+    dna_info,
     records::{
         get_latest_action_hash,
         create_record,
@@ -16,16 +19,24 @@ use hdk_records::{
         read_record_entry_by_action,
         update_record,
     },
+    entries::{get_entry_by_address, get_entry_by_action},
+    countersigning::read_countersigning_agents,
     metadata::read_revision_metadata_abbreviated,
+    record_interface::Updateable,
+    rpc::{call_zome_method, AvailableCapability},
     EntryHash, SignedActionHashed,
 };
 use hdk_semantic_indexes_client_lib::*;
 
+use vf_measurement::QuantityValue;
 use vf_attributes_hdk::{
+    AgentAddress,
+    AgentPubKey,
     EconomicResourceAddress,
     EconomicEventAddress,
     ActionId,
     ProcessSpecificationAddress,
+    DateTime, FixedOffset,
 };
 
 pub use hc_zome_rea_economic_resource_storage_consts::*;
@@ -40,7 +51,8 @@ use hc_zome_rea_process_storage::{
     EntryData as ProcessData,
     EntryStorage as ProcessStorage,
 };
-use hc_zome_rea_economic_event_storage::{EntryData as EventData, EntryStorage as EventStorage};
+use hc_zome_rea_economic_event_storage::{EntryData as EventData, EntryStorage as EventStorage, WhoisRequest};
+use hc_zome_rea_agent_rpc::ResponseData as AgentResponseData;
 use hc_zome_rea_economic_event_rpc::{
     ResourceResponse as Response,
     ResourceResponseData as ResponseData,
@@ -83,7 +95,10 @@ impl API for EconomicResourceZomePermissableDefault {
         let (meta, base_address, entry_resp): (_, EconomicResourceAddress, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(
             read_index_zome,
             &resource_entry_def_id,
-            params.with_inventory_type(ResourceInventoryType::ProvidingInventory),  // inventories can only be inited by their owners initially
+            InventoryChange {
+                params: params.with_inventory_type(ResourceInventoryType::ProvidingInventory),  // inventories can only be inited by their owners initially
+                policy: policy_for_event(&event_params)?,
+            },
         )?;
 
         // :NOTE: this will always run- resource without a specification ID would fail entry validation (implicit in the above)
@@ -100,6 +115,14 @@ impl API for EconomicResourceZomePermissableDefault {
             let e = create_index!(economic_resource.primary_accountable(&event_params.receiver), agent.inventoried_economic_resources(&base_address));
             hdk::prelude::debug!("create_inventory_from_event::new_inventoried_resource::primary_accountable index {:?}", e);
         }
+        if let Some(lot) = &entry_resp.lot {
+            let e = create_index!(economic_resource.lot(lot), product_batch.economic_resources(&base_address));
+            hdk::prelude::debug!("create_inventory_from_event::lot index {:?}", e);
+        }
+        if let Some(current_location) = &entry_resp.current_location {
+            let e = create_index!(economic_resource.current_location(current_location), location.economic_resources(&base_address));
+            hdk::prelude::debug!("create_inventory_from_event::current_location index {:?}", e);
+        }
 
         Ok((meta, base_address, entry_resp))
     }
@@ -122,6 +145,9 @@ impl API for EconomicResourceZomePermissableDefault {
         event: EventCreateRequest,
     ) -> RecordAPIResult<Vec<(SignedActionHashed, EconomicResourceAddress, EntryData, EntryData)>>
     {
+        check_transfer_consent(&event)?;
+        let policy = policy_for_event(&event)?;
+
         let mut resources_affected: Vec<(SignedActionHashed, EconomicResourceAddress, EntryData, EntryData)> = vec![];
 
         // if the event is a transfer-like event, run the receiver's update first
@@ -131,6 +157,7 @@ impl API for EconomicResourceZomePermissableDefault {
             let (meta, resource_address, new_resource, prev_resource) = handle_update_inventory_resource(
                 &get_latest_action_hash(inv_entry_hash.clone())?,
                 event.with_inventory_type(ResourceInventoryType::ReceivingInventory),
+                &policy,
             )?;
             resources_affected.push((meta, resource_address.clone(), new_resource.clone(), prev_resource.clone()));
             if new_resource.primary_accountable != prev_resource.primary_accountable {
@@ -143,14 +170,18 @@ impl API for EconomicResourceZomePermissableDefault {
                     agent.inventoried_economic_resources(&resource_address));
                 hdk::prelude::debug!("update_economic_resource::to_resource_inventoried_as::primary_accountable index {:?}", e);
             }
+            update_current_location_index(&resource_address, &new_resource, &prev_resource);
         }
         // after receiver, run provider. This entry data will be returned in the response.
         if let MaybeUndefined::Some(provider_inventory) = &event.resource_inventoried_as {
             let inv_entry_hash: &EntryHash = provider_inventory.as_ref();
-            resources_affected.push(handle_update_inventory_resource(
+            let (meta, resource_address, new_resource, prev_resource) = handle_update_inventory_resource(
                 &get_latest_action_hash(inv_entry_hash.clone())?,   // :TODO: temporal reduction here! Should error on mismatch and return latest valid ID
                 event.with_inventory_type(ResourceInventoryType::ProvidingInventory),
-            )?);
+                &policy,
+            )?;
+            update_current_location_index(&resource_address, &new_resource, &prev_resource);
+            resources_affected.push((meta, resource_address, new_resource, prev_resource));
         }
 
         Ok(resources_affected)
@@ -174,6 +205,52 @@ impl API for EconomicResourceZomePermissableDefault {
         // :TODO: optimise this- should pass results from `replace_direct_index` instead of retrieving from `get_link_fields` where updates
         construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)
     }
+
+    fn recalculate_economic_resource(address: EconomicResourceAddress, write: bool) -> RecordAPIResult<RecalculationResponse>
+    {
+        let (meta, base_address, stored) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+        let (derived, events_replayed) = replay_resource_events(&base_address, None, &read_inventory_policy()?)?;
+
+        let diverged = derived.accounting_quantity != stored.accounting_quantity
+            || derived.onhand_quantity != stored.onhand_quantity;
+
+        let written = diverged && write;
+        if written {
+            let (_meta, _base, _new_entry, _prev_entry): (_, EconomicResourceAddress, EntryData, EntryData) = update_record(
+                meta.as_hash(),
+                DerivedQuantities {
+                    accounting_quantity: derived.accounting_quantity.to_owned(),
+                    onhand_quantity: derived.onhand_quantity.to_owned(),
+                },
+            )?;
+        }
+
+        Ok(RecalculationResponse {
+            economic_resource: base_address,
+            events_replayed: events_replayed as u32,
+            accounting_quantity: derived.accounting_quantity,
+            onhand_quantity: derived.onhand_quantity,
+            stored_accounting_quantity: stored.accounting_quantity,
+            stored_onhand_quantity: stored.onhand_quantity,
+            diverged,
+            written,
+        })
+    }
+
+    fn economic_resource_as_of(address: EconomicResourceAddress, timestamp: DateTime<FixedOffset>) -> RecordAPIResult<ResourceBalanceResponse>
+    {
+        let (state, events_replayed) = replay_resource_events(&address, Some(&timestamp), &read_inventory_policy()?)?;
+
+        Ok(ResourceBalanceResponse {
+            economic_resource: address,
+            as_of: timestamp,
+            events_replayed: events_replayed as u32,
+            accounting_quantity: state.accounting_quantity,
+            onhand_quantity: state.onhand_quantity,
+            primary_accountable: state.primary_accountable,
+            current_location: state.current_location,
+        })
+    }
 }
 
 /// Properties accessor for zome config
@@ -186,12 +263,169 @@ fn read_resource_specification_index_zome(conf: DnaConfigSlice) -> Option<String
     conf.economic_resource.resource_specification_index_zome
 }
 
+/// Properties accessor for zome config
+fn read_product_batch_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.economic_resource.product_batch_index_zome
+}
+
+/// Properties accessor for zome config
+fn read_location_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.economic_resource.location_index_zome
+}
+
+/// Keep the `Location` index in sync when an event moves a resource to a new `current_location`
+///
+fn update_current_location_index(resource_address: &EconomicResourceAddress, new_resource: &EntryData, prev_resource: &EntryData) {
+    if new_resource.current_location != prev_resource.current_location {
+        let new_value = if let Some(val) = &new_resource.current_location { vec![val.to_owned()] } else { vec![] };
+        let prev_value = if let Some(val) = &prev_resource.current_location { vec![val.to_owned()] } else { vec![] };
+        let e = update_index!(
+            economic_resource
+                .current_location(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            location.economic_resources(resource_address));
+        hdk::prelude::debug!("update_economic_resource::current_location index {:?}", e);
+    }
+}
+
+/// Where `require_transfer_consent` is enabled for the DNA, transfers of rights or custody over an
+/// inventoried resource must be authored or countersigned by the agent currently accountable for
+/// the providing resource.
+///
+/// Determining the accountable agent requires calls to the agent DNA which cannot be made during validation,
+/// so the integrity zome instead requires transfers to be authored or countersigned by the agent key which
+/// created the providing resource. @see `validate_transfer_consent` in the economic event integrity zome.
+///
+fn check_transfer_consent(event: &EventCreateRequest) -> RecordAPIResult<()> {
+    let conf: DnaConfigSlice = dna_info()?.properties.try_into()?;
+    if !conf.economic_resource.require_transfer_consent {
+        return Ok(());
+    }
+    if !is_builtin_action(event.get_action(), &["transfer", "transfer-all-rights", "transfer-custody"]) {
+        return Ok(());
+    }
+    let resource_address = match &event.resource_inventoried_as {
+        MaybeUndefined::Some(resource_address) => resource_address,
+        _ => return Ok(()),
+    };
+
+    let (_meta, _base_address, resource) = read_record_entry::<EntryData, EntryStorage, _>(resource_address.as_ref())?;
+    match resource.primary_accountable {
+        None => Ok(()),
+        Some(accountable) if is_countersigned_by(event, resource_address, &accountable)? => Ok(()),
+        Some(accountable) => match read_my_agent::<EntryTypes, _, _, _>(&accountable, LinkTypes::AvailableCapability) {
+            Ok(my_agent) if my_agent == accountable => Ok(()),
+            _ => Err(DataIntegrityError::TransferConsentRequired(resource_address.to_string())),
+        },
+    }
+}
+
+/// Whether `event` is being applied from a countersigned `EconomicEvent` record which `agent` signed.
+/// Signing agents' public keys are resolved to their `Agent` records via the agent DNA of `agent`.
+fn is_countersigned_by(event: &EventCreateRequest, resource_address: &EconomicResourceAddress, agent: &AgentAddress) -> RecordAPIResult<bool> {
+    let revision_id = match &event.countersigned_as {
+        Some(revision_id) => revision_id,
+        None => return Ok(false),
+    };
+    let signing_agents = read_countersigning_agents(revision_id)?;
+    if signing_agents.is_empty() {
+        return Ok(false);
+    }
+
+    // the countersigned record must describe the same transfer as is being applied
+    let (_meta, signed_event): (_, EventStorage) = get_entry_by_action(revision_id)?;
+    let signed_event: EventData = signed_event.entry();
+    if !(signed_event.action == event.action
+        && signed_event.provider == event.provider
+        && signed_event.receiver == event.receiver
+        && signed_event.resource_inventoried_as.as_ref() == Some(resource_address)
+        && signed_event.resource_quantity == event.resource_quantity.to_owned().to_option()
+        && (signed_event.provider == *agent || signed_event.receiver == *agent))
+    {
+        return Ok(false);
+    }
+
+    // and must have been signed by a key belonging to the accountable agent
+    Ok(signing_agents.iter().any(|agent_pub_key| {
+        matches!(read_agent_whois::<EntryTypes, _, _, _>(agent, agent_pub_key, LinkTypes::AvailableCapability), Ok(signer) if signer == *agent)
+    }))
+}
+
+/// Determine the `Agent` associated with the current user, in the agent DNA of `agent_dna`.
+/// `EN` and `capability_link_type` are the entry and link types of the calling zome, used to store its
+/// authorization to call the agent DNA.
+pub fn read_my_agent<EN, LT, E, E2>(agent_dna: &AgentAddress, capability_link_type: LT) -> RecordAPIResult<AgentAddress>
+    where ScopedLinkType: TryFrom<LT, Error = E>,
+        LT: Clone + LinkTypeFilterExt,
+        EN: TryFrom<AvailableCapability, Error = E>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
+        EntryVisibility: for<'a> From<&'a EN>,
+        Entry: TryFrom<EN, Error = E>,
+        WasmError: From<E> + From<E2>,
+{
+    let agent_data: OtherCellResult<AgentResponseData> = call_zome_method::<EN, _, _, _, _, _, _, _>(
+        agent_dna,
+        &String::from("read_my_agent"),
+        (),
+        capability_link_type
+    );
+
+    match agent_data {
+        Ok(agent_response) => Ok(agent_response.agent.id),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Determine the `Agent` associated with `agent_pub_key`, in the agent DNA of `agent_dna`.
+/// @see read_my_agent
+pub fn read_agent_whois<EN, LT, E, E2>(agent_dna: &AgentAddress, agent_pub_key: &AgentPubKey, capability_link_type: LT) -> RecordAPIResult<AgentAddress>
+    where ScopedLinkType: TryFrom<LT, Error = E>,
+        LT: Clone + LinkTypeFilterExt,
+        EN: TryFrom<AvailableCapability, Error = E>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a EN, Error = E2>,
+        EntryVisibility: for<'a> From<&'a EN>,
+        Entry: TryFrom<EN, Error = E>,
+        WasmError: From<E> + From<E2>,
+{
+    let agent_data: OtherCellResult<AgentResponseData> = call_zome_method::<EN, _, _, _, _, _, _, _>(
+        agent_dna,
+        &String::from("read_agent_whois"),
+        WhoisRequest { agent_pub_key: agent_pub_key.to_owned() },
+        capability_link_type
+    );
+
+    match agent_data {
+        Ok(agent_response) => Ok(agent_response.agent.id),
+        Err(e) => Err(e.into()),
+    }
+}
+
 fn handle_update_inventory_resource(
     resource_addr: &ActionHash,
     event: EventCreateRequest,
+    policy: &InventoryPolicy,
 ) -> RecordAPIResult<(SignedActionHashed, EconomicResourceAddress, EntryData, EntryData)>
 {
-    Ok(update_record(resource_addr, event)?)
+    Ok(update_record(resource_addr, InventoryChange { params: event, policy: policy.to_owned() })?)
+}
+
+/// Determine the `InventoryPolicy` to enforce when applying an event to inventories.
+/// Reversals of corrected events restore balances which were previously accepted, and so bypass its guards
+/// once they have been checked against the event they correct.
+///
+fn policy_for_event(event: &EventCreateRequest) -> RecordAPIResult<InventoryPolicy> {
+    let policy = read_inventory_policy()?;
+    if !event.reversal {
+        return Ok(policy);
+    }
+    let corrected_action = event.corrected_action.as_ref()
+        .ok_or(DataIntegrityError::InvalidReversal("no corrected event referenced".to_string()))?;
+    let (_meta, corrected_address, corrected) = read_record_entry_by_action::<EventData, EventStorage, _>(corrected_action)?;
+    let reversal = EventData::try_from(event.to_owned())?;
+    if reversal.corrects.as_ref() != Some(&corrected_address) || !reversal.is_reversal_of(&corrected) {
+        return Err(DataIntegrityError::InvalidReversal(format!("{:?}", corrected_address)));
+    }
+    Ok(policy.without_guards())
 }
 
 /// Create response from input DHT primitives
@@ -338,6 +572,90 @@ fn get_resource_stage(resource: &EconomicResourceAddress) -> RecordAPIResult<Opt
     )
 }
 
+/// Rebuild the state of an EconomicResource by replaying every event which has affected it,
+/// in the order they were observed. Returns the derived entry data and the number of events applied.
+///
+/// Replay begins from the initial revision of the resource with its quantities zeroed, since the
+/// event which created the resource is itself present in the `affected_by` index.
+/// If `until` is provided, only events observed at or before that moment are applied.
+///
+/// Every replayed event was accepted when it was first observed, so the guards of the given
+/// `policy` are not enforced; only its quantity scale is applied.
+///
+fn replay_resource_events(resource: &EconomicResourceAddress, until: Option<&DateTime<FixedOffset>>, policy: &InventoryPolicy) -> RecordAPIResult<(EntryData, usize)>
+{
+    let policy = policy.without_guards();
+    let (_meta, initial): (_, EntryStorage) = get_entry_by_address(resource.as_ref())?;
+    let initial = initial.entry();
+    let mut state = EntryData {
+        accounting_quantity: zero_quantity(&initial.accounting_quantity),
+        onhand_quantity: zero_quantity(&initial.onhand_quantity),
+        ..initial
+    };
+
+    let mut events = get_affecting_events(resource)?.iter()
+        .map(|event| read_record_entry::<EventData, EventStorage, _>(event.as_ref()))
+        .collect::<RecordAPIResult<Vec<_>>>()?;
+
+    if let Some(cutoff) = until {
+        events.retain(|(_, _, event)| match get_event_time(event) {
+            Some(observed) => observed <= *cutoff,
+            None => false,
+        });
+    }
+
+    // order by observation time, falling back to authoring order for simultaneous events
+    events.sort_by_key(|(meta, _, event)| (
+        get_event_time(event),
+        meta.action().timestamp().as_micros(),
+    ));
+
+    for (_, _, event) in events.iter() {
+        state = apply_event_to_resource(resource, state, event, &policy)?;
+    }
+
+    Ok((state, events.len()))
+}
+
+/// Apply the effects of an observed event to the given resource state, mirroring the
+/// receiver-then-provider ordering used in `update_inventory_from_event`.
+///
+fn apply_event_to_resource(resource: &EconomicResourceAddress, state: EntryData, event: &EventData, policy: &InventoryPolicy) -> RecordAPIResult<EntryData>
+{
+    let mut request = EventCreateRequest::from(event.to_owned());
+    // the creating event may omit units where these were defaulted from the resource specification
+    if let MaybeUndefined::Some(qty) = &request.resource_quantity {
+        if qty.get_unit().is_none() {
+            let unit = state.accounting_quantity.as_ref().or(state.onhand_quantity.as_ref()).and_then(|q| q.get_unit());
+            request.resource_quantity = MaybeUndefined::Some(QuantityValue::new(qty.get_numerical_value(), unit));
+        }
+    }
+
+    let mut next = state;
+    if event.to_resource_inventoried_as.as_ref() == Some(resource) {
+        next = next.update_with(InventoryChange { params: request.with_inventory_type(ResourceInventoryType::ReceivingInventory), policy: policy.to_owned() })?;
+    }
+    if event.resource_inventoried_as.as_ref() == Some(resource) {
+        next = next.update_with(InventoryChange { params: request.with_inventory_type(ResourceInventoryType::ProvidingInventory), policy: policy.to_owned() })?;
+    }
+    Ok(next)
+}
+
+/// Determine the moment an event took effect. Events spanning a period of time are
+/// considered to have affected inventory upon completion.
+///
+fn get_event_time(event: &EventData) -> Option<DateTime<FixedOffset>>
+{
+    event.has_point_in_time.to_owned()
+        .or(event.has_end.to_owned())
+        .or(event.has_beginning.to_owned())
+}
+
+fn zero_quantity(qty: &Option<QuantityValue>) -> Option<QuantityValue>
+{
+    qty.as_ref().map(|q| q.zeroed())
+}
+
 /// Read all the EconomicEvents affecting a given EconomicResource
 fn get_affecting_events(resource: &EconomicResourceAddress) -> RecordAPIResult<Vec<EconomicEventAddress>>
 {
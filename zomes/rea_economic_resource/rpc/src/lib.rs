@@ -9,6 +9,7 @@
 use holochain_serialized_bytes::prelude::*;
 
 use serde_maybe_undefined::MaybeUndefined;
+use vf_measurement::QuantityValue;
 pub use vf_attributes_hdk::{
     ActionHash, ByAddress, ByRevision, RecordMeta, RevisionMeta,
     EconomicResourceAddress,
@@ -19,6 +20,7 @@ pub use vf_attributes_hdk::{
     UnitId,
     ProductBatchAddress,
     AgentAddress,
+    DateTime, FixedOffset,
 };
 
 use hc_zome_rea_economic_event_rpc::{
@@ -93,6 +95,61 @@ pub struct UpdateParams {
     pub resource: UpdateRequest,
 }
 
+//---------------- RECALCULATION REQUEST ----------------
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecalculationParams {
+    pub address: EconomicResourceAddress,
+    // if set, derived quantities which diverge from those stored are written back to the resource
+    #[serde(default)]
+    pub write: bool,
+}
+
+/// Quantities derived by replaying all events affecting a resource, alongside those currently stored
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RecalculationResponse {
+    pub economic_resource: EconomicResourceAddress,
+    pub events_replayed: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accounting_quantity: Option<QuantityValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onhand_quantity: Option<QuantityValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stored_accounting_quantity: Option<QuantityValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stored_onhand_quantity: Option<QuantityValue>,
+    pub diverged: bool,
+    pub written: bool,
+}
+
+//---------------- POINT-IN-TIME REQUEST ----------------
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AsOfParams {
+    pub address: EconomicResourceAddress,
+    pub timestamp: DateTime<FixedOffset>,
+}
+
+/// State of a resource at some past moment, derived from the events observed up until that time
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceBalanceResponse {
+    pub economic_resource: EconomicResourceAddress,
+    pub as_of: DateTime<FixedOffset>,
+    pub events_replayed: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accounting_quantity: Option<QuantityValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onhand_quantity: Option<QuantityValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_accountable: Option<AgentAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_location: Option<LocationAddress>,
+}
+
 //---------------- QUERY FILTER REQUEST ----------------
 
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
@@ -101,6 +158,8 @@ pub struct QueryParams {
     pub contains: Option<EconomicResourceAddress>,
     pub contained_in: Option<EconomicResourceAddress>,
     pub conforms_to: Option<ResourceSpecificationAddress>,
+    pub lot: Option<ProductBatchAddress>,
     pub affected_by: Option<EconomicEventAddress>,
     pub primary_accountable: Option<AgentAddress>,
+    pub current_location: Option<LocationAddress>,
 }
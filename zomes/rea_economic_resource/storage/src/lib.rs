@@ -7,6 +7,7 @@ use hc_zome_dna_auth_resolver_core::AvailableCapability;
  *
  * @package hREA
  */
+use std::collections::HashMap;
 use hdk::prelude::*;
 
 use hdk_records::{
@@ -29,8 +30,9 @@ use vf_attributes_hdk::{
     AgentAddress,
 };
 use vf_actions::{ ActionEffect, ActionInventoryEffect};
-pub use vf_actions::get_builtin_action;
+pub use vf_actions::{get_builtin_action, is_builtin_action};
 use hc_zome_rea_resource_specification_rpc::{ResponseData as ResourceSpecificationResponseData, Response as ResourceSpecificationResponse};
+use hc_zome_rea_unit_rpc::ResponseData as UnitResponseData;
 
 use hc_zome_rea_economic_resource_rpc::*;
 use hc_zome_rea_economic_event_rpc::{
@@ -54,6 +56,55 @@ pub struct EconomicResourceZomeConfig {
     pub index_zome: String,
     pub resource_specification_index_zome: Option<String>,
     pub agent_index_zome: Option<String>,
+    pub location_index_zome: Option<String>,
+    pub product_batch_index_zome: Option<String>,
+    /// reject transfers not authored by the `primary_accountable` agent of the providing resource.
+    /// Validation enforces this against the agent key which created the providing resource.
+    #[serde(default)]
+    pub require_transfer_consent: bool,
+    #[serde(default)]
+    pub inventory_policy: InventoryPolicy,
+}
+
+/// Guards applied when events update the quantities of inventoried resources. All are disabled by default.
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug, Default)]
+pub struct InventoryPolicy {
+    /// reject events which would leave an accounting or onhand quantity below zero
+    #[serde(default)]
+    pub reject_negative_balances: bool,
+    /// reject events affecting resources which have no quantity, or where either quantity has no unit
+    #[serde(default)]
+    pub require_units: bool,
+    /// reject events whose `resource_conforms_to` differs from the `conforms_to` of the resources they affect
+    #[serde(default)]
+    pub require_matching_specification: bool,
+    /// number of fractional digits retained in stored resource quantities, rounding half away from zero.
+    /// Where unset, quantities keep the precision of the events applied to them.
+    #[serde(default)]
+    pub quantity_scale: Option<u32>,
+}
+
+impl InventoryPolicy {
+    /// Policy applied when replaying or reversing previously accepted events, which must not be
+    /// rejected by the guards. Quantities are still rounded to the configured scale.
+    pub fn without_guards(&self) -> Self {
+        InventoryPolicy {
+            quantity_scale: self.quantity_scale,
+            ..InventoryPolicy::default()
+        }
+    }
+}
+
+pub fn read_inventory_policy() -> RecordAPIResult<InventoryPolicy> {
+    let conf: DnaConfigSlice = dna_info()?.properties.try_into()?;
+    Ok(conf.economic_resource.inventory_policy)
+}
+
+/// Pairs an event-driven create or update payload with the `InventoryPolicy` to enforce whilst applying it.
+#[derive(Clone, Debug)]
+pub struct InventoryChange<T> {
+    pub params: T,
+    pub policy: InventoryPolicy,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -83,6 +134,34 @@ impl EntryData {
         }
         Ok(())
     }
+
+    /// Ensure an update by an agent other than the resource's author only alters its quantities, or adds
+    /// classifications. Location and accountability can be reassigned by observed `EconomicEvents`, but
+    /// validation cannot determine whether such an event exists; so those changes, along with any others,
+    /// may only be made by the resource's author or a record administrator.
+    pub fn validate_event_update(&self, previous: &EntryData) -> Result<(), String> {
+        let retains_classifications = match (&previous.classified_as, &self.classified_as) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(previous_classes), Some(classes)) => previous_classes.iter().all(|c| classes.contains(c)),
+        };
+        if !retains_classifications
+            || self.name != previous.name
+            || self.conforms_to != previous.conforms_to
+            || self.tracking_identifier != previous.tracking_identifier
+            || self.lot != previous.lot
+            || self.image != previous.image
+            || self.unit_of_effort != previous.unit_of_effort
+            || self.current_location != previous.current_location
+            || self.contained_in != previous.contained_in
+            || self.note != previous.note
+            || self.primary_accountable != previous.primary_accountable
+            || self._nonce != previous._nonce
+        {
+            return Err("EconomicResource fields other than quantities and classifications may only be modified by their original author or a record administrator".into());
+        }
+        Ok(())
+    }
 }
 
 generate_record_entry!(EntryData, EconomicResourceAddress, EntryStorage);
@@ -124,10 +203,11 @@ pub enum LinkTypes {
 
 /// Handles create operations via observed event resource inspection parameter
 /// @see https://github.com/h-REA/hREA/issues/65
-impl TryFrom<CreationPayload> for EntryData {
+impl TryFrom<InventoryChange<CreationPayload>> for EntryData {
     type Error = DataIntegrityError;
 
-    fn try_from(t: CreationPayload) -> RecordAPIResult<EntryData> {
+    fn try_from(change: InventoryChange<CreationPayload>) -> RecordAPIResult<EntryData> {
+        let InventoryChange { params: t, policy } = change;
         let conforming = t.get_resource_specification_id();
         let r = t.resource;
         let e = t.event;
@@ -135,6 +215,8 @@ impl TryFrom<CreationPayload> for EntryData {
         let raise_action = get_builtin_action("raise").unwrap();
         let lower_action = get_builtin_action("lower").unwrap();
         let action_id = String::from(e.get_action());
+        check_resource_specification(&policy, &conforming, &e.resource_conforms_to)?;
+        let mut unit_conversions = UnitConversions::default();
         // first choice are the units passed in on the event
         // value, fallback is the default_unit_of_resource and
         // default_unit_of_effort
@@ -160,7 +242,7 @@ impl TryFrom<CreationPayload> for EntryData {
             accounting_quantity: match quantity_value.clone() {
                 Some(resource_quantity) => update_quantity(
                     // instantiate with the correct units
-                    Some(QuantityValue::new(0.0, resource_quantity.get_unit())), 
+                    Some(resource_quantity.zeroed()),
                     MaybeUndefined::Some(resource_quantity),
                     &e.action,
                     ResourceValueType::AccountingValue,
@@ -168,13 +250,15 @@ impl TryFrom<CreationPayload> for EntryData {
                         Some(inventory_type) => inventory_type.to_owned(),
                         None => panic!("Developer error: EconomicEvent inventory type must be provided when creating EconomicResource!"),
                     },
+                    &policy,
+                    &mut unit_conversions,
                 )?,
                 None => None,
             },
             onhand_quantity: match quantity_value {
                 Some(resource_quantity) => update_quantity(
                     // instantiate with the correct units
-                    Some(QuantityValue::new(0.0, resource_quantity.get_unit())),
+                    Some(resource_quantity.zeroed()),
                     MaybeUndefined::Some(resource_quantity),
                     &e.action,
                     ResourceValueType::OnhandValue,
@@ -182,6 +266,8 @@ impl TryFrom<CreationPayload> for EntryData {
                         Some(inventory_type) => inventory_type.to_owned(),
                         None => panic!("Developer error: EconomicEvent inventory type must be provided when updating EconomicResource!"),
                     },
+                    &policy,
+                    &mut unit_conversions,
                 )?,
                 None => None,
             },
@@ -242,6 +328,56 @@ fn get_units_for_resource(
     }
 }
 
+/// I/O struct for forwarding records to other DNAs via zome API
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+pub struct GetUnitRequest {
+    pub id: UnitId,
+}
+
+fn get_unit_conversion(unit_id: &UnitId) -> RecordAPIResult<Option<UnitConversion>> {
+    let unit_data: OtherCellResult<UnitResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        unit_id,
+        &String::from("read_unit"),
+        GetUnitRequest { id: unit_id.to_owned() },
+        LinkTypes::AvailableCapability
+    );
+
+    match unit_data {
+        Ok(unit_response) => Ok(unit_response.unit.get_conversion()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Unit conversions read whilst applying an event, so that each unit is only requested once
+#[derive(Default)]
+struct UnitConversions(HashMap<UnitId, Option<UnitConversion>>);
+
+impl UnitConversions {
+    fn get(&mut self, unit_id: &UnitId) -> RecordAPIResult<Option<UnitConversion>> {
+        if let Some(conversion) = self.0.get(unit_id) {
+            return Ok(conversion.to_owned());
+        }
+        let conversion = get_unit_conversion(unit_id)?;
+        self.0.insert(unit_id.to_owned(), conversion.to_owned());
+        Ok(conversion)
+    }
+}
+
+/// Expresses an event quantity in the units of the resource quantity it affects.
+/// Differing units are converted where both are registered against the same dimension.
+fn to_resource_units(current: &QuantityValue, event_qty: QuantityValue, unit_conversions: &mut UnitConversions) -> RecordAPIResult<QuantityValue> {
+    match (current.get_unit(), event_qty.get_unit()) {
+        (Some(resource_unit), Some(event_unit)) if resource_unit != event_unit => {
+            let from = unit_conversions.get(&event_unit)?
+                .ok_or_else(|| DataIntegrityError::UnregisteredUnitConversion(event_unit.to_string()))?;
+            let to = unit_conversions.get(&resource_unit)?
+                .ok_or_else(|| DataIntegrityError::UnregisteredUnitConversion(resource_unit.to_string()))?;
+            convert(event_qty, &from, Some(resource_unit), &to)
+        },
+        _ => Ok(event_qty),
+    }
+}
+
 fn get_resource_specification(specification_id: ResourceSpecificationAddress) -> RecordAPIResult<ResourceSpecificationResponse> {
     let spec_data: OtherCellResult<ResourceSpecificationResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
         &specification_id,
@@ -280,6 +416,25 @@ impl Updateable<UpdateRequest> for EntryData {
     }
 }
 
+/// Quantities derived by replaying the full event history of a resource.
+/// Internal payload used to reconcile stored balances, never accepted via the external API.
+#[derive(Clone, Debug)]
+pub struct DerivedQuantities {
+    pub accounting_quantity: Option<QuantityValue>,
+    pub onhand_quantity: Option<QuantityValue>,
+}
+
+/// Handles reconciliation of stored quantities with those derived from event history
+impl Updateable<DerivedQuantities> for EntryData {
+    fn update_with(&self, e: DerivedQuantities) -> RecordAPIResult<EntryData> {
+        Ok(EntryData {
+            accounting_quantity: e.accounting_quantity,
+            onhand_quantity: e.onhand_quantity,
+            ..self.to_owned()
+        })
+    }
+}
+
 /// Handle update operations by observed events
 ///
 /// :WARNING: we presume the event has already been determined to relate to the
@@ -288,8 +443,12 @@ impl Updateable<UpdateRequest> for EntryData {
 /// Currently it is only called within `hdk_records::record_helpers::update_record`,
 /// where this check is already implicitly performed.
 ///
-impl Updateable<EventCreateRequest> for EntryData {
-    fn update_with(&self, e: EventCreateRequest) -> RecordAPIResult<EntryData> {
+impl Updateable<InventoryChange<EventCreateRequest>> for EntryData {
+    fn update_with(&self, change: InventoryChange<EventCreateRequest>) -> RecordAPIResult<EntryData> {
+        let InventoryChange { params: e, policy } = change;
+        check_resource_specification(&policy, &self.conforms_to, &e.resource_conforms_to)?;
+        let mut unit_conversions = UnitConversions::default();
+
         Ok(EntryData {
             name: self.name.to_owned(),
             conforms_to: self.conforms_to.to_owned(),
@@ -322,6 +481,8 @@ impl Updateable<EventCreateRequest> for EntryData {
                     Some(inventory_type) => inventory_type.to_owned(),
                     None => panic!("Developer error: EconomicEvent inventory type must be provided when updating EconomicResource!"),
                 },
+                &policy,
+                &mut unit_conversions,
             )?,
             onhand_quantity: update_quantity(
                 self.onhand_quantity.to_owned(), e.resource_quantity.to_owned(),
@@ -329,9 +490,11 @@ impl Updateable<EventCreateRequest> for EntryData {
                     Some(inventory_type) => inventory_type.to_owned(),
                     None => panic!("Developer error: EconomicEvent inventory type must be provided when updating EconomicResource!"),
                 },
+                &policy,
+                &mut unit_conversions,
             )?,
             unit_of_effort: self.unit_of_effort.to_owned(), // :TODO: pull from e.resource_conforms_to.unit_of_effort
-            current_location: if e.get_action() == "move" {
+            current_location: if is_builtin_action(e.get_action(), &["move"]) {
                 if let MaybeUndefined::Some(at_location) = e.get_location() {
                     Some(at_location)
                 } else {
@@ -341,8 +504,9 @@ impl Updateable<EventCreateRequest> for EntryData {
             contained_in: self.contained_in.to_owned(),
             note: self.note.to_owned(),
             // NOTE: this could be "dangerous" in the sense that if not validated properly, this ability to update via events could be abused by third party agents transferring rights and 'ownership' to themselves, from resources currently controlled/owned/stewarded by other agents
+            // DNAs holding resources of value should enable `require_transfer_consent` to guard against this.
             // relates to transfer all rights but not custody
-            primary_accountable: if e.to_resource_inventoried_as.to_owned().is_some() && (e.to_resource_inventoried_as == e.resource_inventoried_as) && (e.get_action() == "transfer" || e.get_action() == "transfer_all_rights") {
+            primary_accountable: if e.to_resource_inventoried_as.to_owned().is_some() && (e.to_resource_inventoried_as == e.resource_inventoried_as) && is_builtin_action(e.get_action(), &["transfer", "transfer-all-rights"]) {
                 Some(e.receiver.to_owned())
             } else {
                 self.primary_accountable.to_owned()
@@ -359,22 +523,72 @@ fn update_quantity(
     action: &ActionId,
     which_qty_type: ResourceValueType,
     which_inventory_type: ResourceInventoryType,
+    policy: &InventoryPolicy,
+    unit_conversions: &mut UnitConversions,
 ) -> RecordAPIResult<Option<QuantityValue>> {
-    if None == current_val {
-        return Ok(None);
-    }
     if MaybeUndefined::None == event_val || MaybeUndefined::Undefined == event_val {
         return Ok(current_val);
     }
-    let current = current_val.unwrap();
     let event_qty = event_val.unwrap();
+    let qty_type_name = which_qty_type.to_string();
 
     let action_to_perform = get_event_action(action, which_qty_type, which_inventory_type);
+    if let ActionInventoryEffect::NoEffect = action_to_perform {
+        return Ok(current_val);
+    }
 
-    match action_to_perform {
-        ActionInventoryEffect::NoEffect => Ok(Some(current)),
-        ActionInventoryEffect::Increment => Ok(Some(add(current, event_qty)?)),
-        ActionInventoryEffect::Decrement => Ok(Some(subtract(current, event_qty)?)),
+    let current = match current_val {
+        Some(current) => current,
+        None if policy.require_units => return Err(DataIntegrityError::MissingInventoryUnit(format!("{} quantity of the EconomicResource", qty_type_name))),
+        None => return Ok(None),
+    };
+    if policy.require_units {
+        if current.get_unit().is_none() {
+            return Err(DataIntegrityError::MissingInventoryUnit(format!("{} quantity of the EconomicResource", qty_type_name)));
+        }
+        if event_qty.get_unit().is_none() {
+            return Err(DataIntegrityError::MissingInventoryUnit("resource quantity of the EconomicEvent".to_string()));
+        }
+    }
+
+    let updated = match action_to_perform {
+        ActionInventoryEffect::NoEffect => return Ok(Some(current)),
+        ActionInventoryEffect::Increment => {
+            let event_qty = to_resource_units(&current, event_qty, unit_conversions)?;
+            add(current, event_qty)?
+        },
+        ActionInventoryEffect::Decrement => {
+            let event_qty = to_resource_units(&current, event_qty, unit_conversions)?;
+            subtract(current, event_qty)?
+        },
+    };
+    let updated = match policy.quantity_scale {
+        Some(scale) => updated.rescale(scale)?,
+        None => updated,
+    };
+
+    // events with negated quantities may also reduce balances when incrementing
+    if policy.reject_negative_balances && updated.get_numerical_value().is_negative() {
+        return Err(DataIntegrityError::NegativeInventoryBalance(qty_type_name, updated.get_numerical_value().to_string()));
+    }
+    Ok(Some(updated))
+}
+
+/// Where `require_matching_specification` is enabled, events which specify a `resource_conforms_to`
+/// may only affect resources conforming to the same `ResourceSpecification`.
+fn check_resource_specification(
+    policy: &InventoryPolicy,
+    resource_conforms_to: &Option<ResourceSpecificationAddress>,
+    event_conforms_to: &MaybeUndefined<ResourceSpecificationAddress>,
+) -> RecordAPIResult<()> {
+    match event_conforms_to {
+        MaybeUndefined::Some(event_spec) if policy.require_matching_specification && resource_conforms_to.as_ref() != Some(event_spec) => {
+            Err(DataIntegrityError::MismatchingResourceSpecification(
+                event_spec.to_string(),
+                resource_conforms_to.as_ref().map(|spec| spec.to_string()),
+            ))
+        },
+        _ => Ok(()),
     }
 }
 
@@ -383,6 +597,15 @@ enum ResourceValueType {
     OnhandValue,
 }
 
+impl std::fmt::Display for ResourceValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResourceValueType::AccountingValue => write!(f, "accounting"),
+            ResourceValueType::OnhandValue => write!(f, "onhand"),
+        }
+    }
+}
+
 /// Determines the `ActionInventoryEffect` to apply to a resource, based on the input event
 /// action type, the type of inventory quantity ("accounting" or "on hand"),
 /// and the side of the event that the resource is on (providing or receiving).
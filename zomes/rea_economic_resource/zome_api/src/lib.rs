@@ -18,6 +18,8 @@ pub trait API {
     fn get_economic_resource(address: EconomicResourceAddress) -> RecordAPIResult<ResponseData>;
     fn get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>;
     fn update_economic_resource(resource: UpdateRequest) -> RecordAPIResult<ResponseData>;
+    fn recalculate_economic_resource(address: EconomicResourceAddress, write: bool) -> RecordAPIResult<RecalculationResponse>;
+    fn economic_resource_as_of(address: EconomicResourceAddress, timestamp: DateTime<FixedOffset>) -> RecordAPIResult<ResourceBalanceResponse>;
 }
 
 /// Macro to programatically and predictably bind an `API` implementation to a
@@ -60,5 +62,15 @@ macro_rules! declare_economic_resource_zome_api {
         fn update_economic_resource(UpdateParams { resource }: UpdateParams) -> ExternResult<$crate::ResponseData> {
             Ok(<$zome_api>::update_economic_resource(resource)?)
         }
+
+        #[hdk_extern]
+        fn recalculate_economic_resource(RecalculationParams { address, write }: RecalculationParams) -> ExternResult<RecalculationResponse> {
+            Ok(<$zome_api>::recalculate_economic_resource(address, write)?)
+        }
+
+        #[hdk_extern]
+        fn economic_resource_as_of(AsOfParams { address, timestamp }: AsOfParams) -> ExternResult<ResourceBalanceResponse> {
+            Ok(<$zome_api>::economic_resource_as_of(address, timestamp)?)
+        }
     };
 }
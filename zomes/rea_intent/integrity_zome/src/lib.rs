@@ -8,6 +8,7 @@
  * @package hREA
  */
 use hdi::prelude::*;
+use hdk_records::validation::{ validate_update_authorship, validate_delete_authorship };
 use hc_zome_rea_intent_storage::{
     EntryStorage, EntryTypes, EntryTypesUnit, Identified, LinkTypes,
 };
@@ -38,8 +39,8 @@ fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
         Op::StoreEntry(StoreEntry { entry, .. }) => validate_entry(entry),
         Op::RegisterCreateLink { .. } => Ok(ValidateCallbackResult::Valid),
         Op::RegisterDeleteLink { .. } => Ok(ValidateCallbackResult::Valid),
-        Op::RegisterUpdate { .. } => Ok(ValidateCallbackResult::Valid),
-        Op::RegisterDelete { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterUpdate(update) => validate_update_authorship(&update),
+        Op::RegisterDelete(delete) => validate_delete_authorship(&delete),
         Op::RegisterAgentActivity { .. } => Ok(ValidateCallbackResult::Valid),
     }
 }
@@ -9,6 +9,7 @@
 use paste::paste;
 use hdk_records::{
     RecordAPIResult, MaybeUndefined, SignedActionHashed,
+    rpc::call_local_zome_method,
     records::{
         create_record,
         read_record_entry,
@@ -19,6 +20,7 @@ use hdk_records::{
     metadata::read_revision_metadata_abbreviated,
 };
 use hdk_semantic_indexes_client_lib::*;
+use vf_measurement::QuantityValue;
 
 use hc_zome_rea_intent_storage::*;
 use hc_zome_rea_intent_rpc::*;
@@ -50,6 +52,14 @@ pub fn handle_create_intent<S>(entry_def_id: S, intent: CreateRequest) -> Record
         let e = create_index!(intent.output_of(output_of), process.intended_outputs(&base_address));
         hdk::prelude::debug!("handle_create_intent::output_of index {:?}", e);
     };
+    if let CreateRequest { resource_conforms_to: MaybeUndefined::Some(resource_conforms_to), .. } = &intent {
+        let e = create_index!(intent.resource_conforms_to(resource_conforms_to), resource_specification.intents(&base_address));
+        hdk::prelude::debug!("handle_create_intent::resource_conforms_to index {:?}", e);
+    };
+    if let CreateRequest { in_scenario: MaybeUndefined::Some(in_scenario), .. } = &intent {
+        let e = create_index!(intent.in_scenario(in_scenario), scenario.intents(&base_address));
+        hdk::prelude::debug!("handle_create_intent::in_scenario index {:?}", e);
+    };
 
     // return entire record structure
     construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
@@ -115,6 +125,28 @@ pub fn handle_update_intent(intent: UpdateRequest) -> RecordAPIResult<ResponseDa
         );
         hdk::prelude::debug!("handle_update_intent::output_of index {:?}", e);
     }
+    if new_entry.resource_conforms_to != prev_entry.resource_conforms_to {
+        let new_value = match &new_entry.resource_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.resource_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            intent
+                .resource_conforms_to(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            resource_specification.intents(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_intent::resource_conforms_to index {:?}", e);
+    }
+    if new_entry.in_scenario != prev_entry.in_scenario {
+        let new_value = match &new_entry.in_scenario { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.in_scenario { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            intent
+                .in_scenario(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            scenario.intents(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_intent::in_scenario index {:?}", e);
+    }
 
     construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
 }
@@ -141,6 +173,14 @@ pub fn handle_delete_intent(revision_id: ActionHash) -> RecordAPIResult<bool>
         let e = update_index!(intent.receiver.not(&vec![agent_address]), process.intents_as_receiver(&base_address));
         hdk::prelude::debug!("handle_delete_intent::receiver index {:?}", e);
     }
+    if let Some(resource_specification_address) = entry.resource_conforms_to {
+        let e = update_index!(intent.resource_conforms_to.not(&vec![resource_specification_address]), resource_specification.intents(&base_address));
+        hdk::prelude::debug!("handle_delete_intent::resource_conforms_to index {:?}", e);
+    }
+    if let Some(scenario_address) = entry.in_scenario {
+        let e = update_index!(intent.in_scenario.not(&vec![scenario_address]), scenario.intents(&base_address));
+        hdk::prelude::debug!("handle_delete_intent::in_scenario index {:?}", e);
+    }
 
     // delete entry last, as it must be present in order for links to be removed
     delete_record::<EntryStorage>(&revision_id)
@@ -182,6 +222,7 @@ pub fn construct_response<'a>(
             agreed_in: e.agreed_in.to_owned(),
             finished: e.finished.to_owned(),
             in_scope_of: e.in_scope_of.to_owned(),
+            in_scenario: e.in_scenario.to_owned(),
             satisfied_by: satisfactions.to_owned(),
             // published_in: published_in.to_owned(),
         }
@@ -205,6 +246,16 @@ fn read_agent_index_zome(conf: DnaConfigSlice) -> Option<String> {
     conf.intent.agent_index_zome
 }
 
+/// Properties accessor for zome config
+fn read_resource_specification_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.intent.resource_specification_index_zome
+}
+
+/// Properties accessor for zome config
+fn read_scenario_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.intent.scenario_index_zome
+}
+
 // @see construct_response
 pub fn get_link_fields(intent: &IntentAddress) -> RecordAPIResult<(
     Vec<SatisfactionAddress>,
@@ -213,3 +264,182 @@ pub fn get_link_fields(intent: &IntentAddress) -> RecordAPIResult<(
         read_index!(intent(intent).satisfied_by)?,
     ))
 }
+
+//---------------- MATCHING ----------------
+
+const QUERY_INTENTS_API_METHOD: &str = "query_intents";
+const READ_ALL_INTENTS_API_METHOD: &str = "read_all_intents";
+const INTENT_QUERY_PAGE_SIZE: usize = 100;
+
+/// Intents are not indexed by classification, so matching an intent with no `resource_conforms_to`
+/// scans all intents in the DNA. Only this many of the most recent intents are considered.
+const MAX_UNSPECIFIED_MATCH_CANDIDATES: usize = 500;
+
+/// Which side of a potential trade an open intent represents
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum IntentRole {
+    Offer,
+    Request,
+}
+
+/// Find open intents which could form the other side of a trade with the given intent, best matches first.
+///
+/// Candidates must share the intent's action, describe the same kind of resource, take the opposite
+/// provider / receiver role, overlap in time and measure quantities in the same units.
+///
+pub fn handle_match_intent(request: MatchIntentRequest) -> RecordAPIResult<MatchResponseData>
+{
+    let MatchIntentRequest { address, limit } = request;
+    let target = handle_get_intent(address)?.intent;
+
+    let role = match intent_role(&target) {
+        Some(role) if !target.finished => role,
+        _ => return Ok(MatchResponseData { matches: vec![] }),
+    };
+
+    // intents conforming to a specification only match others of the same specification
+    let candidates = match &target.resource_conforms_to {
+        Some(resource_conforms_to) => query_intents(QueryParams {
+            input_of: None,
+            output_of: None,
+            satisfied_by: None,
+            proposed_in: None,
+            provider: None,
+            receiver: None,
+            resource_conforms_to: Some(resource_conforms_to.to_owned()),
+            in_scenario: None,
+        })?,
+        // unspecified resources may match by classification against anything recent
+        None => read_latest_intents()?,
+    };
+
+    let mut matches: Vec<IntentMatch> = candidates.into_iter()
+        .filter(|candidate| candidate.id != target.id && !candidate.finished)
+        .filter(|candidate| candidate.action == target.action)
+        .filter(|candidate| intent_role(candidate).map_or(false, |r| r != role))
+        .filter_map(|candidate| {
+            let score = match_score(&target, &candidate)?;
+            Some(IntentMatch { intent: candidate, score })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    if let Some(limit) = limit {
+        matches.truncate(limit);
+    }
+
+    Ok(MatchResponseData { matches })
+}
+
+/// Open intents specify only one of their provider or receiver, the other being left for a counterparty
+fn intent_role(intent: &Response) -> Option<IntentRole> {
+    match (&intent.provider, &intent.receiver) {
+        (Some(_), None) => Some(IntentRole::Offer),
+        (None, Some(_)) => Some(IntentRole::Request),
+        _ => None,
+    }
+}
+
+/// Score the fit of `candidate` against `target`, or `None` if the two are incompatible.
+/// Resource, quantity and time fit are each scored from 0 to 1 and averaged.
+///
+fn match_score(target: &Response, candidate: &Response) -> Option<f64> {
+    let resource_fit = resource_fit(target, candidate)?;
+    let time_fit = time_fit(target, candidate)?;
+
+    if !units_compatible(&target.effort_quantity, &candidate.effort_quantity) {
+        return None;
+    }
+    let quantity_fit = quantity_fit(&offered_quantity(target), &offered_quantity(candidate))?;
+
+    Some((resource_fit + quantity_fit + time_fit) / 3.0)
+}
+
+/// Intents conforming to the same specification are an exact fit; otherwise
+/// fit is the proportion of shared classifications.
+fn resource_fit(target: &Response, candidate: &Response) -> Option<f64> {
+    if let (Some(a), Some(b)) = (&target.resource_conforms_to, &candidate.resource_conforms_to) {
+        return if a == b { Some(1.0) } else { None };
+    }
+
+    let (a, b) = match (&target.resource_classified_as, &candidate.resource_classified_as) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return None,
+    };
+    let shared = a.iter().filter(|c| b.contains(c)).count();
+    if shared == 0 {
+        return None;
+    }
+    let total = a.len() + b.iter().filter(|c| !a.contains(c)).count();
+
+    Some(shared as f64 / total as f64)
+}
+
+/// Offers may advertise a larger stock than any single trade through `available_quantity`
+fn offered_quantity(intent: &Response) -> Option<QuantityValue> {
+    intent.available_quantity.to_owned().or_else(|| intent.resource_quantity.to_owned())
+}
+
+fn units_compatible(a: &Option<QuantityValue>, b: &Option<QuantityValue>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => match (a.get_unit(), b.get_unit()) {
+            (Some(unit_a), Some(unit_b)) => unit_a == unit_b,
+            _ => true,
+        },
+        _ => true,
+    }
+}
+
+/// Ratio of the smaller quantity to the larger; unknown quantities are a neutral fit
+fn quantity_fit(a: &Option<QuantityValue>, b: &Option<QuantityValue>) -> Option<f64> {
+    if !units_compatible(a, b) {
+        return None;
+    }
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            let (a, b) = (a.get_numerical_value().abs(), b.get_numerical_value().abs());
+            if a.is_zero() || b.is_zero() {
+                return Some(0.0);
+            }
+            Some(a.min(b).to_f64() / a.max(b).to_f64())
+        },
+        _ => Some(0.5),
+    }
+}
+
+/// Time window covered by an intent, either bound of which may be open-ended
+fn time_window(intent: &Response) -> (Option<DateTime<FixedOffset>>, Option<DateTime<FixedOffset>>) {
+    let start = intent.has_beginning.or(intent.has_point_in_time);
+    let end = intent.has_end.or(intent.due).or(intent.has_point_in_time);
+    (start, end)
+}
+
+/// Windows which overlap are a full fit; open-ended windows are assumed to overlap, but fit less well
+fn time_fit(target: &Response, candidate: &Response) -> Option<f64> {
+    let (a_start, a_end) = time_window(target);
+    let (b_start, b_end) = time_window(candidate);
+
+    if let (Some(a_start), Some(b_end)) = (a_start, b_end) {
+        if a_start > b_end { return None; }
+    }
+    if let (Some(b_start), Some(a_end)) = (b_start, a_end) {
+        if b_start > a_end { return None; }
+    }
+
+    let bounded = [a_start, a_end, b_start, b_end].iter().filter(|t| t.is_some()).count();
+    Some(0.5 + bounded as f64 / 8.0)
+}
+
+/// Read every page of `Intent`s matching `params`
+fn query_intents(params: QueryParams) -> RecordAPIResult<Vec<Response>> {
+    Ok(read_all_pages(Some(params), INTENT_QUERY_PAGE_SIZE, |inputs| call_local_zome_method(
+        read_intent_index_zome, QUERY_INTENTS_API_METHOD, inputs,
+    ))?)
+}
+
+/// Read the most recent `Intent`s in the DNA, up to `MAX_UNSPECIFIED_MATCH_CANDIDATES`
+fn read_latest_intents() -> RecordAPIResult<Vec<Response>> {
+    Ok(read_pages(None::<QueryParams>, INTENT_QUERY_PAGE_SIZE, MAX_UNSPECIFIED_MATCH_CANDIDATES, |inputs| call_local_zome_method(
+        read_intent_index_zome, READ_ALL_INTENTS_API_METHOD, inputs,
+    ))?)
+}
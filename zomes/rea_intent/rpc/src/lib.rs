@@ -21,6 +21,7 @@ pub use vf_attributes_hdk::{
     SatisfactionAddress,
     LocationAddress,
     ProposedIntentAddress,
+    ScenarioAddress,
     ActionHash, ByAction, ByRevision, RecordMeta, RevisionMeta,
 };
 
@@ -76,6 +77,8 @@ pub struct Response {
     pub agreed_in: Option<ExternalURL>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub in_scope_of: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_scenario: Option<ScenarioAddress>,
     pub finished: bool,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -142,6 +145,8 @@ pub struct CreateRequest {
     pub finished: MaybeUndefined<bool>,
     #[serde(default)]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
+    #[serde(default)]
+    pub in_scenario: MaybeUndefined<ScenarioAddress>,
 }
 
 impl<'a> CreateRequest {
@@ -198,6 +203,8 @@ pub struct UpdateRequest {
     pub finished: MaybeUndefined<bool>,
     #[serde(default)]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
+    #[serde(default)]
+    pub in_scenario: MaybeUndefined<ScenarioAddress>,
 }
 
 impl<'a> UpdateRequest {
@@ -219,4 +226,34 @@ pub struct QueryParams {
     pub proposed_in: Option<ProposedIntentAddress>,
     pub provider: Option<AgentAddress>,
     pub receiver: Option<AgentAddress>,
+    pub resource_conforms_to: Option<ResourceSpecificationAddress>,
+    pub in_scenario: Option<ScenarioAddress>,
+}
+
+//---------------- MATCHING ----------------
+
+/// Request to find open intents complementary to an existing one
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchIntentRequest {
+    pub address: IntentAddress,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// A candidate intent which could satisfy the other side of a trade,
+/// scored between 0 (barely compatible) and 1 (exact fit)
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IntentMatch {
+    pub intent: Response,
+    pub score: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchResponseData {
+    pub matches: Vec<IntentMatch>,
 }
@@ -27,6 +27,7 @@ pub use vf_attributes_hdk::{
     EconomicResourceAddress,
     ProcessAddress,
     ResourceSpecificationAddress,
+    ScenarioAddress,
 };
 
 use vf_actions::{ validate_flow_action };
@@ -49,6 +50,8 @@ pub struct IntentZomeConfig {
     pub index_zome: String,
     pub process_index_zome: Option<String>,
     pub agent_index_zome: Option<String>,
+    pub resource_specification_index_zome: Option<String>,
+    pub scenario_index_zome: Option<String>,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -74,6 +77,7 @@ pub struct EntryData {
     pub agreed_in: Option<ExternalURL>,
     pub finished: bool,
     pub in_scope_of: Option<Vec<String>>,
+    pub in_scenario: Option<ScenarioAddress>,
     pub image: Option<ExternalURL>,
     pub note: Option<String>,
     pub _nonce: Bytes,
@@ -157,6 +161,7 @@ impl TryFrom<CreateRequest> for EntryData {
             agreed_in: e.agreed_in.to_owned().into(),
             finished: e.finished.to_option().unwrap(),  // :NOTE: unsafe, would crash if not for "default_false" binding via Serde
             in_scope_of: e.in_scope_of.to_owned().into(),
+            in_scenario: e.in_scenario.into(),
             _nonce: random_bytes(32)?,
         })
     }
@@ -187,6 +192,7 @@ impl Updateable<UpdateRequest> for EntryData {
             agreed_in: if e.agreed_in == MaybeUndefined::Undefined { self.agreed_in.to_owned() } else { e.agreed_in.to_owned().into() },
             finished: if e.finished == MaybeUndefined::Undefined { self.finished.to_owned() } else { e.finished.to_owned().to_option().unwrap() },
             in_scope_of: if e.in_scope_of== MaybeUndefined::Undefined { self.in_scope_of.to_owned() } else { e.in_scope_of.to_owned().into() },
+            in_scenario: if e.in_scenario == MaybeUndefined::Undefined { self.in_scenario.to_owned() } else { e.in_scenario.to_owned().into() },
             image: if e.image== MaybeUndefined::Undefined { self.image.to_owned() } else { e.image.to_owned().into() },
             note: if e.note== MaybeUndefined::Undefined { self.note.to_owned() } else { e.note.to_owned().into() },
             _nonce: self._nonce.to_owned(),
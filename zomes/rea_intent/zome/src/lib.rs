@@ -42,6 +42,11 @@ fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<Response
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn match_intent(request: MatchIntentRequest) -> ExternResult<MatchResponseData> {
+    Ok(handle_match_intent(request)?)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct UpdateParams {
@@ -17,4 +17,6 @@ struct Intent {
     // internal indexes (not part of VF spec)
     provider: Local<agent, intents_as_provider>,
     receiver: Local<agent, intents_as_receiver>,
+    resource_conforms_to: Local<resource_specification, intents>,
+    in_scenario: Local<scenario, intents>,
 }
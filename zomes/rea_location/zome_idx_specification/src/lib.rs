@@ -0,0 +1,15 @@
+/**
+ * Location query indexes for specification DNA
+ *
+ * @package hREA
+ * @since   2026-10-18
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_location_rpc::*;
+
+#[index_zome]
+struct Location {
+    economic_events: Remote<economic_event, at_location>,
+    commitments: Remote<commitment, at_location>,
+    economic_resources: Remote<economic_resource, current_location>,
+}
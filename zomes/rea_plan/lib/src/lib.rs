@@ -7,8 +7,11 @@
  * @package hREA
  */
 use paste::paste;
+use serde::Serialize;
+use chrono::Duration as ChronoDuration;
 use hdk_records::{
-    RecordAPIResult, SignedActionHashed,
+    RecordAPIResult, DataIntegrityError, OtherCellResult,
+    MaybeUndefined, SignedActionHashed,
     records::{
         create_record,
         read_record_entry,
@@ -17,11 +20,36 @@ use hdk_records::{
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    rpc::{call_zome_method, CellDna},
 };
 use hdk_semantic_indexes_client_lib::*;
+use vf_attributes_hdk::{ByAddress, RecipeResourceAddress, RecipeProcessAddress, RecipeFlowAddress};
+use vf_measurement::{QuantityValue, Decimal, multiply};
+use vf_actions::{ActionEffect, get_builtin_action};
 
 use hc_zome_rea_plan_storage::*;
 use hc_zome_rea_plan_rpc::*;
+use hc_zome_rea_recipe_resource_rpc::{
+    ResponseData as RecipeResourceResponseData,
+    Response as RecipeResourceResponse,
+    QueryParams as RecipeResourceQueryParams,
+};
+use hc_zome_rea_recipe_process_rpc::{
+    ResponseData as RecipeProcessResponseData,
+    Response as RecipeProcessResponse,
+};
+use hc_zome_rea_recipe_flow_rpc::{
+    ResponseData as RecipeFlowResponseData,
+    Response as RecipeFlowResponse,
+};
+use hc_zome_rea_process_rpc::{
+    CreateRequest as ProcessCreateRequest,
+    ResponseData as ProcessResponseData,
+};
+use hc_zome_rea_commitment_rpc::{
+    CreateRequest as CommitmentCreateRequest,
+    ResponseData as CommitmentResponseData,
+};
 
 
 pub use hc_zome_rea_plan_storage::PLAN_ENTRY_TYPE;
@@ -35,6 +63,13 @@ pub fn handle_create_plan<S>(entry_def_id: S, plan: CreateRequest) -> RecordAPIR
     where S: AsRef<str> + std::fmt::Display
 {
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, plan)?;
+
+    // handle link fields
+    if let Some(refinement_of) = &entry_resp.refinement_of {
+        let e = create_index!(plan.refinement_of(refinement_of), scenario.plans(&base_address));
+        hdk::prelude::debug!("handle_create_plan::refinement_of index {:?}", e);
+    }
+
     construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
 }
 
@@ -53,11 +88,34 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
 pub fn handle_update_plan(plan: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
     let revision_hash = plan.get_revision_id().clone();
-    let (meta, identity_address, entry, _prev_entry): (_,_, EntryData, EntryData) = update_record(&revision_hash, plan)?;
+    let (meta, identity_address, entry, prev_entry): (_,_, EntryData, EntryData) = update_record(&revision_hash, plan)?;
+
+    // handle link fields
+    if entry.refinement_of != prev_entry.refinement_of {
+        let new_value = match &entry.refinement_of { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.refinement_of { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            plan
+                .refinement_of(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            scenario.plans(&identity_address)
+        );
+        hdk::prelude::debug!("handle_update_plan::refinement_of index {:?}", e);
+    }
+
     construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)
 }
 
 pub fn handle_delete_plan(address: ActionHash) -> RecordAPIResult<bool> {
+    // load the record to ensure it is of the correct type
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&address)?;
+
+    // handle link fields
+    if let Some(scenario_address) = entry.refinement_of {
+        let e = update_index!(plan.refinement_of.not(&vec![scenario_address]), scenario.plans(&base_address));
+        hdk::prelude::debug!("handle_delete_plan::refinement_of index {:?}", e);
+    }
+
     delete_record::<EntryStorage>(&address)
 }
 
@@ -81,6 +139,7 @@ fn construct_response<'a>(
             due: e.due.to_owned(),
             note: e.note.to_owned(),
             deletable: e.deletable.to_owned(),
+            refinement_of: e.refinement_of.to_owned(),
             processes: processes.to_owned(),
             independent_demands: independent_demands.to_owned(),
         }
@@ -94,6 +153,11 @@ fn read_plan_index_zome(conf: DnaConfigSlice) -> Option<String> {
     Some(conf.plan.index_zome)
 }
 
+/// Properties accessor for zome config
+fn read_scenario_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.plan.scenario_index_zome
+}
+
 // @see construct_response
 fn get_link_fields(base_address: &PlanAddress) -> RecordAPIResult<(
     Vec<ProcessAddress>,
@@ -104,3 +168,269 @@ fn get_link_fields(base_address: &PlanAddress) -> RecordAPIResult<(
         read_index!(plan(base_address).independent_demands)?,
     ))
 }
+
+//---------------- PLAN GENERATION ----------------
+
+const QUERY_RECIPE_RESOURCES_API_METHOD: &str = "query_recipe_resources";
+const READ_RECIPE_RESOURCE_API_METHOD: &str = "read_recipe_resource";
+const READ_RECIPE_PROCESS_API_METHOD: &str = "read_recipe_process";
+const READ_RECIPE_FLOW_API_METHOD: &str = "read_recipe_flow";
+const CREATE_PROCESS_API_METHOD: &str = "create_process";
+const CREATE_COMMITMENT_API_METHOD: &str = "create_commitment";
+
+const RECIPE_QUERY_PAGE_SIZE: usize = 100;
+
+/// Maximum number of recipe processes chained together when exploding a single demand,
+/// to guard against runaway generation from malformed recipes.
+const MAX_RECIPE_DEPTH: usize = 20;
+
+/// Generate a new `Plan` by exploding recipes backwards from some desired output.
+///
+/// A `Process` is created for each recipe process required, with input & output `Commitment`s
+/// scaled by the requested quantity and scheduled backwards from `due`. Consumed inputs which
+/// themselves have a recipe generate further upstream processes due before their consumer begins.
+///
+pub fn handle_generate_plan<S>(entry_def_id: S, request: GeneratePlanRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    let GeneratePlanRequest { plan, resource_conforms_to, resource_quantity, due, agent, cells } = request;
+
+    // check the demand can be satisfied before writing anything
+    let recipe = find_recipe(&cells, &resource_conforms_to)?
+        .ok_or_else(|| DataIntegrityError::RecipeNotFound(resource_conforms_to.to_string()))?;
+
+    let ResponseData { plan: Response { id: plan_address, refinement_of: scenario, .. } } = handle_create_plan(&entry_def_id, plan)?;
+    let context = PlanGenerationContext { plan: plan_address.to_owned(), scenario, agent, cells };
+
+    explode_recipe(&context, &recipe, &resource_quantity, &due, true, &mut vec![])?;
+
+    handle_get_plan(plan_address)
+}
+
+struct PlanGenerationContext {
+    plan: PlanAddress,
+    scenario: Option<ScenarioAddress>,
+    agent: AgentAddress,
+    cells: PlanGenerationCells,
+}
+
+/// A recipe process, along with the recipe flow by which it outputs some demanded resource
+struct RecipeStep {
+    process: RecipeProcessResponse,
+    output: RecipeFlowResponse,
+}
+
+/// Create the process & commitments needed to produce `quantity` of the output of `recipe` by `due`,
+/// then recurse into recipes for any consumed inputs.
+///
+/// `visited` holds the recipe processes in the current chain of demand, so that cyclic recipes terminate.
+///
+fn explode_recipe(
+    context: &PlanGenerationContext,
+    recipe: &RecipeStep,
+    quantity: &QuantityValue,
+    due: &DateTime<FixedOffset>,
+    is_independent_demand: bool,
+    visited: &mut Vec<RecipeProcessAddress>,
+) -> RecordAPIResult<()> {
+    if visited.len() >= MAX_RECIPE_DEPTH || visited.contains(&recipe.process.id) {
+        hdk::prelude::debug!("explode_recipe::skipping cyclic or excessively deep recipe {:?}", recipe.process.id);
+        return Ok(());
+    }
+    visited.push(recipe.process.id.to_owned());
+
+    let recipe_dna = CellDna(&context.cells.recipe);
+    let scale = scaling_factor(quantity, &recipe.output.resource_quantity)?;
+
+    let has_end = due.to_owned();
+    let has_beginning = match &recipe.process.has_duration {
+        Some(duration) => has_end - ChronoDuration::seconds(duration.as_seconds().round() as i64),
+        None => has_end,
+    };
+
+    let process = create_process(context, &recipe.process, &has_beginning, &has_end)?;
+
+    for flow_address in recipe.process.recipe_outputs.iter() {
+        let flow = read_recipe_flow(&recipe_dna, flow_address)?;
+        let resource = read_recipe_resource(&recipe_dna, &flow.recipe_flow_resource)?;
+        let independent_demand_of = if is_independent_demand && flow.id == recipe.output.id { Some(context.plan.to_owned()) } else { None };
+
+        create_commitment(context, &flow, &resource, scale, (None, Some(&process)), &has_end, independent_demand_of)?;
+    }
+
+    for flow_address in recipe.process.recipe_inputs.iter() {
+        let flow = read_recipe_flow(&recipe_dna, flow_address)?;
+        let resource = read_recipe_resource(&recipe_dna, &flow.recipe_flow_resource)?;
+
+        create_commitment(context, &flow, &resource, scale, (Some(&process), None), &has_beginning, None)?;
+
+        // only consumed inputs generate dependent demand; used, cited & worked inputs are assumed available
+        if !is_consumed(&flow) {
+            continue;
+        }
+        if let (Some(resource_conforms_to), Some(input_quantity)) = (&resource.resource_conforms_to, &flow.resource_quantity) {
+            if let Some(upstream) = find_recipe(&context.cells, resource_conforms_to)? {
+                explode_recipe(context, &upstream, &scale_quantity(input_quantity, scale)?, &has_beginning, false, visited)?;
+            }
+        }
+    }
+
+    visited.pop();
+    Ok(())
+}
+
+fn is_consumed(flow: &RecipeFlowResponse) -> bool {
+    match get_builtin_action(flow.action.as_ref()) {
+        Some(action) => action.accounting_effect == ActionEffect::Decrement,
+        None => false,
+    }
+}
+
+/// Determine the multiple of a recipe's output needed to produce the `requested` quantity
+fn scaling_factor(requested: &QuantityValue, recipe_output: &Option<QuantityValue>) -> RecordAPIResult<Decimal> {
+    match recipe_output {
+        Some(output) => {
+            if requested.get_unit().is_some() && output.get_unit().is_some() && requested.get_unit() != output.get_unit() {
+                return Err(DataIntegrityError::MismatchingUnits(requested.get_unit().map(|unit| unit.1), output.get_unit().map(|unit| unit.1)));
+            }
+            if output.get_numerical_value().is_zero() {
+                return Ok(Decimal::one());
+            }
+            requested.get_numerical_value().checked_div(&output.get_numerical_value())
+        },
+        None => Ok(Decimal::one()),
+    }
+}
+
+fn scale_quantity(quantity: &QuantityValue, scale: Decimal) -> RecordAPIResult<QuantityValue> {
+    multiply(quantity.to_owned(), scale)
+}
+
+/// Locate a recipe process which outputs resources conforming to the given `ResourceSpecification`.
+/// Where several recipes produce the same resource, the most recently defined is used.
+///
+fn find_recipe(cells: &PlanGenerationCells, resource_conforms_to: &ResourceSpecificationAddress) -> RecordAPIResult<Option<RecipeStep>> {
+    let recipe_dna = CellDna(&cells.recipe);
+    let params = RecipeResourceQueryParams {
+        resource_conforms_to: Some(resource_conforms_to.to_owned()),
+        recipe_flows: None,
+    };
+
+    for recipe_resource in query_recipe_resources(&recipe_dna, params)? {
+        for flow_address in recipe_resource.recipe_flows.iter() {
+            let output = read_recipe_flow(&recipe_dna, flow_address)?;
+            if let Some(process_address) = &output.recipe_output_of {
+                let process = read_recipe_process(&recipe_dna, process_address)?;
+                return Ok(Some(RecipeStep { process, output }));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Read every page of `RecipeResource`s matching `params`, from most recent to oldest
+fn query_recipe_resources(recipe_dna: &CellDna, params: RecipeResourceQueryParams) -> RecordAPIResult<Vec<RecipeResourceResponse>> {
+    Ok(read_all_pages(Some(params), RECIPE_QUERY_PAGE_SIZE, |inputs| call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        recipe_dna, &QUERY_RECIPE_RESOURCES_API_METHOD, inputs, LinkTypes::AvailableCapability,
+    ))?)
+}
+
+fn read_recipe_resource(recipe_dna: &CellDna, address: &RecipeResourceAddress) -> RecordAPIResult<RecipeResourceResponse> {
+    let resp: OtherCellResult<RecipeResourceResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        recipe_dna, &READ_RECIPE_RESOURCE_API_METHOD, ByAddress { address: address.to_owned() }, LinkTypes::AvailableCapability,
+    );
+    Ok(resp?.recipe_resource)
+}
+
+fn read_recipe_process(recipe_dna: &CellDna, address: &RecipeProcessAddress) -> RecordAPIResult<RecipeProcessResponse> {
+    let resp: OtherCellResult<RecipeProcessResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        recipe_dna, &READ_RECIPE_PROCESS_API_METHOD, ByAddress { address: address.to_owned() }, LinkTypes::AvailableCapability,
+    );
+    Ok(resp?.recipe_process)
+}
+
+fn read_recipe_flow(recipe_dna: &CellDna, address: &RecipeFlowAddress) -> RecordAPIResult<RecipeFlowResponse> {
+    let resp: OtherCellResult<RecipeFlowResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        recipe_dna, &READ_RECIPE_FLOW_API_METHOD, ByAddress { address: address.to_owned() }, LinkTypes::AvailableCapability,
+    );
+    Ok(resp?.recipe_flow)
+}
+
+#[derive(Debug, Serialize)]
+struct CreateProcessParams {
+    pub process: ProcessCreateRequest,
+}
+
+fn create_process(
+    context: &PlanGenerationContext,
+    recipe_process: &RecipeProcessResponse,
+    has_beginning: &DateTime<FixedOffset>,
+    has_end: &DateTime<FixedOffset>,
+) -> RecordAPIResult<ProcessAddress> {
+    let process = ProcessCreateRequest {
+        name: recipe_process.name.to_owned(),
+        has_beginning: MaybeUndefined::Some(has_beginning.to_owned()),
+        has_end: MaybeUndefined::Some(has_end.to_owned()),
+        before: MaybeUndefined::Undefined,
+        after: MaybeUndefined::Undefined,
+        classified_as: recipe_process.process_classified_as.to_owned().into(),
+        based_on: recipe_process.process_conforms_to.to_owned().into(),
+        planned_within: MaybeUndefined::Some(context.plan.to_owned()),
+        finished: MaybeUndefined::Some(false),
+        in_scope_of: MaybeUndefined::Undefined,
+        in_scenario: context.scenario.to_owned().into(),
+        note: recipe_process.note.to_owned().into(),
+    };
+
+    let resp: OtherCellResult<ProcessResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        &CellDna(&context.cells.observation), &CREATE_PROCESS_API_METHOD, CreateProcessParams { process }, LinkTypes::AvailableCapability,
+    );
+    Ok(resp?.process.id)
+}
+
+#[derive(Debug, Serialize)]
+struct CreateCommitmentParams {
+    pub commitment: CommitmentCreateRequest,
+}
+
+fn create_commitment(
+    context: &PlanGenerationContext,
+    flow: &RecipeFlowResponse,
+    resource: &RecipeResourceResponse,
+    scale: Decimal,
+    (input_of, output_of): (Option<&ProcessAddress>, Option<&ProcessAddress>),
+    due: &DateTime<FixedOffset>,
+    independent_demand_of: Option<PlanAddress>,
+) -> RecordAPIResult<CommitmentAddress> {
+    let commitment = CommitmentCreateRequest {
+        action: flow.action.to_owned(),
+        note: flow.note.to_owned().into(),
+        input_of: input_of.cloned().into(),
+        output_of: output_of.cloned().into(),
+        provider: context.agent.to_owned(),
+        receiver: context.agent.to_owned(),
+        resource_inventoried_as: MaybeUndefined::Undefined,
+        resource_classified_as: resource.resource_classified_as.to_owned().into(),
+        resource_conforms_to: resource.resource_conforms_to.to_owned().into(),
+        resource_quantity: flow.resource_quantity.as_ref().map(|q| scale_quantity(q, scale)).transpose()?.into(),
+        effort_quantity: flow.effort_quantity.as_ref().map(|q| scale_quantity(q, scale)).transpose()?.into(),
+        has_beginning: MaybeUndefined::Undefined,
+        has_end: MaybeUndefined::Undefined,
+        has_point_in_time: MaybeUndefined::Undefined,
+        due: MaybeUndefined::Some(due.to_owned()),
+        at_location: MaybeUndefined::Undefined,
+        agreed_in: MaybeUndefined::Undefined,
+        clause_of: MaybeUndefined::Undefined,
+        planned_within: MaybeUndefined::Undefined,
+        independent_demand_of: independent_demand_of.into(),
+        finished: MaybeUndefined::Some(false),
+        in_scope_of: MaybeUndefined::Undefined,
+        in_scenario: context.scenario.to_owned().into(),
+    };
+
+    let resp: OtherCellResult<CommitmentResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        &CellDna(&context.cells.planning), &CREATE_COMMITMENT_API_METHOD, CreateCommitmentParams { commitment }, LinkTypes::AvailableCapability,
+    );
+    Ok(resp?.commitment.id)
+}
@@ -9,11 +9,16 @@
 use holochain_serialized_bytes::prelude::*;
 
 use serde_maybe_undefined::MaybeUndefined;
+use vf_measurement::QuantityValue;
 pub use vf_attributes_hdk::{
     PlanAddress,
     CommitmentAddress,
     ProcessAddress,
+    ScenarioAddress,
     EconomicEventAddress,
+    ResourceSpecificationAddress,
+    AgentAddress,
+    DnaHash,
     DateTime,
     FixedOffset,
     ByAction, ActionHash, ByRevision, RecordMeta, RevisionMeta,
@@ -39,6 +44,8 @@ pub struct Response {
     pub note: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deletable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refinement_of: Option<ScenarioAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub processes: Vec<ProcessAddress>,
@@ -79,16 +86,41 @@ pub struct CreateRequest {
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
     pub deletable: MaybeUndefined<bool>,
-    // exclude `refinementOf` because it relates to Scenario, which is out of MMR scope
-    // #[serde(default)]
-    // #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
-    // pub refinementOf: MaybeUndefined<ScenarioAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub refinement_of: MaybeUndefined<ScenarioAddress>,
 }
 
 impl<'a> CreateRequest {
     // :TODO: accessors for field data
 }
 
+//---------------- PLAN GENERATION REQUEST ----------------
+
+/// DNAs of the cells which recipes are read from and generated records are written to
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanGenerationCells {
+    pub recipe: DnaHash,
+    pub observation: DnaHash,
+    pub planning: DnaHash,
+}
+
+/// I/O struct describing a desired output to explode recipes backwards from in order to
+/// create a new plan. All generated commitments are provided and received by `agent`.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratePlanRequest {
+    pub plan: CreateRequest,
+    pub resource_conforms_to: ResourceSpecificationAddress,
+    pub resource_quantity: QuantityValue,
+    pub due: DateTime<FixedOffset>,
+    pub agent: AgentAddress,
+    pub cells: PlanGenerationCells,
+}
+
 //---------------- UPDATE REQUEST ----------------
 
 /// I/O struct to describe the complete input record, including all managed links
@@ -112,10 +144,9 @@ pub struct UpdateRequest {
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
     pub deletable: MaybeUndefined<bool>,
-    // exclude `refinementOf` because it relates to Scenario, which is out of MMR scope
-    // #[serde(default)]
-    // #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
-    // pub refinementOf: MaybeUndefined<ScenarioAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub refinement_of: MaybeUndefined<ScenarioAddress>,
 }
 
 impl<'a> UpdateRequest {
@@ -134,4 +165,5 @@ pub struct QueryParams {
     pub processes: Option<ProcessAddress>,
     pub non_process_commitments: Option<CommitmentAddress>,
     pub independent_demands: Option<CommitmentAddress>,
+    pub refinement_of: Option<ScenarioAddress>,
 }
@@ -11,6 +11,7 @@ use hdk::prelude::*;
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
 use hdk_records::{
     RecordAPIResult, DataIntegrityError,
+    MaybeUndefined,
     generate_record_entry,
     record_interface::{Updateable},
 };
@@ -18,6 +19,7 @@ use hdk_records::{
 use vf_attributes_hdk::{
     DateTime,
     FixedOffset,
+    ScenarioAddress,
 };
 
 use hc_zome_rea_plan_rpc::{ CreateRequest, UpdateRequest };
@@ -39,6 +41,7 @@ pub struct DnaConfigSlice {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
 pub struct PlanZomeConfig {
     pub index_zome: String,
+    pub scenario_index_zome: Option<String>,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -50,6 +53,7 @@ pub struct EntryData {
     pub due: Option<DateTime<FixedOffset>>,
     pub note: Option<String>,
     pub deletable: Option<bool>,
+    pub refinement_of: Option<ScenarioAddress>,
     pub _nonce: Bytes,
 }
 
@@ -102,6 +106,7 @@ impl TryFrom<CreateRequest> for EntryData {
             due: e.due.into(),
             note: e.note.into(),
             deletable: e.deletable.into(),
+            refinement_of: e.refinement_of.into(),
             _nonce: random_bytes(32)?,
         })
     }
@@ -118,6 +123,7 @@ impl Updateable<UpdateRequest> for EntryData {
             due: if !e.due.is_some() { self.due.to_owned() } else { e.due.to_owned().into() },
             note: if !e.note.is_some() { self.note.to_owned() } else { e.note.to_owned().into() },
             deletable: if !e.deletable.is_some() { self.deletable.to_owned() } else { e.deletable.to_owned().into() },
+            refinement_of: if e.refinement_of == MaybeUndefined::Undefined { self.refinement_of.to_owned() } else { e.refinement_of.to_owned().into() },
             _nonce: self._nonce.to_owned(),
         })
     }
@@ -49,6 +49,11 @@ fn update_plan(UpdateParams { plan }: UpdateParams) -> ExternResult<ResponseData
     Ok(handle_update_plan(plan)?)
 }
 
+#[hdk_extern]
+fn generate_plan(request: GeneratePlanRequest) -> ExternResult<ResponseData> {
+    Ok(handle_generate_plan(PLAN_ENTRY_TYPE, request)?)
+}
+
 #[hdk_extern]
 fn delete_plan(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_plan(revision_id)?)
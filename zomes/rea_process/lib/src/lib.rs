@@ -6,9 +6,11 @@
  *
  * @package hREA
  */
+use std::collections::HashSet;
 use paste::paste;
+use serde::Serialize;
 use hdk_records::{
-    RecordAPIResult,
+    RecordAPIResult, OtherCellResult, DnaHash,
     records::{
         create_record,
         read_record_entry,
@@ -17,12 +19,28 @@ use hdk_records::{
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    rpc::call_zome_method,
     MaybeUndefined, SignedActionHashed,
 };
 use hdk_semantic_indexes_client_lib::*;
+use vf_attributes_hdk::{ByAddress, ResourceSpecificationAddress};
 
 use hc_zome_rea_process_storage::*;
 use hc_zome_rea_process_rpc::*;
+use hc_zome_rea_economic_event_storage::{
+    EntryData as EconomicEventData,
+    EntryStorage as EconomicEventStorage,
+};
+use hc_zome_rea_commitment_rpc::{
+    ResponseData as CommitmentResponseData,
+    Response as CommitmentResponse,
+    QueryParams as CommitmentQueryParams,
+};
+use hc_zome_rea_intent_rpc::{
+    ResponseData as IntentResponseData,
+    Response as IntentResponse,
+    QueryParams as IntentQueryParams,
+};
 
 
 /// properties accessor for zome config
@@ -41,6 +59,10 @@ pub fn handle_create_process<S>(entry_def_id: S, process: CreateRequest) -> Reco
         let e = create_index!(process.planned_within(planned_within), plan.processes(&base_address));
         hdk::prelude::debug!("handle_create_process::planned_within index {:?}", e);
     };
+    if let CreateRequest { in_scenario: MaybeUndefined::Some(in_scenario), .. } = &process {
+        let e = create_index!(process.in_scenario(in_scenario), scenario.processes(&base_address));
+        hdk::prelude::debug!("handle_create_process::in_scenario index {:?}", e);
+    };
 
     // :TODO: pass results from link creation rather than re-reading
     construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
@@ -75,6 +97,17 @@ pub fn handle_update_process(process: UpdateRequest) -> RecordAPIResult<Response
         );
         hdk::prelude::debug!("handle_update_process::planned_within index {:?}", e);
     }
+    if new_entry.in_scenario != prev_entry.in_scenario {
+        let new_value = match &new_entry.in_scenario { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.in_scenario { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            process
+                .in_scenario(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            scenario.processes(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_process::in_scenario index {:?}", e);
+    }
     construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
 }
 
@@ -88,6 +121,10 @@ pub fn handle_delete_process(revision_id: ActionHash) -> RecordAPIResult<bool>
         let e = update_index!(process.planned_within.not(&vec![plan_address]), plan.processes(&base_address));
         hdk::prelude::debug!("handle_delete_process::planned_within index {:?}", e);
     }
+    if let Some(scenario_address) = entry.in_scenario {
+        let e = update_index!(process.in_scenario.not(&vec![scenario_address]), scenario.processes(&base_address));
+        hdk::prelude::debug!("handle_delete_process::in_scenario index {:?}", e);
+    }
 
     delete_record::<EntryStorage>(&revision_id)
 }
@@ -128,6 +165,7 @@ fn construct_response<'a>(
             planned_within: e.planned_within.to_owned(),
             note: e.note.to_owned(),
             in_scope_of: e.in_scope_of.to_owned(),
+            in_scenario: e.in_scenario.to_owned(),
             finished: e.finished.to_owned(),
             deletable: true,    // :TODO:
 
@@ -159,6 +197,15 @@ fn read_plan_index_zome(conf: DnaConfigSlice) -> Option<String> {
     conf.process.plan_index_zome
 }
 
+/// Properties accessor for zome config
+fn read_scenario_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.process.scenario_index_zome
+}
+/// Properties accessor for zome config
+fn read_economic_resource_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.process.economic_resource_index_zome
+}
+
 // @see construct_response
 fn get_link_fields(process: &ProcessAddress) -> RecordAPIResult<(
     Vec<EconomicEventAddress>,
@@ -174,18 +221,182 @@ fn get_link_fields(process: &ProcessAddress) -> RecordAPIResult<(
     Vec<EconomicEventAddress>,
     Vec<EconomicEventAddress>,
 )> {
+    let observed_inputs: Vec<EconomicEventAddress> = read_index!(process(process).observed_inputs)?;
+    let observed_outputs: Vec<EconomicEventAddress> = read_index!(process(process).observed_outputs)?;
+
     Ok((
-        read_index!(process(process).observed_inputs)?,
-        read_index!(process(process).observed_outputs)?,
+        observed_inputs.to_owned(),
+        observed_outputs.to_owned(),
         vec![],  // :TODO: unplanned_economic_events
         read_index!(process(process).committed_inputs)?,
         read_index!(process(process).committed_outputs)?,
         read_index!(process(process).intended_inputs)?,
         read_index!(process(process).intended_outputs)?,
-        vec![], // :TODO: next_processes
-        vec![], // :TODO: previous_processes
+        vec![], // next_processes: derived on request only, @see get_next_processes
+        vec![], // previous_processes: derived on request only, @see get_previous_processes
         vec![], // :TODO: working_agents
-        vec![], // :TODO: trace
-        vec![], // :TODO: track
+        observed_inputs,    // trace: events flowing into the process (see economic event `trace` for deep traversal)
+        observed_outputs,   // track: events flowing out of the process
+    ))
+}
+
+//---------------- PROCESS FLOWS ----------------
+
+// remote API methods in the planning DNA used to determine planned process flows
+const READ_COMMITMENT_API_METHOD: &str = "read_commitment";
+const QUERY_COMMITMENTS_API_METHOD: &str = "query_commitments";
+const READ_INTENT_API_METHOD: &str = "read_intent";
+const QUERY_INTENTS_API_METHOD: &str = "query_intents";
+
+// number of records requested per page when reading planned flows from the planning DNA
+const FLOW_QUERY_PAGE_SIZE: usize = 100;
+
+#[derive(Clone, Copy, PartialEq)]
+enum FlowDirection {
+    Previous,
+    Next,
+}
+
+/// Processes consuming the outputs of `process`: those with observed inputs drawn from the
+/// resources it produced, or with planned inputs of the resource specifications it is planned to output.
+///
+/// Determining these requires reading every related event and querying the planning DNA, so they are
+/// not included in `Process` responses and are instead read via the `query_next_processes` index API.
+pub fn get_next_processes(process: &ProcessAddress) -> RecordAPIResult<Vec<ProcessAddress>> {
+    get_connected_processes(process, FlowDirection::Next)
+}
+
+/// Processes providing the inputs of `process`: those with observed outputs into the
+/// resources it consumed, or with planned outputs of the resource specifications it is planned to input.
+///
+/// As with `get_next_processes`, these are read via the `query_previous_processes` index API only.
+pub fn get_previous_processes(process: &ProcessAddress) -> RecordAPIResult<Vec<ProcessAddress>> {
+    get_connected_processes(process, FlowDirection::Previous)
+}
+
+fn get_connected_processes(process: &ProcessAddress, direction: FlowDirection) -> RecordAPIResult<Vec<ProcessAddress>> {
+    let mut connected = read_observed_process_flows(process, direction)?;
+    connected.append(&mut read_planned_process_flows(process, direction)?);
+
+    let mut seen = HashSet::new();
+    connected.retain(|p| p != process && seen.insert(p.to_owned()));
+
+    Ok(connected)
+}
+
+/// Determine connected processes via the `EconomicResource`s affected by observed events.
+fn read_observed_process_flows(process: &ProcessAddress, direction: FlowDirection) -> RecordAPIResult<Vec<ProcessAddress>> {
+    let events: Vec<EconomicEventAddress> = match direction {
+        FlowDirection::Next => read_index!(process(process).observed_outputs)?,
+        FlowDirection::Previous => read_index!(process(process).observed_inputs)?,
+    };
+
+    let mut resources = HashSet::new();
+    for event_address in events {
+        let (_meta, _base, event) = read_record_entry::<EconomicEventData, EconomicEventStorage, _>(event_address.as_ref())?;
+        if let Some(resource) = event.resource_inventoried_as {
+            resources.insert(resource);
+        }
+    }
+
+    let mut connected = vec![];
+    for resource in resources {
+        let resource_events: Vec<EconomicEventAddress> = read_index!(economic_resource(&resource).affected_by)?;
+        for event_address in resource_events {
+            let (_meta, _base, event) = read_record_entry::<EconomicEventData, EconomicEventStorage, _>(event_address.as_ref())?;
+            if event.resource_inventoried_as.as_ref() != Some(&resource) {
+                continue;
+            }
+            let linked = match direction {
+                FlowDirection::Next => event.input_of,
+                FlowDirection::Previous => event.output_of,
+            };
+            if let Some(linked_process) = linked {
+                connected.push(linked_process);
+            }
+        }
+    }
+
+    Ok(connected)
+}
+
+/// Determine connected processes via the `ResourceSpecification`s of planned commitments & intents.
+///
+/// Planning records live in a separate DNA, so any errors communicating with it are logged and
+/// treated as there being no planned flows. This allows the observation DNA to operate standalone.
+///
+fn read_planned_process_flows(process: &ProcessAddress, direction: FlowDirection) -> RecordAPIResult<Vec<ProcessAddress>> {
+    let commitments: Vec<CommitmentAddress> = match direction {
+        FlowDirection::Next => read_index!(process(process).committed_outputs)?,
+        FlowDirection::Previous => read_index!(process(process).committed_inputs)?,
+    };
+    let intents: Vec<IntentAddress> = match direction {
+        FlowDirection::Next => read_index!(process(process).intended_outputs)?,
+        FlowDirection::Previous => read_index!(process(process).intended_inputs)?,
+    };
+
+    let mut connected = vec![];
+
+    let mut commitment_specs = HashSet::new();
+    for commitment in commitments.iter() {
+        let resp: OtherCellResult<CommitmentResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+            commitment, &READ_COMMITMENT_API_METHOD, ByAddress { address: commitment.to_owned() }, LinkTypes::AvailableCapability,
+        );
+        match resp {
+            Ok(CommitmentResponseData { commitment: CommitmentResponse { resource_conforms_to: Some(spec), .. } }) => {
+                if commitment_specs.insert(spec.to_owned()) {
+                    let params = CommitmentQueryParams {
+                        input_of: None, output_of: None, fulfilled_by: None, satisfies: None, clause_of: None,
+                        provider: None, receiver: None, independent_demand_of: None, planned_within: None,
+                        at_location: None, resource_conforms_to: Some(spec), in_scenario: None,
+                    };
+                    for CommitmentResponse { input_of, output_of, .. } in query_planned_flows(commitment, &QUERY_COMMITMENTS_API_METHOD, params) {
+                        connected.extend(match direction { FlowDirection::Next => input_of, FlowDirection::Previous => output_of });
+                    }
+                }
+            },
+            Ok(_) => (),
+            Err(e) => hdk::prelude::debug!("read_planned_process_flows::{:?} {:?}", READ_COMMITMENT_API_METHOD, e),
+        }
+    }
+
+    let mut intent_specs: HashSet<ResourceSpecificationAddress> = HashSet::new();
+    for intent in intents.iter() {
+        let resp: OtherCellResult<IntentResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+            intent, &READ_INTENT_API_METHOD, ByAddress { address: intent.to_owned() }, LinkTypes::AvailableCapability,
+        );
+        match resp {
+            Ok(IntentResponseData { intent: IntentResponse { resource_conforms_to: Some(spec), .. } }) => {
+                if intent_specs.insert(spec.to_owned()) {
+                    let params = IntentQueryParams {
+                        input_of: None, output_of: None, satisfied_by: None, proposed_in: None,
+                        provider: None, receiver: None, resource_conforms_to: Some(spec), in_scenario: None,
+                    };
+                    for IntentResponse { input_of, output_of, .. } in query_planned_flows(intent, &QUERY_INTENTS_API_METHOD, params) {
+                        connected.extend(match direction { FlowDirection::Next => input_of, FlowDirection::Previous => output_of });
+                    }
+                }
+            },
+            Ok(_) => (),
+            Err(e) => hdk::prelude::debug!("read_planned_process_flows::{:?} {:?}", READ_INTENT_API_METHOD, e),
+        }
+    }
+
+    Ok(connected)
+}
+
+/// Read every page of results for a query against an index zome in the planning DNA
+/// containing `route_via`, from most recent to oldest.
+fn query_planned_flows<A, Q, T>(route_via: &A, query_method: &&str, params: Q) -> Vec<T>
+    where A: AsRef<DnaHash>,
+        Q: Clone + Serialize + std::fmt::Debug,
+        T: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    read_all_pages(Some(params), FLOW_QUERY_PAGE_SIZE, |inputs| call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        route_via, query_method, inputs, LinkTypes::AvailableCapability,
     ))
+        .unwrap_or_else(|e| {
+            hdk::prelude::debug!("query_planned_flows::{:?} {:?}", query_method, e);
+            vec![]
+        })
 }
@@ -18,6 +18,7 @@ pub use vf_attributes_hdk::{
     CommitmentAddress,
     IntentAddress,
     AgentAddress,
+    ScenarioAddress,
 };
 
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
@@ -49,6 +50,8 @@ pub struct Response {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub in_scope_of: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_scenario: Option<ScenarioAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
 
     // query edges
@@ -123,6 +126,8 @@ pub struct CreateRequest {
     #[serde(default)]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
     #[serde(default)]
+    pub in_scenario: MaybeUndefined<ScenarioAddress>,
+    #[serde(default)]
     pub note: MaybeUndefined<String>,
 }
 
@@ -158,6 +163,8 @@ pub struct UpdateRequest {
     #[serde(default)]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
     #[serde(default)]
+    pub in_scenario: MaybeUndefined<ScenarioAddress>,
+    #[serde(default)]
     pub note: MaybeUndefined<String>,
 }
 
@@ -183,4 +190,5 @@ pub struct QueryParams {
     pub intended_outputs: Option<IntentAddress>,
     pub working_agents: Option<AgentAddress>,
     pub planned_within: Option<PlanAddress>,
+    pub in_scenario: Option<ScenarioAddress>,
 }
@@ -22,6 +22,7 @@ use vf_attributes_hdk::{
     ExternalURL,
     ProcessSpecificationAddress,
     PlanAddress,
+    ScenarioAddress,
 };
 
 use hc_zome_rea_process_rpc::{ CreateRequest, UpdateRequest };
@@ -38,6 +39,8 @@ pub struct DnaConfigSlice {
 pub struct ProcessZomeConfig {
     pub index_zome: String,
     pub plan_index_zome: Option<String>,
+    pub scenario_index_zome: Option<String>,
+    pub economic_resource_index_zome: Option<String>,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -54,6 +57,7 @@ pub struct EntryData {
     pub planned_within: Option<PlanAddress>,
     pub finished: bool,
     pub in_scope_of: Option<Vec<String>>,
+    pub in_scenario: Option<ScenarioAddress>,
     pub note: Option<String>,
     pub _nonce: Bytes,
 }
@@ -110,6 +114,7 @@ impl TryFrom<CreateRequest> for EntryData {
             planned_within: e.planned_within.into(),
             finished: e.finished.to_option().unwrap(),  // :NOTE: unsafe, would crash if not for "default_*" bindings via Serde
             in_scope_of: e.in_scope_of.into(),
+            in_scenario: e.in_scenario.into(),
             note: e.note.into(),
             _nonce: random_bytes(32)?,
         })
@@ -132,6 +137,7 @@ impl Updateable<UpdateRequest> for EntryData {
             planned_within: if e.planned_within == MaybeUndefined::Undefined { self.planned_within.to_owned() } else { e.planned_within.to_owned().into() },
             finished: if e.finished == MaybeUndefined::Undefined { self.finished.to_owned() } else { e.finished.to_owned().to_option().unwrap() },
             in_scope_of: if e.in_scope_of == MaybeUndefined::Undefined { self.in_scope_of.to_owned() } else { e.in_scope_of.to_owned().into() },
+            in_scenario: if e.in_scenario == MaybeUndefined::Undefined { self.in_scenario.to_owned() } else { e.in_scenario.to_owned().into() },
             note: if e.note == MaybeUndefined::Undefined { self.note.to_owned() } else { e.note.to_owned().into() },
             _nonce: self._nonce.to_owned(),
         })
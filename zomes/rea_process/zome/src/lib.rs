@@ -54,3 +54,13 @@ fn update_process(UpdateParams { process }: UpdateParams) -> ExternResult<Respon
 fn delete_process(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_process(revision_id)?)
 }
+
+#[hdk_extern]
+fn read_next_processes(ReadParams { address }: ReadParams) -> ExternResult<Vec<ProcessAddress>> {
+    Ok(get_next_processes(&address)?)
+}
+
+#[hdk_extern]
+fn read_previous_processes(ReadParams { address }: ReadParams) -> ExternResult<Vec<ProcessAddress>> {
+    Ok(get_previous_processes(&address)?)
+}
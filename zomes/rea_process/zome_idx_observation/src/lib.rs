@@ -16,4 +16,41 @@ struct Process {
     intended_inputs: Remote<intent, input_of>,
     intended_outputs: Remote<intent, output_of>,
     planned_within: Local<plan, processes>,
+    in_scenario: Local<scenario, processes>,
+}
+
+// process flows are derived by the record storage zome rather than indexed directly
+// @see hc_zome_rea_process_lib::get_next_processes
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ProcessFlowInputs {
+    pub process: ProcessAddress,
+    pub first: Option<usize>,
+    pub after: Option<String>,
+    pub last: Option<usize>,
+    pub before: Option<String>,
+}
+
+#[hdk_extern]
+fn query_next_processes(inputs: ProcessFlowInputs) -> ExternResult<QueryResults> {
+    handle_query_process_flows(&"read_next_processes", inputs)
+}
+
+#[hdk_extern]
+fn query_previous_processes(inputs: ProcessFlowInputs) -> ExternResult<QueryResults> {
+    handle_query_process_flows(&"read_previous_processes", inputs)
+}
+
+fn handle_query_process_flows(derive_method_name: &&str, ProcessFlowInputs { process, first, after, last, before }: ProcessFlowInputs) -> ExternResult<QueryResults> {
+    let page = query_derived_index::<ResponseData, ProcessAddress, ProcessAddress, _,_,_,_,_>(
+        &process,
+        derive_method_name,
+        &LOCAL_TIME_INDEX_ID,
+        &read_index_target_zome,
+        &QUERY_FN_NAME,
+        &PagingParams { first, after, last, before },
+        PAGE_SIZE,
+    )?;
+
+    Ok(handle_list_output(page)?)
 }
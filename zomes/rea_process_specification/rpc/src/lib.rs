@@ -11,6 +11,7 @@ use serde_maybe_undefined::MaybeUndefined;
 pub use vf_attributes_hdk::{
     ActionHash, ByAddress, ByAction, ByRevision, RecordMeta, RevisionMeta,
     ProcessSpecificationAddress,
+    RecipeProcessAddress,
 };
 
 // toplevel I/O structs for WASM API
@@ -90,4 +91,5 @@ impl<'a> UpdateRequest {
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryParams {
+    pub recipe_processes: Option<RecipeProcessAddress>,
 }
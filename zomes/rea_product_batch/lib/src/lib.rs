@@ -0,0 +1,99 @@
+/**
+ * hREA product batch zome library API
+ *
+ * Contains helper methods that can be used to manipulate `ProductBatch` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_product_batch_storage::*;
+use hc_zome_rea_product_batch_rpc::*;
+
+
+pub use hc_zome_rea_product_batch_storage::PRODUCT_BATCH_ENTRY_TYPE;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.product_batch.index_zome)
+}
+
+pub fn handle_create_product_batch<S>(entry_def_id: S, product_batch: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, product_batch)?;
+    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_product_batch(address: ProductBatchAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_update_product_batch(product_batch: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    let revision_hash = product_batch.get_revision_id().clone();
+    let (meta, identity_address, entry, _prev_entry): (_,_, EntryData, EntryData) = update_record(&revision_hash, product_batch)?;
+    construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)
+}
+
+pub fn handle_delete_product_batch(address: ActionHash) -> RecordAPIResult<bool> {
+    delete_record::<EntryStorage>(&address)
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &ProductBatchAddress, meta: &SignedActionHashed, e: &EntryData, (
+        economic_resources,
+    ): (
+        Vec<EconomicResourceAddress>,
+    ),
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        product_batch: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            batch_number: e.batch_number.to_owned(),
+            expiry_date: e.expiry_date.to_owned(),
+            production_date: e.production_date.to_owned(),
+            economic_resources: economic_resources.to_owned(),
+        }
+    })
+}
+
+//---------------- READ ----------------
+
+/// Properties accessor for zome config
+fn read_product_batch_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.product_batch.index_zome)
+}
+
+// @see construct_response
+fn get_link_fields(base_address: &ProductBatchAddress) -> RecordAPIResult<(
+    Vec<EconomicResourceAddress>,
+)> {
+    Ok((
+        read_index!(product_batch(base_address).economic_resources)?,
+    ))
+}
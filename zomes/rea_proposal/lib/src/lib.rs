@@ -7,8 +7,10 @@
 * @package hREA
 */
 use paste::paste;
+use serde::Serialize;
 use hdk_records::{
-    RecordAPIResult, SignedActionHashed,
+    RecordAPIResult, DataIntegrityError, OtherCellResult, DnaAddressable,
+    MaybeUndefined, SignedActionHashed,
     records::{
         create_record,
         delete_record,
@@ -17,11 +19,34 @@ use hdk_records::{
         update_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    rpc::{call_zome_method, call_local_zome_method, CellDna},
 };
 use hdk_semantic_indexes_client_lib::*;
+use vf_attributes_hdk::{IntentAddress, EventOrCommitmentAddress, EntryHash};
+use vf_measurement::{QuantityValue, Decimal, multiply};
 
 use hc_zome_rea_proposal_rpc::*;
 use hc_zome_rea_proposal_storage::*;
+use hc_zome_rea_proposed_intent_rpc::{
+    ResponseData as ProposedIntentResponseData,
+    Response as ProposedIntentResponse,
+};
+use hc_zome_rea_intent_rpc::{
+    ResponseData as IntentResponseData,
+    Response as IntentResponse,
+};
+use hc_zome_rea_agreement_rpc::{
+    CreateRequest as AgreementCreateRequest,
+    ResponseData as AgreementResponseData,
+};
+use hc_zome_rea_commitment_rpc::{
+    CreateRequest as CommitmentCreateRequest,
+    ResponseData as CommitmentResponseData,
+};
+use hc_zome_rea_satisfaction_rpc::{
+    CreateRequest as SatisfactionCreateRequest,
+    ResponseData as SatisfactionResponseData,
+};
 
 
 /// properties accessor for zome config
@@ -105,3 +130,221 @@ fn get_link_fields<'a>(
         read_index!(proposal(proposal).published_to)?,
     ))
 }
+
+//---------------- ACCEPTANCE ----------------
+
+const READ_PROPOSED_INTENT_API_METHOD: &str = "get_proposed_intent";
+const READ_INTENT_API_METHOD: &str = "read_intent";
+const CREATE_AGREEMENT_API_METHOD: &str = "create_agreement";
+const DELETE_AGREEMENT_API_METHOD: &str = "delete_agreement";
+const CREATE_COMMITMENT_API_METHOD: &str = "create_commitment";
+const DELETE_COMMITMENT_API_METHOD: &str = "delete_commitment";
+const CREATE_SATISFACTION_API_METHOD: &str = "create_satisfaction";
+const DELETE_SATISFACTION_API_METHOD: &str = "delete_satisfaction";
+
+/// Accept a `Proposal` on behalf of `agent`, creating an `Agreement` with a `Commitment` as clause
+/// for each published `Intent`, and a `Satisfaction` linking each intent to its commitment.
+///
+/// Records are created across several cells, so cannot be written atomically. If any step fails, records
+/// already created are deleted again; any which cannot be are reported in the returned error.
+///
+pub fn handle_accept_proposal(request: AcceptProposalRequest) -> RecordAPIResult<AcceptProposalResponseData>
+{
+    let AcceptProposalRequest { proposal, agent, unit_quantity, note, cells } = request;
+    let proposal = handle_get_proposal(proposal)?.proposal;
+
+    let scale = match (proposal.unit_based.unwrap_or(false), unit_quantity) {
+        (true, Some(quantity)) if quantity > Decimal::zero() => quantity,
+        (true, _) => return Err(DataIntegrityError::ProposalNotAcceptable("unit based proposals must be accepted with a positive unit quantity".into())),
+        (false, Some(_)) => return Err(DataIntegrityError::ProposalNotAcceptable("unit quantities may only be given for unit based proposals".into())),
+        (false, None) => Decimal::one(),
+    };
+
+    // read everything up-front so that nothing is written for proposals which cannot be accepted
+    let planning_dna = CellDna(&cells.planning);
+    let intents = proposal.publishes.iter()
+        .map(|proposed_intent_address| {
+            let proposed_intent = read_proposed_intent(proposed_intent_address)?;
+            read_intent(&planning_dna, &proposed_intent.publishes)
+        })
+        .collect::<RecordAPIResult<Vec<IntentResponse>>>()?;
+
+    if intents.is_empty() {
+        return Err(DataIntegrityError::ProposalNotAcceptable("proposal does not publish any intents".into()));
+    }
+    if let Some(finished) = intents.iter().find(|intent| intent.finished) {
+        return Err(DataIntegrityError::ProposalNotAcceptable(format!("intent {} is already finished", finished.id)));
+    }
+
+    let mut created = AcceptedRecords::default();
+    match create_acceptance_records(&cells, &proposal, &intents, &agent, scale, note, &mut created) {
+        Ok(()) => Ok(AcceptProposalResponseData {
+            agreement: created.agreement.map(|(address, _)| address).ok_or(DataIntegrityError::EntryNotFound)?,
+            commitments: created.commitments.into_iter().map(|(address, _)| address).collect(),
+            satisfactions: created.satisfactions.into_iter().map(|(address, _)| address).collect(),
+        }),
+        Err(e) => Err(DataIntegrityError::ProposalAcceptanceFailed(e.to_string(), rollback_acceptance(&cells, created))),
+    }
+}
+
+/// Records written whilst accepting a proposal, along with the revisions needed to delete them
+#[derive(Default)]
+struct AcceptedRecords {
+    agreement: Option<(AgreementAddress, ActionHash)>,
+    commitments: Vec<(CommitmentAddress, ActionHash)>,
+    satisfactions: Vec<(SatisfactionAddress, ActionHash)>,
+}
+
+fn create_acceptance_records(
+    cells: &ProposalAcceptanceCells,
+    proposal: &Response,
+    intents: &[IntentResponse],
+    agent: &AgentAddress,
+    scale: Decimal,
+    note: Option<String>,
+    created: &mut AcceptedRecords,
+) -> RecordAPIResult<()> {
+    let planning_dna = CellDna(&cells.planning);
+
+    let agreement = AgreementCreateRequest {
+        name: proposal.name.to_owned().into(),
+        created: MaybeUndefined::Undefined,
+        note: note.or_else(|| proposal.note.to_owned()).into(),
+    };
+    let resp: OtherCellResult<AgreementResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        &CellDna(&cells.agreement), &CREATE_AGREEMENT_API_METHOD, CreateAgreementParams { agreement }, LinkTypes::AvailableCapability,
+    );
+    let agreement = resp?.agreement;
+    created.agreement = Some((agreement.id.to_owned(), agreement.revision_id.to_owned()));
+
+    for intent in intents {
+        let commitment = commitment_for_intent(intent, agent, &agreement.id, scale)?;
+        let resp: OtherCellResult<CommitmentResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+            &planning_dna, &CREATE_COMMITMENT_API_METHOD, CreateCommitmentParams { commitment }, LinkTypes::AvailableCapability,
+        );
+        let commitment = resp?.commitment;
+        created.commitments.push((commitment.id.to_owned(), commitment.revision_id.to_owned()));
+
+        let satisfaction = SatisfactionCreateRequest {
+            satisfied_by: EventOrCommitmentAddress::new(
+                AsRef::<DnaHash>::as_ref(&commitment.id).to_owned(),
+                AsRef::<EntryHash>::as_ref(&commitment.id).to_owned(),
+            ),
+            satisfies: intent.id.to_owned(),
+            resource_quantity: commitment.resource_quantity.to_owned().into(),
+            effort_quantity: commitment.effort_quantity.to_owned().into(),
+            note: MaybeUndefined::Undefined,
+            nonce: MaybeUndefined::Undefined,
+        };
+        let resp: OtherCellResult<SatisfactionResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+            &planning_dna, &CREATE_SATISFACTION_API_METHOD, CreateSatisfactionParams { satisfaction }, LinkTypes::AvailableCapability,
+        );
+        let satisfaction = resp?.satisfaction;
+        created.satisfactions.push((satisfaction.id.to_owned(), satisfaction.revision_id.to_owned()));
+    }
+
+    Ok(())
+}
+
+/// Build a commitment fulfilling `intent` under the given agreement, with `agent` taking
+/// whichever of the provider or receiver roles the intent left open
+fn commitment_for_intent(intent: &IntentResponse, agent: &AgentAddress, agreement: &AgreementAddress, scale: Decimal) -> RecordAPIResult<CommitmentCreateRequest> {
+    Ok(CommitmentCreateRequest {
+        action: intent.action.to_owned(),
+        note: intent.note.to_owned().into(),
+        input_of: intent.input_of.to_owned().into(),
+        output_of: intent.output_of.to_owned().into(),
+        provider: intent.provider.to_owned().unwrap_or_else(|| agent.to_owned()),
+        receiver: intent.receiver.to_owned().unwrap_or_else(|| agent.to_owned()),
+        resource_inventoried_as: intent.resource_inventoried_as.to_owned().into(),
+        resource_classified_as: intent.resource_classified_as.to_owned().into(),
+        resource_conforms_to: intent.resource_conforms_to.to_owned().into(),
+        resource_quantity: intent.resource_quantity.as_ref().map(|q| scale_quantity(q, scale)).transpose()?.into(),
+        effort_quantity: intent.effort_quantity.as_ref().map(|q| scale_quantity(q, scale)).transpose()?.into(),
+        has_beginning: intent.has_beginning.to_owned().into(),
+        has_end: intent.has_end.to_owned().into(),
+        has_point_in_time: intent.has_point_in_time.to_owned().into(),
+        due: intent.due.to_owned().into(),
+        at_location: intent.at_location.to_owned().into(),
+        agreed_in: MaybeUndefined::Undefined,
+        clause_of: MaybeUndefined::Some(agreement.to_owned()),
+        planned_within: MaybeUndefined::Undefined,
+        independent_demand_of: MaybeUndefined::Undefined,
+        finished: MaybeUndefined::Some(false),
+        in_scope_of: intent.in_scope_of.to_owned().into(),
+        in_scenario: intent.in_scenario.to_owned().into(),
+    })
+}
+
+fn scale_quantity(quantity: &QuantityValue, scale: Decimal) -> RecordAPIResult<QuantityValue> {
+    multiply(quantity.to_owned(), scale)
+}
+
+/// Delete records created by a failed acceptance, most recent first.
+/// Returns the identifiers of any records which could not be removed.
+///
+fn rollback_acceptance(cells: &ProposalAcceptanceCells, created: AcceptedRecords) -> Vec<String> {
+    let planning_dna = CellDna(&cells.planning);
+    let mut failed = vec![];
+
+    for (address, revision_id) in created.satisfactions.into_iter().rev() {
+        if !delete_remote_record(&planning_dna, DELETE_SATISFACTION_API_METHOD, revision_id) {
+            failed.push(address.to_string());
+        }
+    }
+    for (address, revision_id) in created.commitments.into_iter().rev() {
+        if !delete_remote_record(&planning_dna, DELETE_COMMITMENT_API_METHOD, revision_id) {
+            failed.push(address.to_string());
+        }
+    }
+    if let Some((address, revision_id)) = created.agreement {
+        if !delete_remote_record(&CellDna(&cells.agreement), DELETE_AGREEMENT_API_METHOD, revision_id) {
+            failed.push(address.to_string());
+        }
+    }
+
+    failed
+}
+
+/// Delete a record written to another cell, returning whether it was removed.
+/// Deletes which error and deletes which are refused both leave the record in place.
+fn delete_remote_record(to_dna: &CellDna, method: &str, revision_id: ActionHash) -> bool {
+    let resp: OtherCellResult<bool> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        to_dna, &method, ByRevision { revision_id }, LinkTypes::AvailableCapability,
+    );
+    matches!(resp, Ok(true))
+}
+
+/// Properties accessor for zome config
+fn read_proposed_intent_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.proposal.proposed_intent_zome
+}
+
+fn read_proposed_intent(address: &ProposedIntentAddress) -> RecordAPIResult<ProposedIntentResponse> {
+    let resp: ProposedIntentResponseData = call_local_zome_method(
+        read_proposed_intent_zome, READ_PROPOSED_INTENT_API_METHOD, ByAddress { address: address.to_owned() },
+    )?;
+    Ok(resp.proposed_intent)
+}
+
+fn read_intent(planning_dna: &CellDna, address: &IntentAddress) -> RecordAPIResult<IntentResponse> {
+    let resp: OtherCellResult<IntentResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        planning_dna, &READ_INTENT_API_METHOD, ByAddress { address: address.to_owned() }, LinkTypes::AvailableCapability,
+    );
+    Ok(resp?.intent)
+}
+
+#[derive(Debug, Serialize)]
+struct CreateAgreementParams {
+    pub agreement: AgreementCreateRequest,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateCommitmentParams {
+    pub commitment: CommitmentCreateRequest,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateSatisfactionParams {
+    pub satisfaction: SatisfactionCreateRequest,
+}
@@ -8,10 +8,12 @@
  */
 use holochain_serialized_bytes::prelude::*;
 use serde_maybe_undefined::MaybeUndefined;
+use vf_measurement::Decimal;
 pub use vf_attributes_hdk::{
     ActionHash, ByAddress, ByAction, ByRevision, RecordMeta, RevisionMeta,
     ProposalAddress, ProposedIntentAddress, ProposedToAddress,
-    DateTime, FixedOffset,
+    AgentAddress, AgreementAddress, CommitmentAddress, SatisfactionAddress,
+    DateTime, FixedOffset, DnaHash,
 };
 
 /// Toplevel I/O structs for WASM API
@@ -135,3 +137,39 @@ pub struct QueryParams {
     pub publishes: Option<ProposedIntentAddress>,
     pub published_to: Option<ProposedToAddress>,
 }
+
+//---------------- ACCEPTANCE REQUEST ----------------
+
+/// Cells in which the records resulting from an accepted `Proposal` are created
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalAcceptanceCells {
+    pub planning: DnaHash,
+    pub agreement: DnaHash,
+}
+
+/// I/O struct for accepting a `Proposal` on behalf of some agent, who takes the
+/// unspecified provider or receiver role of each of its published intents.
+///
+/// `unit_quantity` is required for `unit_based` proposals, and scales all quantities.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptProposalRequest {
+    pub proposal: ProposalAddress,
+    pub agent: AgentAddress,
+    #[serde(default)]
+    pub unit_quantity: Option<Decimal>,
+    #[serde(default)]
+    pub note: Option<String>,
+    pub cells: ProposalAcceptanceCells,
+}
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptProposalResponseData {
+    pub agreement: AgreementAddress,
+    pub commitments: Vec<CommitmentAddress>,
+    pub satisfactions: Vec<SatisfactionAddress>,
+}
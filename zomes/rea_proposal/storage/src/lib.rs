@@ -30,6 +30,7 @@ pub struct DnaConfigSlice {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
 pub struct ProposalZomeConfig {
     pub index_zome: String,
+    pub proposed_intent_zome: Option<String>,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -37,3 +37,8 @@ fn update_proposal(UpdateParams { proposal }: UpdateParams) -> ExternResult<Resp
 fn delete_proposal(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_proposal(revision_id)?)
 }
+
+#[hdk_extern]
+fn accept_proposal(request: AcceptProposalRequest) -> ExternResult<AcceptProposalResponseData> {
+    Ok(handle_accept_proposal(request)?)
+}
@@ -0,0 +1,13 @@
+/**
+ * RecipeExchange query indexes for recipe DNA
+ *
+ * @package hREA
+ * @since   2026-10-18
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_recipe_exchange_rpc::*;
+
+#[index_zome]
+struct RecipeExchange {
+    recipe_stipulates: Local<recipe_flow, recipe_clause_of>,
+}
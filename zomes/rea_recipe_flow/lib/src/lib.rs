@@ -0,0 +1,192 @@
+/**
+ * hREA recipe flow zome library API
+ *
+ * Contains helper methods that can be used to manipulate `RecipeFlow` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_recipe_flow_storage::*;
+use hc_zome_rea_recipe_flow_rpc::*;
+
+pub use hc_zome_rea_recipe_flow_storage::RECIPE_FLOW_ENTRY_TYPE;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.recipe_flow.index_zome)
+}
+
+pub fn handle_create_recipe_flow<S>(entry_def_id: S, recipe_flow: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, recipe_flow)?;
+
+    // handle link fields
+    let e = create_index!(recipe_flow.recipe_flow_resource(&entry_resp.recipe_flow_resource), recipe_resource.recipe_flows(&base_address));
+    hdk::prelude::debug!("handle_create_recipe_flow::recipe_flow_resource index {:?}", e);
+
+    if let Some(recipe_input_of) = &entry_resp.recipe_input_of {
+        let e = create_index!(recipe_flow.recipe_input_of(recipe_input_of), recipe_process.recipe_inputs(&base_address));
+        hdk::prelude::debug!("handle_create_recipe_flow::recipe_input_of index {:?}", e);
+    };
+    if let Some(recipe_output_of) = &entry_resp.recipe_output_of {
+        let e = create_index!(recipe_flow.recipe_output_of(recipe_output_of), recipe_process.recipe_outputs(&base_address));
+        hdk::prelude::debug!("handle_create_recipe_flow::recipe_output_of index {:?}", e);
+    };
+    if let Some(recipe_clause_of) = &entry_resp.recipe_clause_of {
+        let e = create_index!(recipe_flow.recipe_clause_of(recipe_clause_of), recipe_exchange.recipe_stipulates(&base_address));
+        hdk::prelude::debug!("handle_create_recipe_flow::recipe_clause_of index {:?}", e);
+    };
+
+    construct_response(&base_address, &meta, &entry_resp)
+}
+
+pub fn handle_get_recipe_flow(address: RecipeFlowAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_update_recipe_flow(recipe_flow: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    let revision_hash = recipe_flow.get_revision_id().clone();
+    let (meta, base_address, new_entry, prev_entry): (_, RecipeFlowAddress, EntryData, EntryData) = update_record(&revision_hash, recipe_flow)?;
+
+    if new_entry.recipe_flow_resource != prev_entry.recipe_flow_resource {
+        let new_value = vec![new_entry.recipe_flow_resource.to_owned()];
+        let prev_value = vec![prev_entry.recipe_flow_resource.to_owned()];
+        let e = update_index!(
+            recipe_flow
+                .recipe_flow_resource(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            recipe_resource.recipe_flows(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_recipe_flow::recipe_flow_resource index {:?}", e);
+    }
+    if new_entry.recipe_input_of != prev_entry.recipe_input_of {
+        let new_value = match &new_entry.recipe_input_of { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.recipe_input_of { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            recipe_flow
+                .recipe_input_of(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            recipe_process.recipe_inputs(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_recipe_flow::recipe_input_of index {:?}", e);
+    }
+    if new_entry.recipe_output_of != prev_entry.recipe_output_of {
+        let new_value = match &new_entry.recipe_output_of { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.recipe_output_of { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            recipe_flow
+                .recipe_output_of(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            recipe_process.recipe_outputs(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_recipe_flow::recipe_output_of index {:?}", e);
+    }
+    if new_entry.recipe_clause_of != prev_entry.recipe_clause_of {
+        let new_value = match &new_entry.recipe_clause_of { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.recipe_clause_of { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            recipe_flow
+                .recipe_clause_of(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            recipe_exchange.recipe_stipulates(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_recipe_flow::recipe_clause_of index {:?}", e);
+    }
+
+    construct_response(&base_address, &meta, &new_entry)
+}
+
+pub fn handle_delete_recipe_flow(revision_id: ActionHash) -> RecordAPIResult<bool>
+{
+    // load the record to ensure it is of the correct type
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // handle link fields
+    let e = update_index!(recipe_flow.recipe_flow_resource.not(&vec![entry.recipe_flow_resource]), recipe_resource.recipe_flows(&base_address));
+    hdk::prelude::debug!("handle_delete_recipe_flow::recipe_flow_resource index {:?}", e);
+
+    if let Some(recipe_process_address) = entry.recipe_input_of {
+        let e = update_index!(recipe_flow.recipe_input_of.not(&vec![recipe_process_address]), recipe_process.recipe_inputs(&base_address));
+        hdk::prelude::debug!("handle_delete_recipe_flow::recipe_input_of index {:?}", e);
+    }
+    if let Some(recipe_process_address) = entry.recipe_output_of {
+        let e = update_index!(recipe_flow.recipe_output_of.not(&vec![recipe_process_address]), recipe_process.recipe_outputs(&base_address));
+        hdk::prelude::debug!("handle_delete_recipe_flow::recipe_output_of index {:?}", e);
+    }
+    if let Some(recipe_exchange_address) = entry.recipe_clause_of {
+        let e = update_index!(recipe_flow.recipe_clause_of.not(&vec![recipe_exchange_address]), recipe_exchange.recipe_stipulates(&base_address));
+        hdk::prelude::debug!("handle_delete_recipe_flow::recipe_clause_of index {:?}", e);
+    }
+
+    // delete entry last, as it must be present in order for links to be removed
+    delete_record::<EntryStorage>(&revision_id)
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &RecipeFlowAddress, meta: &SignedActionHashed, e: &EntryData,
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        recipe_flow: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            action: e.action.to_owned(),
+            recipe_flow_resource: e.recipe_flow_resource.to_owned(),
+            resource_quantity: e.resource_quantity.to_owned(),
+            effort_quantity: e.effort_quantity.to_owned(),
+            recipe_input_of: e.recipe_input_of.to_owned(),
+            recipe_output_of: e.recipe_output_of.to_owned(),
+            recipe_clause_of: e.recipe_clause_of.to_owned(),
+            stage: e.stage.to_owned(),
+            state: e.state.to_owned(),
+            note: e.note.to_owned(),
+        }
+    })
+}
+
+//---------------- READ ----------------
+
+/// Properties accessor for zome config
+fn read_recipe_flow_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.recipe_flow.index_zome)
+}
+
+/// Properties accessor for zome config
+fn read_recipe_resource_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.recipe_flow.recipe_resource_index_zome
+}
+
+/// Properties accessor for zome config
+fn read_recipe_process_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.recipe_flow.recipe_process_index_zome
+}
+
+/// Properties accessor for zome config
+fn read_recipe_exchange_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.recipe_flow.recipe_exchange_index_zome
+}
@@ -0,0 +1,164 @@
+/**
+ * hREA recipe flow zome internal data structures
+ *
+ * Required by the zome itself, and for any DNA-local zomes interacting with its
+ * storage API directly.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+
+use hc_zome_dna_auth_resolver_core::AvailableCapability;
+use hdk_records::{
+    RecordAPIResult, DataIntegrityError,
+    MaybeUndefined,
+    generate_record_entry,
+    record_interface::{Updateable},
+};
+use vf_measurement::QuantityValue;
+
+pub use vf_attributes_hdk::{
+    ActionId,
+    RecipeResourceAddress,
+    RecipeProcessAddress,
+    RecipeExchangeAddress,
+    ProcessSpecificationAddress,
+};
+
+use vf_actions::{ validate_recipe_flow_action };
+
+use hc_zome_rea_recipe_flow_rpc::{ CreateRequest, UpdateRequest };
+
+pub use vf_attributes_hdk::RecipeFlowAddress;
+pub use hc_zome_rea_recipe_flow_storage_consts::RECIPE_FLOW_ENTRY_TYPE;
+
+// :SHONK: needed as re-export in zome logic to allow validation logic to parse entries
+pub use hdk_records::record_interface::Identified;
+
+//--------------- ZOME CONFIGURATION ATTRIBUTES ----------------
+
+// :TODO: remove this, replace with reference to appropriate namespacing of zome config
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+pub struct DnaConfigSlice {
+    pub recipe_flow: RecipeFlowZomeConfig,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+pub struct RecipeFlowZomeConfig {
+    pub index_zome: String,
+    pub recipe_resource_index_zome: Option<String>,
+    pub recipe_process_index_zome: Option<String>,
+    pub recipe_exchange_index_zome: Option<String>,
+}
+
+//---------------- RECORD INTERNALS & VALIDATION ----------------
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+pub struct EntryData {
+    pub action: ActionId,
+    pub recipe_flow_resource: RecipeResourceAddress,
+    pub resource_quantity: Option<QuantityValue>,
+    pub effort_quantity: Option<QuantityValue>,
+    pub recipe_input_of: Option<RecipeProcessAddress>,
+    pub recipe_output_of: Option<RecipeProcessAddress>,
+    pub recipe_clause_of: Option<RecipeExchangeAddress>,
+    pub stage: Option<ProcessSpecificationAddress>,
+    pub state: Option<String>,
+    pub note: Option<String>,
+    pub _nonce: Bytes,
+}
+
+impl EntryData {
+    pub fn validate_action(&self) -> Result<(), String> {
+        validate_recipe_flow_action(self.action.to_owned(), self.recipe_input_of.to_owned(), self.recipe_output_of.to_owned())
+    }
+
+    pub fn validate_or_fields(&self) -> Result<(), String> {
+        if !(self.resource_quantity.is_some() || self.effort_quantity.is_some()) {
+            return Err("RecipeFlow must include either a resource quantity or an effort quantity".into());
+        }
+        if !(self.recipe_input_of.is_some() || self.recipe_output_of.is_some() || self.recipe_clause_of.is_some()) {
+            return Err("RecipeFlow must be an input or output of a recipe process, or a clause of a recipe exchange".into());
+        }
+        Ok(())
+    }
+}
+
+generate_record_entry!(EntryData, RecipeFlowAddress, EntryStorage);
+
+//---------------- Holochain App Entry And Link Types Setup ----------------
+
+#[hdk_entry_defs(skip_hdk_extern = true)]
+#[unit_enum(EntryTypesUnit)]
+pub enum EntryTypes {
+    RecipeFlow(EntryStorage),
+    #[entry_def(visibility = "private")]
+    AvailableCapability(AvailableCapability)
+}
+
+impl From<EntryStorage> for EntryTypes
+{
+    fn from(e: EntryStorage) -> EntryTypes
+    {
+        EntryTypes::RecipeFlow(e)
+    }
+}
+impl TryFrom<AvailableCapability> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: AvailableCapability) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::AvailableCapability(e))
+    }
+}
+
+#[hdk_link_types(skip_no_mangle = true)]
+pub enum LinkTypes {
+    // relates to dna-auth-resolver mixin
+    // and remote authorizations
+    AvailableCapability
+}
+
+//---------------- CREATE ----------------
+
+/// Pick relevant fields out of I/O record into underlying DHT entry
+impl TryFrom<CreateRequest> for EntryData {
+    type Error = DataIntegrityError;
+
+    fn try_from(e: CreateRequest) -> RecordAPIResult<EntryData> {
+        Ok(EntryData {
+            action: e.action.to_owned(),
+            recipe_flow_resource: e.recipe_flow_resource.to_owned(),
+            resource_quantity: e.resource_quantity.into(),
+            effort_quantity: e.effort_quantity.into(),
+            recipe_input_of: e.recipe_input_of.into(),
+            recipe_output_of: e.recipe_output_of.into(),
+            recipe_clause_of: e.recipe_clause_of.into(),
+            stage: e.stage.into(),
+            state: e.state.into(),
+            note: e.note.into(),
+            _nonce: random_bytes(32)?,
+        })
+    }
+}
+
+//---------------- UPDATE ----------------
+
+/// Handles update operations by merging any newly provided fields
+impl Updateable<UpdateRequest> for EntryData {
+    fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
+        Ok(EntryData {
+            action: if !e.action.is_some() { self.action.to_owned() } else { e.action.to_owned().unwrap() },
+            recipe_flow_resource: if !e.recipe_flow_resource.is_some() { self.recipe_flow_resource.to_owned() } else { e.recipe_flow_resource.to_owned().unwrap() },
+            resource_quantity: if e.resource_quantity == MaybeUndefined::Undefined { self.resource_quantity.to_owned() } else { e.resource_quantity.to_owned().into() },
+            effort_quantity: if e.effort_quantity == MaybeUndefined::Undefined { self.effort_quantity.to_owned() } else { e.effort_quantity.to_owned().into() },
+            recipe_input_of: if e.recipe_input_of == MaybeUndefined::Undefined { self.recipe_input_of.to_owned() } else { e.recipe_input_of.to_owned().into() },
+            recipe_output_of: if e.recipe_output_of == MaybeUndefined::Undefined { self.recipe_output_of.to_owned() } else { e.recipe_output_of.to_owned().into() },
+            recipe_clause_of: if e.recipe_clause_of == MaybeUndefined::Undefined { self.recipe_clause_of.to_owned() } else { e.recipe_clause_of.to_owned().into() },
+            stage: if e.stage == MaybeUndefined::Undefined { self.stage.to_owned() } else { e.stage.to_owned().into() },
+            state: if e.state == MaybeUndefined::Undefined { self.state.to_owned() } else { e.state.to_owned().into() },
+            note: if e.note == MaybeUndefined::Undefined { self.note.to_owned() } else { e.note.to_owned().into() },
+            _nonce: self._nonce.to_owned(),
+        })
+    }
+}
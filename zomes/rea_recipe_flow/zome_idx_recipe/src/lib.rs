@@ -0,0 +1,16 @@
+/**
+ * RecipeFlow query indexes for recipe DNA
+ *
+ * @package hREA
+ * @since   2026-10-18
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_recipe_flow_rpc::*;
+
+#[index_zome]
+struct RecipeFlow {
+    recipe_flow_resource: Local<recipe_resource, recipe_flows>,
+    recipe_input_of: Local<recipe_process, recipe_inputs>,
+    recipe_output_of: Local<recipe_process, recipe_outputs>,
+    recipe_clause_of: Local<recipe_exchange, recipe_stipulates>,
+}
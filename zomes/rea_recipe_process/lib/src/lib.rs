@@ -0,0 +1,141 @@
+/**
+ * hREA recipe process zome library API
+ *
+ * Contains helper methods that can be used to manipulate `RecipeProcess` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_recipe_process_storage::*;
+use hc_zome_rea_recipe_process_rpc::*;
+
+pub use hc_zome_rea_recipe_process_storage::RECIPE_PROCESS_ENTRY_TYPE;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.recipe_process.index_zome)
+}
+
+pub fn handle_create_recipe_process<S>(entry_def_id: S, recipe_process: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, recipe_process)?;
+
+    // handle link fields
+    if let Some(process_conforms_to) = &entry_resp.process_conforms_to {
+        let e = create_index!(recipe_process.process_conforms_to(process_conforms_to), process_specification.recipe_processes(&base_address));
+        hdk::prelude::debug!("handle_create_recipe_process::process_conforms_to index {:?}", e);
+    };
+
+    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_recipe_process(address: RecipeProcessAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_update_recipe_process(recipe_process: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    let revision_hash = recipe_process.get_revision_id().clone();
+    let (meta, base_address, new_entry, prev_entry): (_, RecipeProcessAddress, EntryData, EntryData) = update_record(&revision_hash, recipe_process)?;
+
+    if new_entry.process_conforms_to != prev_entry.process_conforms_to {
+        let new_value = match &new_entry.process_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.process_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            recipe_process
+                .process_conforms_to(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            process_specification.recipe_processes(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_recipe_process::process_conforms_to index {:?}", e);
+    }
+
+    construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_delete_recipe_process(revision_id: ActionHash) -> RecordAPIResult<bool>
+{
+    // load the record to ensure it is of the correct type
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // handle link fields
+    if let Some(process_specification_address) = entry.process_conforms_to {
+        let e = update_index!(recipe_process.process_conforms_to.not(&vec![process_specification_address]), process_specification.recipe_processes(&base_address));
+        hdk::prelude::debug!("handle_delete_recipe_process::process_conforms_to index {:?}", e);
+    }
+
+    // delete entry last, as it must be present in order for links to be removed
+    delete_record::<EntryStorage>(&revision_id)
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &RecipeProcessAddress, meta: &SignedActionHashed, e: &EntryData, (
+        recipe_inputs,
+        recipe_outputs,
+    ): (
+        Vec<RecipeFlowAddress>,
+        Vec<RecipeFlowAddress>,
+    ),
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        recipe_process: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            name: e.name.to_owned(),
+            process_conforms_to: e.process_conforms_to.to_owned(),
+            process_classified_as: e.process_classified_as.to_owned(),
+            has_duration: e.has_duration.to_owned(),
+            note: e.note.to_owned(),
+            recipe_inputs: recipe_inputs.to_owned(),
+            recipe_outputs: recipe_outputs.to_owned(),
+        }
+    })
+}
+
+//---------------- READ ----------------
+
+/// Properties accessor for zome config
+fn read_recipe_process_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.recipe_process.index_zome)
+}
+
+/// Properties accessor for zome config
+fn read_process_specification_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.recipe_process.process_specification_index_zome
+}
+
+// @see construct_response
+fn get_link_fields(base_address: &RecipeProcessAddress) -> RecordAPIResult<(
+    Vec<RecipeFlowAddress>,
+    Vec<RecipeFlowAddress>,
+)> {
+    Ok((
+        read_index!(recipe_process(base_address).recipe_inputs)?,
+        read_index!(recipe_process(base_address).recipe_outputs)?,
+    ))
+}
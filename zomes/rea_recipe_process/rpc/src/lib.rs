@@ -0,0 +1,155 @@
+/**
+ * hREA recipe process zome I/O data structures
+ *
+ * Required by packages wishing to interact with the zome via its standard RPC interface,
+ * and by the zome API handlers accepting these parameters.
+ *
+ * @package hREA
+ */
+use holochain_serialized_bytes::prelude::*;
+
+use serde_maybe_undefined::MaybeUndefined;
+pub use vf_attributes_hdk::{
+    RecipeProcessAddress,
+    RecipeFlowAddress,
+    ProcessSpecificationAddress,
+    ExternalURL,
+    ByAction, ActionHash, ByRevision, RecordMeta, RevisionMeta,
+};
+
+//---------------- DURATIONS ----------------
+
+/// Units of time which a `Duration` may be expressed in
+///
+#[derive(Clone, Copy, Serialize, Deserialize, SerializedBytes, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TimeUnit {
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+/// Time period during which a recipe process is expected to run
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Duration {
+    pub numeric_duration: f64,
+    pub unit_type: TimeUnit,
+}
+
+impl Duration {
+    /// Approximate length of this duration in seconds, treating months as 30 days and years as 365 days
+    pub fn as_seconds(&self) -> f64 {
+        self.numeric_duration * match self.unit_type {
+            TimeUnit::Year => 31_536_000.0,
+            TimeUnit::Month => 2_592_000.0,
+            TimeUnit::Week => 604_800.0,
+            TimeUnit::Day => 86_400.0,
+            TimeUnit::Hour => 3_600.0,
+            TimeUnit::Minute => 60.0,
+            TimeUnit::Second => 1.0,
+        }
+    }
+}
+
+//---------------- EXTERNAL RECORD STRUCTURE ----------------
+
+/// I/O struct to describe the complete record, including all managed link fields
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub id: RecipeProcessAddress,
+    pub revision_id: ActionHash,
+    pub meta: RecordMeta,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process_conforms_to: Option<ProcessSpecificationAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process_classified_as: Option<Vec<ExternalURL>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_duration: Option<Duration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recipe_inputs: Vec<RecipeFlowAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recipe_outputs: Vec<RecipeFlowAddress>,
+}
+
+/// I/O struct to describe what is returned outside the gateway.
+/// Responses are usually returned as named attributes in order to leave space
+/// for future additional return values.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseData {
+    pub recipe_process: Response,
+}
+
+//---------------- CREATE REQUEST ----------------
+
+/// I/O struct to describe the complete input record, including all managed links
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRequest {
+    pub name: String,
+    #[serde(default)]
+    pub process_conforms_to: MaybeUndefined<ProcessSpecificationAddress>,
+    #[serde(default)]
+    pub process_classified_as: MaybeUndefined<Vec<ExternalURL>>,
+    #[serde(default)]
+    pub has_duration: MaybeUndefined<Duration>,
+    #[serde(default)]
+    pub note: MaybeUndefined<String>,
+}
+
+impl<'a> CreateRequest {
+    // :TODO: accessors for field data
+}
+
+//---------------- UPDATE REQUEST ----------------
+
+/// I/O struct to describe the complete input record, including all managed links
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRequest {
+    pub revision_id: ActionHash,
+    #[serde(default)]
+    pub name: MaybeUndefined<String>,
+    #[serde(default)]
+    pub process_conforms_to: MaybeUndefined<ProcessSpecificationAddress>,
+    #[serde(default)]
+    pub process_classified_as: MaybeUndefined<Vec<ExternalURL>>,
+    #[serde(default)]
+    pub has_duration: MaybeUndefined<Duration>,
+    #[serde(default)]
+    pub note: MaybeUndefined<String>,
+}
+
+impl<'a> UpdateRequest {
+    pub fn get_revision_id(&'a self) -> &ActionHash {
+        &self.revision_id
+    }
+
+    // :TODO: accessors for other field data
+}
+
+//---------------- QUERY FILTER REQUEST ----------------
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryParams {
+    pub process_conforms_to: Option<ProcessSpecificationAddress>,
+    pub recipe_inputs: Option<RecipeFlowAddress>,
+    pub recipe_outputs: Option<RecipeFlowAddress>,
+}
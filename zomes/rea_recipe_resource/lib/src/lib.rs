@@ -0,0 +1,139 @@
+/**
+ * hREA recipe resource zome library API
+ *
+ * Contains helper methods that can be used to manipulate `RecipeResource` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_recipe_resource_storage::*;
+use hc_zome_rea_recipe_resource_rpc::*;
+
+pub use hc_zome_rea_recipe_resource_storage::RECIPE_RESOURCE_ENTRY_TYPE;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.recipe_resource.index_zome)
+}
+
+pub fn handle_create_recipe_resource<S>(entry_def_id: S, recipe_resource: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, recipe_resource)?;
+
+    // handle link fields
+    if let Some(resource_conforms_to) = &entry_resp.resource_conforms_to {
+        let e = create_index!(recipe_resource.resource_conforms_to(resource_conforms_to), resource_specification.recipe_resources(&base_address));
+        hdk::prelude::debug!("handle_create_recipe_resource::resource_conforms_to index {:?}", e);
+    };
+
+    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_recipe_resource(address: RecipeResourceAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_update_recipe_resource(recipe_resource: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    let revision_hash = recipe_resource.get_revision_id().clone();
+    let (meta, base_address, new_entry, prev_entry): (_, RecipeResourceAddress, EntryData, EntryData) = update_record(&revision_hash, recipe_resource)?;
+
+    if new_entry.resource_conforms_to != prev_entry.resource_conforms_to {
+        let new_value = match &new_entry.resource_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.resource_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            recipe_resource
+                .resource_conforms_to(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            resource_specification.recipe_resources(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_recipe_resource::resource_conforms_to index {:?}", e);
+    }
+
+    construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_delete_recipe_resource(revision_id: ActionHash) -> RecordAPIResult<bool>
+{
+    // load the record to ensure it is of the correct type
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // handle link fields
+    if let Some(resource_specification_address) = entry.resource_conforms_to {
+        let e = update_index!(recipe_resource.resource_conforms_to.not(&vec![resource_specification_address]), resource_specification.recipe_resources(&base_address));
+        hdk::prelude::debug!("handle_delete_recipe_resource::resource_conforms_to index {:?}", e);
+    }
+
+    // delete entry last, as it must be present in order for links to be removed
+    delete_record::<EntryStorage>(&revision_id)
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &RecipeResourceAddress, meta: &SignedActionHashed, e: &EntryData, (
+        recipe_flows,
+    ): (
+        Vec<RecipeFlowAddress>,
+    ),
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        recipe_resource: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            name: e.name.to_owned(),
+            resource_conforms_to: e.resource_conforms_to.to_owned(),
+            resource_classified_as: e.resource_classified_as.to_owned(),
+            unit_of_resource: e.unit_of_resource.to_owned(),
+            unit_of_effort: e.unit_of_effort.to_owned(),
+            substitutable: e.substitutable.to_owned(),
+            image: e.image.to_owned(),
+            note: e.note.to_owned(),
+            recipe_flows: recipe_flows.to_owned(),
+        }
+    })
+}
+
+//---------------- READ ----------------
+
+/// Properties accessor for zome config
+fn read_recipe_resource_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.recipe_resource.index_zome)
+}
+
+/// Properties accessor for zome config
+fn read_resource_specification_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.recipe_resource.resource_specification_index_zome
+}
+
+// @see construct_response
+fn get_link_fields(base_address: &RecipeResourceAddress) -> RecordAPIResult<(
+    Vec<RecipeFlowAddress>,
+)> {
+    Ok((
+        read_index!(recipe_resource(base_address).recipe_flows)?,
+    ))
+}
@@ -0,0 +1,55 @@
+/**
+ * hREA RecipeResource zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+
+use hc_zome_rea_recipe_resource_rpc::*;
+use hc_zome_rea_recipe_resource_lib::*;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub recipe_resource: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_recipe_resource(CreateParams { recipe_resource }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_recipe_resource(RECIPE_RESOURCE_ENTRY_TYPE, recipe_resource)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: RecipeResourceAddress,
+}
+
+#[hdk_extern]
+fn get_recipe_resource(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_recipe_resource(address)?)
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub recipe_resource: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_recipe_resource(UpdateParams { recipe_resource }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_recipe_resource(recipe_resource)?)
+}
+
+#[hdk_extern]
+fn delete_recipe_resource(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
+    Ok(handle_delete_recipe_resource(revision_id)?)
+}
@@ -12,6 +12,9 @@ pub use vf_attributes_hdk::{
     ActionHash, ByAddress, ByAction, RecordMeta, RevisionMeta,
     ResourceSpecificationAddress,
     EconomicResourceAddress,
+    CommitmentAddress,
+    IntentAddress,
+    RecipeResourceAddress,
     ExternalURL,
     UnitId,
     ByRevision,
@@ -118,4 +121,7 @@ impl<'a> UpdateRequest {
 #[serde(rename_all = "camelCase")]
 pub struct QueryParams {
     pub conforming_resources: Option<EconomicResourceAddress>,
+    pub commitments: Option<CommitmentAddress>,
+    pub intents: Option<IntentAddress>,
+    pub recipe_resources: Option<RecipeResourceAddress>,
 }
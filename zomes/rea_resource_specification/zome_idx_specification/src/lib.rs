@@ -11,4 +11,7 @@ use hdk_semantic_indexes_zome_lib::ByAddress; // disambiguate from record query
 #[index_zome]
 struct ResourceSpecification {
     conforming_resources: Remote<economic_resource, conforms_to>,
+    commitments: Remote<commitment, resource_conforms_to>,
+    intents: Remote<intent, resource_conforms_to>,
+    recipe_resources: Remote<recipe_resource, resource_conforms_to>,
 }
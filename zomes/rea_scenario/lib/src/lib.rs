@@ -0,0 +1,311 @@
+/**
+ * hREA scenario zome library API
+ *
+ * Contains helper methods that can be used to manipulate `Scenario` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult, OtherCellResult, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+    rpc::call_zome_method,
+};
+use hdk_semantic_indexes_client_lib::*;
+use vf_attributes_hdk::{ByAddress, ActionId};
+use vf_measurement::{QuantityValue, add};
+use vf_actions::{ProcessType, get_builtin_action};
+
+use hc_zome_rea_scenario_storage::*;
+use hc_zome_rea_scenario_rpc::*;
+use hc_zome_rea_commitment_rpc::{
+    ResponseData as CommitmentResponseData,
+    Response as CommitmentResponse,
+};
+use hc_zome_rea_intent_rpc::{
+    ResponseData as IntentResponseData,
+    Response as IntentResponse,
+};
+
+pub use hc_zome_rea_scenario_storage::SCENARIO_ENTRY_TYPE;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.scenario.index_zome)
+}
+
+pub fn handle_create_scenario<S>(entry_def_id: S, scenario: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, scenario)?;
+
+    // handle link fields
+    if let Some(refinement_of) = &entry_resp.refinement_of {
+        let e = create_index!(scenario.refinement_of(refinement_of), scenario.refinements(&base_address));
+        hdk::prelude::debug!("handle_create_scenario::refinement_of index {:?}", e);
+    }
+    if let Some(defined_as) = &entry_resp.defined_as {
+        let e = create_index!(scenario.defined_as(defined_as), scenario_definition.defines_scenario(&base_address));
+        hdk::prelude::debug!("handle_create_scenario::defined_as index {:?}", e);
+    }
+
+    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_scenario(address: ScenarioAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_update_scenario(scenario: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    let revision_hash = scenario.get_revision_id().clone();
+    let (meta, base_address, new_entry, prev_entry): (_, ScenarioAddress, EntryData, EntryData) = update_record(&revision_hash, scenario)?;
+
+    // handle link fields
+    if new_entry.refinement_of != prev_entry.refinement_of {
+        let new_value = match &new_entry.refinement_of { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.refinement_of { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            scenario
+                .refinement_of(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            scenario.refinements(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_scenario::refinement_of index {:?}", e);
+    }
+    if new_entry.defined_as != prev_entry.defined_as {
+        let new_value = match &new_entry.defined_as { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.defined_as { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            scenario
+                .defined_as(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            scenario_definition.defines_scenario(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_scenario::defined_as index {:?}", e);
+    }
+
+    construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
+}
+
+pub fn handle_delete_scenario(revision_id: ActionHash) -> RecordAPIResult<bool>
+{
+    // load the record to ensure it is of the correct type
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // handle link fields
+    if let Some(scenario_address) = entry.refinement_of {
+        let e = update_index!(scenario.refinement_of.not(&vec![scenario_address]), scenario.refinements(&base_address));
+        hdk::prelude::debug!("handle_delete_scenario::refinement_of index {:?}", e);
+    }
+    if let Some(scenario_definition_address) = entry.defined_as {
+        let e = update_index!(scenario.defined_as.not(&vec![scenario_definition_address]), scenario_definition.defines_scenario(&base_address));
+        hdk::prelude::debug!("handle_delete_scenario::defined_as index {:?}", e);
+    }
+
+    // delete entry last, as it must be present in order for links to be removed
+    delete_record::<EntryStorage>(&revision_id)
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &ScenarioAddress, meta: &SignedActionHashed, e: &EntryData, (
+        refinements,
+        plans,
+        processes,
+        commitments,
+        intents,
+    ): (
+        Vec<ScenarioAddress>,
+        Vec<PlanAddress>,
+        Vec<ProcessAddress>,
+        Vec<CommitmentAddress>,
+        Vec<IntentAddress>,
+    ),
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        scenario: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            name: e.name.to_owned(),
+            note: e.note.to_owned(),
+            has_beginning: e.has_beginning.to_owned(),
+            has_end: e.has_end.to_owned(),
+            in_scope_of: e.in_scope_of.to_owned(),
+            refinement_of: e.refinement_of.to_owned(),
+            defined_as: e.defined_as.to_owned(),
+            refinements: refinements.to_owned(),
+            plans: plans.to_owned(),
+            processes: processes.to_owned(),
+            commitments: commitments.to_owned(),
+            intents: intents.to_owned(),
+        }
+    })
+}
+
+//---------------- READ ----------------
+
+/// Properties accessor for zome config
+fn read_scenario_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.scenario.index_zome)
+}
+
+/// Properties accessor for zome config
+fn read_scenario_definition_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.scenario.scenario_definition_index_zome
+}
+
+// @see construct_response
+fn get_link_fields(base_address: &ScenarioAddress) -> RecordAPIResult<(
+    Vec<ScenarioAddress>,
+    Vec<PlanAddress>,
+    Vec<ProcessAddress>,
+    Vec<CommitmentAddress>,
+    Vec<IntentAddress>,
+)> {
+    Ok((
+        read_index!(scenario(base_address).refinements)?,
+        read_index!(scenario(base_address).plans)?,
+        read_index!(scenario(base_address).processes)?,
+        read_index!(scenario(base_address).commitments)?,
+        read_index!(scenario(base_address).intents)?,
+    ))
+}
+
+//---------------- COMPARISON ----------------
+
+// remote API methods in the planning DNA used to read scoped commitments & intents
+const READ_COMMITMENT_API_METHOD: &str = "read_commitment";
+const READ_INTENT_API_METHOD: &str = "read_intent";
+
+/// Maximum depth of refinements totalled beneath each compared scenario, to bound the number of reads
+const MAX_REFINEMENT_DEPTH: usize = 10;
+
+/// Total the quantities of all `Commitment`s and `Intent`s scoped to each of the given scenarios
+/// and their refinements, so that alternative plans or budgets can be evaluated side by side.
+///
+/// Results are returned in the same order as the requested scenarios.
+///
+pub fn handle_compare_scenarios(request: CompareScenariosRequest) -> RecordAPIResult<ComparisonResponseData>
+{
+    let scenarios = request.scenarios.iter()
+        .map(total_scenario)
+        .collect::<RecordAPIResult<Vec<ScenarioTotals>>>()?;
+
+    Ok(ComparisonResponseData { scenarios })
+}
+
+fn total_scenario(scenario: &ScenarioAddress) -> RecordAPIResult<ScenarioTotals>
+{
+    let mut totals = ScenarioTotals {
+        scenario: scenario.to_owned(),
+        commitment_count: 0,
+        intent_count: 0,
+        inputs: FlowTotals::default(),
+        outputs: FlowTotals::default(),
+        transfers: FlowTotals::default(),
+    };
+
+    let mut visited = vec![];
+    accumulate_scenario(scenario, 0, &mut totals, &mut visited)?;
+
+    Ok(totals)
+}
+
+/// Add the flows scoped to `scenario` to `totals`, then recurse into its refinements.
+/// `visited` holds all scenarios already totalled, so that each is only counted once.
+///
+fn accumulate_scenario(
+    scenario: &ScenarioAddress,
+    depth: usize,
+    totals: &mut ScenarioTotals,
+    visited: &mut Vec<ScenarioAddress>,
+) -> RecordAPIResult<()>
+{
+    visited.push(scenario.to_owned());
+
+    let commitments: Vec<CommitmentAddress> = read_index!(scenario(scenario).commitments)?;
+    let intents: Vec<IntentAddress> = read_index!(scenario(scenario).intents)?;
+
+    for commitment in commitments.iter() {
+        let resp: OtherCellResult<CommitmentResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+            commitment, &READ_COMMITMENT_API_METHOD, ByAddress { address: commitment.to_owned() }, LinkTypes::AvailableCapability,
+        );
+        let CommitmentResponse { action, resource_quantity, effort_quantity, .. } = resp?.commitment;
+        accumulate_flow(totals, &action, resource_quantity, effort_quantity)?;
+        totals.commitment_count += 1;
+    }
+
+    for intent in intents.iter() {
+        let resp: OtherCellResult<IntentResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+            intent, &READ_INTENT_API_METHOD, ByAddress { address: intent.to_owned() }, LinkTypes::AvailableCapability,
+        );
+        let IntentResponse { action, resource_quantity, effort_quantity, satisfied_by, .. } = resp?.intent;
+        // satisfied intents are accounted for by the commitments or events satisfying them
+        if !satisfied_by.is_empty() {
+            continue;
+        }
+        accumulate_flow(totals, &action, resource_quantity, effort_quantity)?;
+        totals.intent_count += 1;
+    }
+
+    if depth >= MAX_REFINEMENT_DEPTH {
+        hdk::prelude::debug!("accumulate_scenario::skipping refinements of excessively deep scenario {:?}", scenario);
+        return Ok(());
+    }
+    let refinements: Vec<ScenarioAddress> = read_index!(scenario(scenario).refinements)?;
+    for refinement in refinements.iter() {
+        if !visited.contains(refinement) {
+            accumulate_scenario(refinement, depth + 1, totals, visited)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Add the quantities of a flow to the totals for the direction implied by its `action`
+fn accumulate_flow(totals: &mut ScenarioTotals, action: &ActionId, resource_quantity: Option<QuantityValue>, effort_quantity: Option<QuantityValue>) -> RecordAPIResult<()>
+{
+    let flow_totals = match get_builtin_action(action.as_ref()).map(|a| a.input_output) {
+        Some(ProcessType::Input) => &mut totals.inputs,
+        Some(ProcessType::Output) => &mut totals.outputs,
+        _ => &mut totals.transfers,
+    };
+
+    accumulate(&mut flow_totals.resource_quantities, resource_quantity)?;
+    accumulate(&mut flow_totals.effort_quantities, effort_quantity)
+}
+
+/// Add a quantity to the running total for its unit, starting a new total for unseen units
+fn accumulate(totals: &mut Vec<QuantityValue>, quantity: Option<QuantityValue>) -> RecordAPIResult<()>
+{
+    let quantity = match quantity {
+        Some(q) => q,
+        None => return Ok(()),
+    };
+
+    match totals.iter_mut().find(|t| t.get_unit() == quantity.get_unit()) {
+        Some(total) => {
+            *total = add(total.to_owned(), quantity)?;
+        },
+        None => totals.push(quantity),
+    }
+    Ok(())
+}
@@ -0,0 +1,134 @@
+/**
+ * hREA scenario zome internal data structures
+ *
+ * Required by the zome itself, and for any DNA-local zomes interacting with its
+ * storage API directly.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+
+use hc_zome_dna_auth_resolver_core::AvailableCapability;
+use hdk_records::{
+    RecordAPIResult, DataIntegrityError,
+    MaybeUndefined,
+    generate_record_entry,
+    record_interface::{Updateable},
+};
+
+pub use vf_attributes_hdk::{
+    DateTime, FixedOffset,
+    ScenarioDefinitionAddress,
+};
+
+use hc_zome_rea_scenario_rpc::{ CreateRequest, UpdateRequest };
+
+pub use vf_attributes_hdk::ScenarioAddress;
+pub use hc_zome_rea_scenario_storage_consts::SCENARIO_ENTRY_TYPE;
+
+// :SHONK: needed as re-export in zome logic to allow validation logic to parse entries
+pub use hdk_records::record_interface::Identified;
+
+//--------------- ZOME CONFIGURATION ATTRIBUTES ----------------
+
+// :TODO: remove this, replace with reference to appropriate namespacing of zome config
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+pub struct DnaConfigSlice {
+    pub scenario: ScenarioZomeConfig,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+pub struct ScenarioZomeConfig {
+    pub index_zome: String,
+    pub scenario_definition_index_zome: Option<String>,
+}
+
+//---------------- RECORD INTERNALS & VALIDATION ----------------
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+pub struct EntryData {
+    pub name: String,
+    pub note: Option<String>,
+    pub has_beginning: Option<DateTime<FixedOffset>>,
+    pub has_end: Option<DateTime<FixedOffset>>,
+    pub in_scope_of: Option<Vec<String>>,
+    pub refinement_of: Option<ScenarioAddress>,
+    pub defined_as: Option<ScenarioDefinitionAddress>,
+    pub _nonce: Bytes,
+}
+
+generate_record_entry!(EntryData, ScenarioAddress, EntryStorage);
+
+//---------------- Holochain App Entry And Link Types Setup ----------------
+
+#[hdk_entry_defs(skip_hdk_extern = true)]
+#[unit_enum(EntryTypesUnit)]
+pub enum EntryTypes {
+    Scenario(EntryStorage),
+    #[entry_def(visibility = "private")]
+    AvailableCapability(AvailableCapability)
+}
+
+impl From<EntryStorage> for EntryTypes
+{
+    fn from(e: EntryStorage) -> EntryTypes
+    {
+        EntryTypes::Scenario(e)
+    }
+}
+impl TryFrom<AvailableCapability> for EntryTypes {
+    type Error = WasmError;
+
+    fn try_from(e: AvailableCapability) -> Result<EntryTypes, Self::Error>
+    {
+        Ok(EntryTypes::AvailableCapability(e))
+    }
+}
+
+#[hdk_link_types(skip_no_mangle = true)]
+pub enum LinkTypes {
+    // relates to dna-auth-resolver mixin
+    // and remote authorizations
+    AvailableCapability
+}
+
+//---------------- CREATE ----------------
+
+/// Pick relevant fields out of I/O record into underlying DHT entry
+impl TryFrom<CreateRequest> for EntryData {
+    type Error = DataIntegrityError;
+
+    fn try_from(e: CreateRequest) -> RecordAPIResult<EntryData> {
+        Ok(EntryData {
+            name: e.name.to_owned(),
+            note: e.note.into(),
+            has_beginning: e.has_beginning.into(),
+            has_end: e.has_end.into(),
+            in_scope_of: e.in_scope_of.into(),
+            refinement_of: e.refinement_of.into(),
+            defined_as: e.defined_as.into(),
+            _nonce: random_bytes(32)?,
+        })
+    }
+}
+
+//---------------- UPDATE ----------------
+
+/// Handles update operations by merging any newly provided fields
+impl Updateable<UpdateRequest> for EntryData {
+    fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
+        Ok(EntryData {
+            name: match &e.name {
+                MaybeUndefined::Some(name) => name.clone(),
+                _ => self.name.clone(),
+            },
+            note: if e.note == MaybeUndefined::Undefined { self.note.to_owned() } else { e.note.to_owned().into() },
+            has_beginning: if e.has_beginning == MaybeUndefined::Undefined { self.has_beginning.to_owned() } else { e.has_beginning.to_owned().into() },
+            has_end: if e.has_end == MaybeUndefined::Undefined { self.has_end.to_owned() } else { e.has_end.to_owned().into() },
+            in_scope_of: if e.in_scope_of == MaybeUndefined::Undefined { self.in_scope_of.to_owned() } else { e.in_scope_of.to_owned().into() },
+            refinement_of: if e.refinement_of == MaybeUndefined::Undefined { self.refinement_of.to_owned() } else { e.refinement_of.to_owned().into() },
+            defined_as: if e.defined_as == MaybeUndefined::Undefined { self.defined_as.to_owned() } else { e.defined_as.to_owned().into() },
+            _nonce: self._nonce.to_owned(),
+        })
+    }
+}
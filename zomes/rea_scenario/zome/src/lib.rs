@@ -0,0 +1,60 @@
+/**
+ * hREA Scenario zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+
+use hc_zome_rea_scenario_rpc::*;
+use hc_zome_rea_scenario_lib::*;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub scenario: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_scenario(CreateParams { scenario }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_scenario(SCENARIO_ENTRY_TYPE, scenario)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: ScenarioAddress,
+}
+
+#[hdk_extern]
+fn get_scenario(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_scenario(address)?)
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub scenario: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_scenario(UpdateParams { scenario }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_scenario(scenario)?)
+}
+
+#[hdk_extern]
+fn compare_scenarios(request: CompareScenariosRequest) -> ExternResult<ComparisonResponseData> {
+    Ok(handle_compare_scenarios(request)?)
+}
+
+#[hdk_extern]
+fn delete_scenario(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
+    Ok(handle_delete_scenario(revision_id)?)
+}
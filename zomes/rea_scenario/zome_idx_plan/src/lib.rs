@@ -0,0 +1,19 @@
+/**
+ * Scenario query indexes for plan DNA
+ *
+ * @package hREA
+ * @since   2026-10-18
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_scenario_rpc::*;
+
+#[index_zome]
+struct Scenario {
+    refinement_of: Local<scenario, refinements>,
+    refinements: Local<scenario, refinement_of>,
+    defined_as: Local<scenario_definition, defines_scenario>,
+    plans: Local<plan, refinement_of>,
+    processes: Remote<process, in_scenario>,
+    commitments: Remote<commitment, in_scenario>,
+    intents: Remote<intent, in_scenario>,
+}
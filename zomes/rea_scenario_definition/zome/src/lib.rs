@@ -0,0 +1,55 @@
+/**
+ * hREA ScenarioDefinition zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+
+use hc_zome_rea_scenario_definition_rpc::*;
+use hc_zome_rea_scenario_definition_lib::*;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub scenario_definition: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_scenario_definition(CreateParams { scenario_definition }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_scenario_definition(SCENARIO_DEFINITION_ENTRY_TYPE, scenario_definition)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: ScenarioDefinitionAddress,
+}
+
+#[hdk_extern]
+fn get_scenario_definition(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_scenario_definition(address)?)
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub scenario_definition: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_scenario_definition(UpdateParams { scenario_definition }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_scenario_definition(scenario_definition)?)
+}
+
+#[hdk_extern]
+fn delete_scenario_definition(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
+    Ok(handle_delete_scenario_definition(revision_id)?)
+}
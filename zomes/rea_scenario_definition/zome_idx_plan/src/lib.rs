@@ -0,0 +1,13 @@
+/**
+ * Scenario definition query indexes for plan DNA
+ *
+ * @package hREA
+ * @since   2026-10-18
+ */
+use hdk_semantic_indexes_zome_derive::index_zome;
+use hc_zome_rea_scenario_definition_rpc::*;
+
+#[index_zome]
+struct ScenarioDefinition {
+    defines_scenario: Local<scenario, defined_as>,
+}
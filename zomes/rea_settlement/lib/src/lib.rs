@@ -0,0 +1,139 @@
+/**
+ * hREA settlement zome library API
+ *
+ * Contains helper methods that can be used to manipulate `Settlement` data
+ * structures in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * @package hREA
+ */
+use paste::paste;
+use hdk_records::{
+    RecordAPIResult, SignedActionHashed,
+    records::{
+        create_record,
+        read_record_entry,
+        read_record_entry_by_action,
+        update_record,
+        delete_record,
+    },
+    metadata::read_revision_metadata_abbreviated,
+};
+use hdk_semantic_indexes_client_lib::*;
+
+use hc_zome_rea_settlement_storage::*;
+use hc_zome_rea_settlement_rpc::*;
+
+pub use hc_zome_rea_settlement_storage::SETTLEMENT_ENTRY_TYPE;
+
+/// properties accessor for zome config
+fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.settlement.index_zome)
+}
+
+pub fn handle_create_settlement<S>(entry_def_id: S, settlement: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display
+{
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, settlement)?;
+
+    // handle link fields
+    let e = create_index!(settlement.settled_by(&entry_resp.settled_by), economic_event.settles(&base_address));
+    hdk::prelude::debug!("handle_create_settlement::settled_by index {:?}", e);
+    let e = create_index!(settlement.settles(&entry_resp.settles), claim.settled_by(&base_address));
+    hdk::prelude::debug!("handle_create_settlement::settles index {:?}", e);
+
+    construct_response(&base_address, &meta, &entry_resp)
+}
+
+pub fn handle_get_settlement(address: SettlementAddress) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    construct_response(&base_address, &meta, &entry)
+}
+
+pub fn handle_update_settlement(settlement: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    let revision_hash = settlement.get_revision_id().clone();
+    let (meta, base_address, new_entry, prev_entry): (_, SettlementAddress, EntryData, EntryData) = update_record(&revision_hash, settlement)?;
+
+    // handle link fields
+    if new_entry.settled_by != prev_entry.settled_by {
+        let new_value = vec![new_entry.settled_by.to_owned()];
+        let prev_value = vec![prev_entry.settled_by.to_owned()];
+        let e = update_index!(
+            settlement
+                .settled_by(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            economic_event.settles(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_settlement::settled_by index {:?}", e);
+    }
+    if new_entry.settles != prev_entry.settles {
+        let new_value = vec![new_entry.settles.to_owned()];
+        let prev_value = vec![prev_entry.settles.to_owned()];
+        let e = update_index!(
+            settlement
+                .settles(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            claim.settled_by(&base_address)
+        );
+        hdk::prelude::debug!("handle_update_settlement::settles index {:?}", e);
+    }
+
+    construct_response(&base_address, &meta, &new_entry)
+}
+
+pub fn handle_delete_settlement(revision_id: ActionHash) -> RecordAPIResult<bool>
+{
+    // load the record to ensure it is of the correct type
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // handle link fields
+    let e = update_index!(settlement.settled_by.not(&vec![entry.settled_by]), economic_event.settles(&base_address));
+    hdk::prelude::debug!("handle_delete_settlement::settled_by index {:?}", e);
+    let e = update_index!(settlement.settles.not(&vec![entry.settles]), claim.settled_by(&base_address));
+    hdk::prelude::debug!("handle_delete_settlement::settles index {:?}", e);
+
+    // delete entry last, as it must be present in order for links to be removed
+    delete_record::<EntryStorage>(&revision_id)
+}
+
+/// Create response from input DHT primitives
+fn construct_response<'a>(
+    address: &SettlementAddress, meta: &SignedActionHashed, e: &EntryData,
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        settlement: Response {
+            id: address.to_owned(),
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            settled_by: e.settled_by.to_owned(),
+            settles: e.settles.to_owned(),
+            resource_quantity: e.resource_quantity.to_owned(),
+            effort_quantity: e.effort_quantity.to_owned(),
+            note: e.note.to_owned(),
+        }
+    })
+}
+
+//---------------- READ ----------------
+
+/// Properties accessor for zome config
+fn read_settlement_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.settlement.index_zome)
+}
+
+/// Properties accessor for zome config
+fn read_economic_event_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.settlement.economic_event_index_zome
+}
+
+/// Properties accessor for zome config
+fn read_claim_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.settlement.claim_index_zome
+}
@@ -0,0 +1,55 @@
+/**
+ * hREA Settlement zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdk::prelude::*;
+
+use hc_zome_rea_settlement_rpc::*;
+use hc_zome_rea_settlement_lib::*;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub settlement: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_settlement(CreateParams { settlement }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_settlement(SETTLEMENT_ENTRY_TYPE, settlement)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReadParams {
+    pub address: SettlementAddress,
+}
+
+#[hdk_extern]
+fn get_settlement(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
+    Ok(handle_get_settlement(address)?)
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub settlement: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_settlement(UpdateParams { settlement }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_settlement(settlement)?)
+}
+
+#[hdk_extern]
+fn delete_settlement(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
+    Ok(handle_delete_settlement(revision_id)?)
+}
@@ -0,0 +1,61 @@
+/**
+ * Standard measurement units.
+ *
+ * A subset of UCUM unit codes, with dimensions and conversion factors relative to
+ * the SI base unit of each dimension as defined by the Ontology of units of Measure (OM).
+ * These are available for seeding into a DNA at genesis, so that commonly used units
+ * share the same `UnitId` across all apps using that DNA.
+ *
+ * @see https://ucum.org/ucum
+ * @see http://www.ontology-of-units-of-measure.org/
+ *
+ * @package hREA
+ * @since   2026-10-18
+ */
+use hdk_records::MaybeUndefined;
+use hc_zome_rea_unit_rpc::CreateRequest;
+
+// (symbol, label, dimension, conversion factor)
+const BUILTIN_UNITS: &[(&str, &str, &str, &str)] = &[
+    // dimensionless counts
+    ("1", "each", "count", "1"),
+    ("[doz]", "dozen", "count", "12"),
+    // mass
+    ("kg", "kilogram", "mass", "1"),
+    ("g", "gram", "mass", "0.001"),
+    ("mg", "milligram", "mass", "0.000001"),
+    ("t", "tonne", "mass", "1000"),
+    ("[lb_av]", "pound", "mass", "0.45359237"),
+    // length
+    ("m", "metre", "length", "1"),
+    ("mm", "millimetre", "length", "0.001"),
+    ("cm", "centimetre", "length", "0.01"),
+    ("km", "kilometre", "length", "1000"),
+    // area
+    ("m2", "square metre", "area", "1"),
+    ("har", "hectare", "area", "10000"),
+    // volume
+    ("m3", "cubic metre", "volume", "1"),
+    ("L", "litre", "volume", "0.001"),
+    ("mL", "millilitre", "volume", "0.000001"),
+    // time
+    ("s", "second", "time", "1"),
+    ("min", "minute", "time", "60"),
+    ("h", "hour", "time", "3600"),
+    ("d", "day", "time", "86400"),
+    // energy
+    ("J", "joule", "energy", "1"),
+    ("kW.h", "kilowatt hour", "energy", "3600000"),
+];
+
+/// Retrieve the definition of a standard unit by its UCUM symbol
+pub fn get_builtin_unit(symbol: &str) -> Option<CreateRequest> {
+    BUILTIN_UNITS.iter()
+        .find(|(s, _, _, _)| *s == symbol)
+        .map(|(symbol, label, dimension, factor)| CreateRequest {
+            label: label.to_string(),
+            symbol: symbol.to_string(),
+            dimension: MaybeUndefined::Some(dimension.to_string()),
+            conversion_factor: MaybeUndefined::Some(factor.parse().expect("invalid builtin unit conversion factor")),
+        })
+}
@@ -8,7 +8,7 @@
  */
 use hdk::prelude::*;
 use hdk_records::{
-    RecordAPIResult,
+    RecordAPIResult, DataIntegrityError, SemanticIndexError,
     records_anchored::{
         create_anchored_record,
         read_anchored_record_entry,
@@ -31,6 +31,8 @@ pub use hc_zome_rea_unit_storage_consts::*;
 use hc_zome_rea_unit_storage::*;
 use hc_zome_rea_unit_rpc::*;
 
+mod builtins;
+pub use builtins::get_builtin_unit;
 
 /// properties accessor for zome config
 fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
@@ -50,6 +52,55 @@ pub fn handle_create_unit<S>(entry_def_id: S, unit: CreateRequest) -> RecordAPIR
     construct_response(&entry_id, &meta, &entry_resp)
 }
 
+/// Creates any standard units listed in the `seed_units` zome config which do not yet exist.
+/// Seeding is opt-in, and no units are created unless the DNA lists them.
+///
+/// Invalid configuration, including symbols with no standard unit definition, is an error.
+/// Units which cannot be written are logged and skipped, so that network conditions never
+/// prevent an agent from joining.
+///
+/// :TODO: agents joining simultaneously may both create a unit before seeing the other's
+/// anchor, in which case the first anchor link returned determines the canonical record.
+///
+pub fn handle_seed_units<S>(entry_def_id: S) -> RecordAPIResult<Vec<UnitId>>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    let conf: DnaConfigSlice = dna_info()?.properties.try_into()?;
+    let units = conf.unit.seed_units.iter()
+        .map(|symbol| get_builtin_unit(symbol).ok_or_else(|| DataIntegrityError::UnknownBuiltinUnit(symbol.to_owned())))
+        .collect::<RecordAPIResult<Vec<CreateRequest>>>()?;
+    let mut created = vec![];
+
+    for unit in units {
+        let symbol = unit.get_symbol().to_owned();
+        match seed_unit(entry_def_id.as_ref(), unit) {
+            Ok(Some(unit_id)) => created.push(unit_id),
+            Ok(None) => (),
+            Err(e) => debug!("skipped seeding unit '{}': {}", symbol, e),
+        }
+    }
+
+    Ok(created)
+}
+
+/// Create the standard `unit`, unless one with the same symbol is already present
+fn seed_unit(entry_def_id: &str, unit: CreateRequest) -> RecordAPIResult<Option<UnitId>> {
+    let existing = read_anchored_record_entry::<LinkTypes, EntryData, EntryStorage, UnitInternalAddress, UnitId, _>(
+        LinkTypes::UnitIdentifier,
+        unit.get_symbol(),
+    );
+    match existing {
+        Ok(_) => return Ok(None),
+        // no anchor has been written for the symbol, so the unit has never been created
+        Err(DataIntegrityError::SemanticIndexingError(SemanticIndexError::IndexNotFound(_))) => (),
+        // any other failure may be a unit which exists but could not be retrieved
+        Err(e) => return Err(e),
+    }
+
+    let resp = handle_create_unit(entry_def_id, unit)?;
+    Ok(Some(resp.unit.id))
+}
+
 pub fn handle_get_unit(id: UnitId) -> RecordAPIResult<ResponseData>
 {
     let id_str: &String = id.as_ref();
@@ -101,6 +152,8 @@ fn construct_response<'a>(
             meta: read_revision_metadata_abbreviated(meta)?,
             label: e.label.to_owned(),
             symbol: e.symbol.to_owned(),
+            dimension: e.dimension.to_owned(),
+            conversion_factor: e.conversion_factor.to_owned(),
         }
     })
 }
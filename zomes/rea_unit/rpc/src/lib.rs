@@ -12,6 +12,7 @@ use hdk_records::{
     MaybeUndefined, RecordAPIResult,
     record_interface::{ UniquelyIdentifiable, UpdateableIdentifier },
 };
+use vf_measurement::{ Decimal, UnitConversion };
 
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
 
@@ -33,9 +34,24 @@ pub struct Response {
     pub meta: RecordMeta,
     pub label: String,
     pub symbol: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimension: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversion_factor: Option<Decimal>,
 }
 
 impl<'a> Response {
+    /// Conversion parameters for this unit, if it has been registered with a dimension
+    pub fn get_conversion(&'a self) -> Option<UnitConversion> {
+        match (&self.dimension, &self.conversion_factor) {
+            (Some(dimension), Some(conversion_factor)) => Some(UnitConversion {
+                dimension: dimension.to_owned(),
+                conversion_factor: conversion_factor.to_owned(),
+            }),
+            _ => None,
+        }
+    }
+
     pub fn into_cursor(&'a self) -> Result<String, std::string::FromUtf8Error> {
         let s: &String = self.id.as_ref();
         Ok(s.to_owned())
@@ -61,6 +77,12 @@ pub struct ResponseData {
 pub struct CreateRequest {
     pub label: String,
     pub symbol: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub dimension: MaybeUndefined<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub conversion_factor: MaybeUndefined<Decimal>,
 }
 
 impl<'a> CreateRequest {
@@ -85,6 +107,12 @@ pub struct UpdateRequest {
     pub revision_id: ActionHash,
     pub label: MaybeUndefined<String>,
     pub symbol: MaybeUndefined<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub dimension: MaybeUndefined<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub conversion_factor: MaybeUndefined<Decimal>,
 }
 
 impl<'a> UpdateRequest {
@@ -10,12 +10,13 @@ use hdk::prelude::*;
 
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
 use hdk_records::{
-    RecordAPIResult, DataIntegrityError,
+    RecordAPIResult, DataIntegrityError, MaybeUndefined,
     generate_record_entry,
     record_interface::{ Updateable },
 };
 
 use hc_zome_rea_unit_rpc::{ CreateRequest, UpdateRequest };
+use vf_measurement::Decimal;
 
 pub use vf_attributes_hdk::{ UnitInternalAddress };
 
@@ -33,6 +34,9 @@ pub struct DnaConfigSlice {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
 pub struct UnitZomeConfig {
     pub index_zome: String,
+    /// symbols of standard units to create when an agent joins the network
+    #[serde(default)]
+    pub seed_units: Vec<String>,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -41,12 +45,26 @@ pub struct UnitZomeConfig {
 pub struct EntryData {
     pub label: String,
     pub symbol: String,
+    #[serde(default)]
+    pub dimension: Option<String>,
+    #[serde(default)]
+    pub conversion_factor: Option<Decimal>,
 }
 
 impl<'a> EntryData {
     pub fn get_symbol(&'a self) -> String {
         self.symbol.to_owned()
     }
+
+    /// Units must declare both a dimension and a positive conversion factor in order to be convertible
+    pub fn validate(&self) -> RecordAPIResult<()> {
+        match (&self.dimension, &self.conversion_factor) {
+            (None, None) => Ok(()),
+            (Some(_), Some(factor)) if *factor > Decimal::zero() => Ok(()),
+            (Some(_), Some(factor)) => Err(DataIntegrityError::InvalidUnitConversion(format!("conversion factor must be positive, got {}", factor))),
+            _ => Err(DataIntegrityError::InvalidUnitConversion("dimension and conversion factor must be provided together".to_string())),
+        }
+    }
 }
 
 generate_record_entry!(EntryData, UnitInternalAddress, EntryStorage);
@@ -93,10 +111,14 @@ impl TryFrom<CreateRequest> for EntryData {
     type Error = DataIntegrityError;
 
     fn try_from(e: CreateRequest) -> RecordAPIResult<EntryData> {
-        Ok(EntryData {
+        let entry = EntryData {
             label: e.label.into(),
             symbol: e.symbol.into(),
-        })
+            dimension: e.dimension.to_option(),
+            conversion_factor: e.conversion_factor.to_option(),
+        };
+        entry.validate()?;
+        Ok(entry)
     }
 }
 
@@ -105,9 +127,13 @@ impl TryFrom<CreateRequest> for EntryData {
 /// Handles update operations by merging any newly provided fields
 impl Updateable<UpdateRequest> for EntryData {
     fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
-        Ok(EntryData {
+        let entry = EntryData {
             label:   if !e.label.is_some()   { self.label.to_owned()   } else { e.label.to_owned().unwrap() },
             symbol: if !e.symbol.is_some() { self.symbol.to_owned() } else { e.symbol.to_owned().unwrap() },
-        })
+            dimension: if e.dimension == MaybeUndefined::Undefined { self.dimension.to_owned() } else { e.dimension.to_owned().to_option() },
+            conversion_factor: if e.conversion_factor == MaybeUndefined::Undefined { self.conversion_factor.to_owned() } else { e.conversion_factor.to_owned().to_option() },
+        };
+        entry.validate()?;
+        Ok(entry)
     }
 }
@@ -13,6 +13,14 @@ use hc_zome_rea_unit_rpc::*;
 use hc_zome_rea_unit_lib::*;
 use vf_attributes_hdk::UnitInternalAddress;
 
+#[hdk_extern]
+fn init(_: ()) -> ExternResult<InitCallbackResult> {
+    match handle_seed_units(UNIT_ENTRY_TYPE) {
+        Ok(_) => Ok(InitCallbackResult::Pass),
+        Err(e) => Ok(InitCallbackResult::Fail(e.to_string())),
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct CreateParams {